@@ -58,11 +58,12 @@
 
 use core::{cmp::Ordering, ops::Neg};
 
-pub use parser::{str_to_dec, ParseDecimalError};
+pub use parser::{str_to_dec, str_to_udec, ParseDecimalError};
 pub use powers_of_ten::{checked_mul_pow_ten, mul_pow_ten, ten_pow};
 pub use rounding::{
     i128_div_rounded, i128_mul_div_ten_pow_rounded, i128_shifted_div_rounded,
-    Round, RoundingMode,
+    round_quot, with_rounding_mode, RandomSource, Round, RoundingMode,
+    RoundingModeGuard,
 };
 
 mod parser;
@@ -277,8 +278,11 @@ const fn u128_lo(u: u128) -> u128 {
     u & 0xffffffffffffffff
 }
 
+/// Return `(h, l)` with `h * 2^128 + l = x * y`.
+#[doc(hidden)]
 #[inline(always)]
-const fn u128_mul_u128(x: u128, y: u128) -> (u128, u128) {
+#[must_use]
+pub const fn u128_mul_u128(x: u128, y: u128) -> (u128, u128) {
     let xh = u128_hi(x);
     let xl = u128_lo(x);
     let yh = u128_hi(y);
@@ -410,6 +414,123 @@ fn u256_idiv_u128(xh: &mut u128, xl: &mut u128, y: u128) -> u128 {
     r
 }
 
+/// Return `(r, e)` with `r = floor(sqrt(x))`, where `x = xh * 2^128 + xl`,
+/// and `e = true` if `r * r = x`, otherwise `e = false`.
+#[doc(hidden)]
+#[must_use]
+pub fn u256_isqrt(xh: u128, xl: u128) -> (u128, bool) {
+    if xh == 0 && xl == 0 {
+        return (0, true);
+    }
+    // x < 2^n_bits => sqrt(x) < 2^ceil(n_bits / 2)
+    let n_bits = if xh == 0 {
+        u128_msb(xl) as u32 + 1
+    } else {
+        u128_msb(xh) as u32 + 129
+    };
+    let shift = (n_bits + 1) >> 1;
+    let mut r = if shift >= 128 {
+        u128::MAX
+    } else {
+        1_u128 << shift
+    };
+    // Newton iteration, starting above sqrt(x) and decreasing
+    // monotonically until floor(sqrt(x)) is reached.
+    loop {
+        let (mut qh, mut ql) = (xh, xl);
+        u256_idiv_u128(&mut qh, &mut ql, r);
+        if qh != 0 {
+            // x / r >= 2^128 > r
+            break;
+        }
+        // (r + q) / 2 without overflow
+        let t = (r >> 1) + (ql >> 1) + (r & ql & 1);
+        if t >= r {
+            break;
+        }
+        r = t;
+    }
+    let (sh, sl) = u128_mul_u128(r, r);
+    (r, sh == xh && sl == xl)
+}
+
+/// Return `Some((h, l))` with `h * 2^128 + l = x + y`, where
+/// `x = xh * 2^128 + xl` and `y = yh * 2^128 + yl`, or `None` if the sum
+/// does not fit into 256 bits.
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub const fn u256_checked_add(
+    xh: u128,
+    xl: u128,
+    yh: u128,
+    yl: u128,
+) -> Option<(u128, u128)> {
+    let (l, carry) = xl.overflowing_add(yl);
+    match xh.checked_add(yh) {
+        Some(h) => match h.checked_add(carry as u128) {
+            Some(h) => Some((h, l)),
+            None => None,
+        },
+        None => None,
+    }
+}
+
+/// Return `(h, l)` with `h * 2^128 + l = x - y`, where
+/// `x = xh * 2^128 + xl` and `y = yh * 2^128 + yl`.
+/// Pre-condition: x >= y
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub const fn u256_sub(
+    xh: u128,
+    xl: u128,
+    yh: u128,
+    yl: u128,
+) -> (u128, u128) {
+    let (l, borrow) = xl.overflowing_sub(yl);
+    (xh - yh - borrow as u128, l)
+}
+
+/// Return `Some((h, l))` with `h * 2^128 + l = x * y`, where
+/// `x = xh * 2^128 + xl`, or `None` if the product does not fit into 256
+/// bits.
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub const fn u256_checked_mul_u128(
+    xh: u128,
+    xl: u128,
+    y: u128,
+) -> Option<(u128, u128)> {
+    let (th, tl) = u128_mul_u128(xl, y);
+    let (uh, ul) = u128_mul_u128(xh, y);
+    if uh != 0 {
+        return None;
+    }
+    match ul.checked_add(th) {
+        Some(h) => Some((h, tl)),
+        None => None,
+    }
+}
+
+/// Return `((qh, ql), r)` with `qh * 2^128 + ql = x / y` and `r = x % y`,
+/// where `x = xh * 2^128 + xl`.
+/// Pre-condition: y != 0
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub fn u256_div_rem_u128(
+    xh: u128,
+    xl: u128,
+    y: u128,
+) -> ((u128, u128), u128) {
+    debug_assert_ne!(y, 0);
+    let (mut qh, mut ql) = (xh, xl);
+    let r = u256_idiv_u128(&mut qh, &mut ql, y);
+    ((qh, ql), r)
+}
+
 /// Return `Some<(q, r)>` with `q = (x * 10^p) / y` and `r = (x * 10^p) % y`,
 /// so that `(x * 10^p) = q * y + r`, where q is rounded against floor so that
 /// r, if non-zero, has the same sign as y and `0 <= abs(r) < abs(y)`, or
@@ -221,6 +221,35 @@ impl<'a> AsciiDecLit<'a> {
 /// `[+|-].<frac>[<e|E>[+|-]<exp>]`.
 #[doc(hidden)]
 pub fn str_to_dec(lit: &str) -> Result<(i128, isize), ParseDecimalError> {
+    let (is_negative, coeff, exp) = parse_dec_lit(lit, i128::MAX as u128)?;
+    if is_negative {
+        Ok((-(coeff as i128), exp))
+    } else {
+        Ok((coeff as i128, exp))
+    }
+}
+
+/// Convert a non-negative decimal number literal into a representation in
+/// the form (coefficient, exponent), so that
+/// number == coefficient * 10 ^ exponent.
+///
+/// The literal must have the same form as accepted by [`str_to_dec`]. A
+/// negative sign is only accepted for a value equal to zero.
+#[doc(hidden)]
+pub fn str_to_udec(lit: &str) -> Result<(u128, isize), ParseDecimalError> {
+    let (is_negative, coeff, exp) = parse_dec_lit(lit, u128::MAX)?;
+    if is_negative && coeff != 0 {
+        return Err(ParseDecimalError::Invalid);
+    }
+    Ok((coeff, exp))
+}
+
+// Returns (is_negative, coefficient, exponent) or an error. The coefficient
+// must not exceed `max_coeff`.
+fn parse_dec_lit(
+    lit: &str,
+    max_coeff: u128,
+) -> Result<(bool, u128, isize), ParseDecimalError> {
     let mut lit = AsciiDecLit::new(lit.as_ref());
     let is_negative = match lit.first() {
         None => {
@@ -244,8 +273,13 @@ pub fn str_to_dec(lit: &str) -> Result<(i128, isize), ParseDecimalError> {
     lit.skip_leading_zeroes();
     if lit.is_empty() {
         // There must have been atleast one zero. Ignore sign.
-        return Ok((0, 0));
+        return Ok((false, 0, 0));
     }
+    // Leading digit of the coefficient, needed to detect an overflow below.
+    let lead_digit = match lit.bytes {
+        [b'.', d, ..] | [d, ..] => d.wrapping_sub(b'0'),
+        [] => 0,
+    };
     let mut coeff = 0_u128;
     // Parse integral digits.
     let n_int_digits = lit.accum_coeff(&mut coeff);
@@ -263,13 +297,17 @@ pub fn str_to_dec(lit: &str) -> Result<(i128, isize), ParseDecimalError> {
         return Err(ParseDecimalError::Invalid);
     }
     // check for overflow
-    // 1. 10^e > i128::MAX for e > 39
-    // 2. e = 39 && coeff < 10³⁸ (overflow occured during accumulation)
-    // 3. coeff > i128::MAX
+    // 1. 10^e > u128::MAX for e > 39
+    // 2. e = 39 && (lead digit > 3 || lead digit = 3 && coeff < 3 * 10³⁸)
+    //    (overflow occured during accumulation, because
+    //    3 * 10³⁸ < u128::MAX < 4 * 10³⁸ and u128::MAX < 2 * 3 * 10³⁸)
+    // 3. coeff > max_coeff
     if n_digits > 39
         || n_digits == 39
-            && coeff < 100000000000000000000000000000000000000_u128
-        || coeff > i128::MAX as u128
+            && (lead_digit > 3
+                || lead_digit == 3
+                    && coeff < 300000000000000000000000000000000000000_u128)
+        || coeff > max_coeff
     {
         return Err(ParseDecimalError::InternalOverflow);
     }
@@ -313,11 +351,7 @@ pub fn str_to_dec(lit: &str) -> Result<(i128, isize), ParseDecimalError> {
     if -exp > crate::MAX_N_FRAC_DIGITS as isize {
         return Err(ParseDecimalError::FracDigitLimitExceeded);
     }
-    if is_negative {
-        Ok((-(coeff as i128), exp))
-    } else {
-        Ok((coeff as i128, exp))
-    }
+    Ok((is_negative, coeff, exp))
 }
 
 #[cfg(test)]
@@ -411,6 +445,40 @@ mod tests {
         assert_eq!(err, ParseDecimalError::InternalOverflow);
     }
 
+    #[test]
+    fn test_int_lit_accum_overflow() {
+        let s = "500000000000000000000000000000000000000";
+        let res = str_to_dec(s);
+        assert_eq!(res.unwrap_err(), ParseDecimalError::InternalOverflow);
+        let res = str_to_udec(s);
+        assert_eq!(res.unwrap_err(), ParseDecimalError::InternalOverflow);
+        for s in [
+            "399999999999999999999999999999999999999",
+            "3999999999999999999999999999999999999.99",
+            "999999999999999999999999999999999999999",
+        ] {
+            let res = str_to_udec(s);
+            assert_eq!(res.unwrap_err(), ParseDecimalError::InternalOverflow);
+        }
+        let s = "300000000000000000000000000000000000000";
+        assert_eq!(str_to_udec(s).unwrap(), (3 * 10_u128.pow(38), 0));
+    }
+
+    #[test]
+    fn test_parse_unsigned_lit() {
+        let s = "340282366920938463463374607431768211455";
+        assert_eq!(str_to_udec(s).unwrap(), (u128::MAX, 0));
+        let s = "34028236692093846346337460743176821.1455";
+        assert_eq!(str_to_udec(s).unwrap(), (u128::MAX, -4));
+        let s = "340282366920938463463374607431768211456";
+        let res = str_to_udec(s);
+        assert_eq!(res.unwrap_err(), ParseDecimalError::InternalOverflow);
+        assert_eq!(str_to_udec("+17.5e-1").unwrap(), (175, -2));
+        assert_eq!(str_to_udec("-0.00").unwrap(), (0, -2));
+        let res = str_to_udec("-0.01");
+        assert_eq!(res.unwrap_err(), ParseDecimalError::Invalid);
+    }
+
     #[test]
     fn test_dec_lit_max_val_exceeded() {
         let s = "1701411834604692317316873037158841058.00";
@@ -7,8 +7,16 @@
 // $Source$
 // $Revision$
 
+use core::marker::PhantomData;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+#[cfg(feature = "std")]
+use core::{
+    cell::{Cell, RefCell},
+    hash::{BuildHasher, Hasher},
+};
 #[cfg(feature = "std")]
-use core::cell::RefCell;
+use std::collections::hash_map::RandomState;
 
 use crate::{
     i128_div_mod_floor, i128_shifted_div_mod_floor, i256_div_mod_floor,
@@ -17,6 +25,7 @@ use crate::{
 
 /// Enum representing the different methods used when rounding a number.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum RoundingMode {
     /// Round away from zero if last digit after rounding towards zero would
     /// have been 0 or 5; otherwise round towards zero.
@@ -31,23 +40,114 @@ pub enum RoundingMode {
     RoundHalfDown,
     /// Round to nearest with ties going to nearest even integer.
     RoundHalfEven,
+    /// Round to nearest with ties going to nearest odd integer.
+    RoundHalfOdd,
     /// Round to nearest with ties going away from zero.
     RoundHalfUp,
+    /// Round towards Infinity with a probability equal to the fraction to
+    /// be discarded, otherwise towards -Infinity.
+    ///
+    /// The random numbers are taken from the source set via
+    /// `RoundingMode::set_random_source` or, if none has been set, from a
+    /// builtin pseudo-random number generator. Without feature `std` only
+    /// the builtin generator is available, which can be seeded via
+    /// `RoundingMode::set_random_seed`.
+    RoundStochastic,
+    /// Round towards zero, unless the last digit would then be even; in
+    /// that case round away from zero (aka sticky rounding).
+    ///
+    /// Rounding to odd with at least two more digits than finally needed,
+    /// followed by rounding with one of the other modes (except
+    /// `RoundStochastic`), gives the same result as rounding directly with
+    /// that mode.
+    RoundToOdd,
     /// Round away from zero.
     RoundUp,
 }
 
+impl RoundingMode {
+    /// The RoundingMode used as default if no other mode has been set via
+    /// [RoundingMode::set_default].
+    ///
+    /// It is [RoundingMode::RoundHalfEven], unless the environment variable
+    /// `FPDEC_DEFAULT_ROUNDING_MODE` is set to the name of one of the
+    /// variants of `RoundingMode` (for example `RoundHalfUp`) when
+    /// compiling this crate.
+    pub const INITIAL_DEFAULT: Self =
+        match option_env!("FPDEC_DEFAULT_ROUNDING_MODE") {
+            Some(name) => Self::from_name(name),
+            None => Self::RoundHalfEven,
+        };
+
+    const ALL: [Self; 11] = [
+        Self::Round05Up,
+        Self::RoundCeiling,
+        Self::RoundDown,
+        Self::RoundFloor,
+        Self::RoundHalfDown,
+        Self::RoundHalfEven,
+        Self::RoundHalfOdd,
+        Self::RoundHalfUp,
+        Self::RoundStochastic,
+        Self::RoundToOdd,
+        Self::RoundUp,
+    ];
+
+    const NAMES: [&'static str; 11] = [
+        "Round05Up",
+        "RoundCeiling",
+        "RoundDown",
+        "RoundFloor",
+        "RoundHalfDown",
+        "RoundHalfEven",
+        "RoundHalfOdd",
+        "RoundHalfUp",
+        "RoundStochastic",
+        "RoundToOdd",
+        "RoundUp",
+    ];
+
+    // Returns the variant with the given name. Panics (i.e. fails to
+    // compile, when evaluated in a const context) if there is no such
+    // variant.
+    const fn from_name(name: &str) -> Self {
+        let mut idx = 0;
+        while idx < Self::NAMES.len() {
+            if str_eq(name, Self::NAMES[idx]) {
+                return Self::ALL[idx];
+            }
+            idx += 1;
+        }
+        panic!("Invalid value of FPDEC_DEFAULT_ROUNDING_MODE.");
+    }
+}
+
+const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < lhs.len() {
+        if lhs[idx] != rhs[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
 #[cfg(feature = "std")]
 thread_local!(
     static DFLT_ROUNDING_MODE: RefCell<RoundingMode> =
-        RefCell::new(RoundingMode::RoundHalfEven)
+        const { RefCell::new(RoundingMode::INITIAL_DEFAULT) }
 );
 
 #[cfg(feature = "std")]
 impl Default for RoundingMode {
     /// Returns the default RoundingMode set for the current thread.
     ///
-    /// It is initially set to [RoundingMode::RoundHalfEven], but can be
+    /// It is initially set to [RoundingMode::INITIAL_DEFAULT], but can be
     /// changed using the fn [RoundingMode::set_default].
     fn default() -> Self {
         DFLT_ROUNDING_MODE.with(|m| *m.borrow())
@@ -62,20 +162,220 @@ impl RoundingMode {
     }
 }
 
+/// Guard which sets the default [RoundingMode] for the current thread and
+/// restores the previous one when dropped.
+///
+/// Without feature `std` the guard sets the global default RoundingMode
+/// (see [RoundingMode::set_default]).
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec_core::{RoundingMode, RoundingModeGuard};
+/// assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+/// {
+///     let _guard = RoundingModeGuard::new(RoundingMode::RoundUp);
+///     assert_eq!(RoundingMode::default(), RoundingMode::RoundUp);
+/// }
+/// assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+/// ```
+#[must_use = "the previous rounding mode is restored when the guard is \
+              dropped"]
+#[derive(Debug)]
+pub struct RoundingModeGuard {
+    prev_mode: RoundingMode,
+    // The guard refers to a thread local setting, so it must not be sent
+    // to another thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl RoundingModeGuard {
+    /// Sets the default RoundingMode for the current thread to `mode`,
+    /// until the returned guard is dropped.
+    pub fn new(mode: RoundingMode) -> Self {
+        let prev_mode = RoundingMode::default();
+        RoundingMode::set_default(mode);
+        Self {
+            prev_mode,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for RoundingModeGuard {
+    fn drop(&mut self) {
+        RoundingMode::set_default(self.prev_mode);
+    }
+}
+
+/// Calls `f` with the default [RoundingMode] for the current thread set to
+/// `mode`, and restores the previous default afterwards (even if `f`
+/// panics).
+///
+/// Without feature `std` there are no thread locals, so this temporarily
+/// sets the global default RoundingMode (see [RoundingMode::set_default]).
+/// While `f` runs, `mode` then also applies to other threads and to
+/// interrupt handlers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec_core::{with_rounding_mode, RoundingMode};
+/// let mode = with_rounding_mode(RoundingMode::RoundFloor, || {
+///     RoundingMode::default()
+/// });
+/// assert_eq!(mode, RoundingMode::RoundFloor);
+/// assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+/// ```
+pub fn with_rounding_mode<R, F>(mode: RoundingMode, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = RoundingModeGuard::new(mode);
+    f()
+}
+
+// Without `std` there are no thread locals, so the default RoundingMode is
+// a global setting, held as index into `RoundingMode::ALL`.
 #[cfg(not(feature = "std"))]
-static DFLT_ROUNDING_MODE: RoundingMode = RoundingMode::RoundHalfEven;
+static DFLT_ROUNDING_MODE: AtomicU8 =
+    AtomicU8::new(RoundingMode::INITIAL_DEFAULT as u8);
 
 #[cfg(not(feature = "std"))]
 impl Default for RoundingMode {
     /// Returns the current default RoundingMode.
     ///
-    /// It is initially set to [RoundingMode::RoundHalfEven], but can be
+    /// It is initially set to [RoundingMode::INITIAL_DEFAULT], but can be
     /// changed using the fn [RoundingMode::set_default].
     fn default() -> Self {
-        DFLT_ROUNDING_MODE
+        Self::ALL[DFLT_ROUNDING_MODE.load(Ordering::Relaxed) as usize]
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl RoundingMode {
+    /// Sets the global default RoundingMode.
+    ///
+    /// Note that without feature `std` this setting is shared by all
+    /// threads of execution (including interrupt handlers).
+    pub fn set_default(mode: Self) {
+        DFLT_ROUNDING_MODE.store(mode as u8, Ordering::Relaxed);
     }
 }
 
+/// Function returning uniformly distributed random numbers, to be used as
+/// source for [RoundingMode::RoundStochastic].
+pub type RandomSource = fn() -> u64;
+
+// Simple xorshift pseudo-random number generators, used if no
+// `RandomSource` has been set.
+const fn xorshift64(mut x: u64) -> u64 {
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
+const fn xorshift32(mut x: u32) -> u32 {
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    x
+}
+
+#[cfg(feature = "std")]
+thread_local!(
+    static RANDOM_SOURCE: Cell<Option<RandomSource>> =
+        const { Cell::new(None) };
+    static RNG_STATE: Cell<u64> =
+        Cell::new(RandomState::new().build_hasher().finish() | 1);
+);
+
+#[cfg(feature = "std")]
+impl RoundingMode {
+    /// Sets the source of random numbers used by
+    /// [RoundingMode::RoundStochastic] for the current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec_core::{i128_div_rounded, RoundingMode};
+    /// // always returns 0, i.e. always rounds up
+    /// RoundingMode::set_random_source(|| 0);
+    /// let mode = Some(RoundingMode::RoundStochastic);
+    /// assert_eq!(i128_div_rounded(-701, 100, mode), -7);
+    /// RoundingMode::reset_random_source();
+    /// ```
+    pub fn set_random_source(source: RandomSource) {
+        RANDOM_SOURCE.with(|s| s.set(Some(source)));
+    }
+
+    /// Resets the source of random numbers used by
+    /// [RoundingMode::RoundStochastic] for the current thread to the
+    /// builtin pseudo-random number generator.
+    pub fn reset_random_source() {
+        RANDOM_SOURCE.with(|s| s.set(None));
+    }
+}
+
+#[cfg(feature = "std")]
+fn next_random() -> u64 {
+    match RANDOM_SOURCE.with(Cell::get) {
+        Some(source) => source(),
+        None => RNG_STATE.with(|state| {
+            let x = xorshift64(state.get());
+            state.set(x);
+            x
+        }),
+    }
+}
+
+// Without `std` there is no safe way to hold a custom `RandomSource` in a
+// global, so only the builtin generator is available, which can be seeded.
+#[cfg(not(feature = "std"))]
+const RNG_INITIAL_STATE: u32 = 0x9e37_79b9;
+
+#[cfg(not(feature = "std"))]
+static RNG_STATE: AtomicU32 = AtomicU32::new(RNG_INITIAL_STATE);
+
+#[cfg(not(feature = "std"))]
+impl RoundingMode {
+    /// Seeds the global pseudo-random number generator used by
+    /// [RoundingMode::RoundStochastic].
+    ///
+    /// Without feature `std` a custom [RandomSource] can not be set, and
+    /// the builtin generator is seeded with a constant value unless this fn
+    /// is called.
+    pub fn set_random_seed(seed: u32) {
+        // xorshift gets stuck at zero
+        let state = if seed == 0 { RNG_INITIAL_STATE } else { seed };
+        RNG_STATE.store(state, Ordering::Relaxed);
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn next_random() -> u64 {
+    // Only atomic loads and stores are used, because they are available on
+    // more targets than compare-and-swap. Concurrent calls may therefore
+    // give the same number, which is acceptable for the purpose of
+    // stochastic rounding.
+    let hi = xorshift32(RNG_STATE.load(Ordering::Relaxed));
+    let lo = xorshift32(hi);
+    RNG_STATE.store(lo, Ordering::Relaxed);
+    (u64::from(hi) << 32) | u64::from(lo)
+}
+
+// Returns true with probability `rem` / `divisor`.
+// Pre-condition: 0 < rem < divisor
+#[allow(clippy::integer_division)]
+fn stochastic_round_up(rem: u128, divisor: u128) -> bool {
+    // Scale rem / divisor to a 64-bit fraction, dropping the lowest bits of
+    // both values if divisor has more than 64 bits.
+    let shift = 64_u32.saturating_sub(divisor.leading_zeros());
+    let threshold = ((rem >> shift) << 64) / (divisor >> shift);
+    u128::from(next_random()) < threshold
+}
+
 /// Rounding a number to a given number of fractional digits.
 pub trait Round
 where
@@ -91,14 +391,47 @@ where
     /// `RoundingMode`, wrapped in `Option::Some`, or `Option::None` if
     /// the result can not be represented by `Self`.
     fn checked_round(self, n_frac_digits: i8) -> Option<Self>;
+
+    /// Returns a new `Self` instance with its value rounded to
+    /// `n_frac_digits` fractional digits according to the given
+    /// [RoundingMode].
+    ///
+    /// The default implementation calls [Round::round] with the default
+    /// RoundingMode temporarily set to `mode` (see [with_rounding_mode]).
+    /// Without feature `std` that setting is global, so implementations
+    /// used concurrently should override this method.
+    #[inline]
+    fn round_with_mode(self, n_frac_digits: i8, mode: RoundingMode) -> Self {
+        with_rounding_mode(mode, || self.round(n_frac_digits))
+    }
+
+    /// Returns a new `Self` instance with its value rounded to
+    /// `n_frac_digits` fractional digits according to the given
+    /// `RoundingMode`, wrapped in `Option::Some`, or `Option::None` if the
+    /// result can not be represented by `Self`.
+    ///
+    /// The default implementation calls [Round::checked_round] with the
+    /// default RoundingMode temporarily set to `mode` (see
+    /// [with_rounding_mode]). Like for [Round::round_with_mode], that
+    /// setting is global without feature `std`.
+    #[inline]
+    fn checked_round_with_mode(
+        self,
+        n_frac_digits: i8,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        with_rounding_mode(mode, || self.checked_round(n_frac_digits))
+    }
 }
 
 // rounding helper
 
-// Round `quot` according to `mode` based on `rem` and `divisor`.
-// Pre-condition: 0 < divisor and rem <= divisor
+/// Round `quot` according to `mode` based on `rem` and `divisor`.
+/// Pre-condition: 0 < divisor and rem <= divisor
+#[doc(hidden)]
 #[inline]
-fn round_quot(
+#[must_use]
+pub fn round_quot(
     quot: i128,
     rem: u128,
     divisor: u128,
@@ -163,6 +496,18 @@ fn round_quot(
                 return quot + 1;
             }
         }
+        RoundingMode::RoundHalfOdd => {
+            // Round 5 to nearest odd, rest to nearest:
+            // remainder > |divisor| / 2 or
+            // remainder = |divisor| / 2 and quotient even
+            // => add 1
+            let rem_doubled = rem << 1;
+            if rem_doubled > divisor
+                || rem_doubled == divisor && quot % 2 == 0
+            {
+                return quot + 1;
+            }
+        }
         RoundingMode::RoundHalfUp => {
             // Round 5 up (away from 0), rest to nearest:
             // remainder > |divisor| / 2 or
@@ -173,6 +518,23 @@ fn round_quot(
                 return quot + 1;
             }
         }
+        RoundingMode::RoundStochastic => {
+            // Round towards Infinity with probability remainder / |divisor|
+            // => add 1
+            if stochastic_round_up(rem, divisor) {
+                return quot + 1;
+            }
+            return quot;
+        }
+        RoundingMode::RoundToOdd => {
+            // Round towards 0 unless that gives an even quotient:
+            // quotient not negative and even or
+            // quotient negative and (quotient + 1) odd
+            // => add 1
+            if quot >= 0 && quot % 2 == 0 || quot < 0 && (quot + 1) % 2 != 0 {
+                return quot + 1;
+            }
+        }
         RoundingMode::RoundUp => {
             // Round away from 0:
             // quotient not negative => add 1
@@ -258,13 +620,168 @@ mod rounding_mode_tests {
         RoundingMode::set_default(RoundingMode::RoundHalfEven);
         assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
     }
+
+    #[test]
+    fn test_guard() {
+        assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+        {
+            let _guard = RoundingModeGuard::new(RoundingMode::RoundDown);
+            assert_eq!(RoundingMode::default(), RoundingMode::RoundDown);
+            {
+                let _guard = RoundingModeGuard::new(RoundingMode::RoundUp);
+                assert_eq!(RoundingMode::default(), RoundingMode::RoundUp);
+            }
+            assert_eq!(RoundingMode::default(), RoundingMode::RoundDown);
+        }
+        assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+    }
+
+    #[test]
+    fn test_with_rounding_mode() {
+        let res = with_rounding_mode(RoundingMode::RoundCeiling, || {
+            i128_div_rounded(-7, 2, None)
+        });
+        assert_eq!(res, -3);
+        assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+        let res = std::panic::catch_unwind(|| {
+            with_rounding_mode(RoundingMode::RoundUp, || panic!());
+        });
+        assert!(res.is_err());
+        assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+    }
+
+    // Implements only the required methods of trait Round.
+    #[derive(Debug, PartialEq)]
+    struct Tenths(i128);
+
+    impl Round for Tenths {
+        fn round(self, n_frac_digits: i8) -> Self {
+            self.checked_round(n_frac_digits).unwrap()
+        }
+
+        fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+            match n_frac_digits {
+                0 => Some(Self(i128_div_rounded(self.0, 10, None) * 10)),
+                1.. => Some(self),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_provided_round_with_mode() {
+        assert_eq!(Tenths(-25).round(0), Tenths(-20));
+        assert_eq!(
+            Tenths(-25).round_with_mode(0, RoundingMode::RoundHalfUp),
+            Tenths(-30)
+        );
+        assert_eq!(
+            Tenths(21).checked_round_with_mode(0, RoundingMode::RoundUp),
+            Some(Tenths(30))
+        );
+        assert_eq!(
+            Tenths(21).checked_round_with_mode(-1, RoundingMode::RoundUp),
+            None
+        );
+        assert_eq!(RoundingMode::default(), RoundingMode::RoundHalfEven);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod stochastic_rounding_tests {
+    use super::*;
+
+    #[test]
+    fn test_random_source() {
+        let mode = Some(RoundingMode::RoundStochastic);
+        RoundingMode::set_random_source(|| 0);
+        assert_eq!(i128_div_rounded(1, 1000, mode), 1);
+        assert_eq!(i128_div_rounded(-1999, 1000, mode), -1);
+        assert_eq!(i128_div_rounded(2000, 1000, mode), 2);
+        RoundingMode::set_random_source(|| u64::MAX);
+        assert_eq!(i128_div_rounded(999, 1000, mode), 0);
+        assert_eq!(i128_div_rounded(-1, 1000, mode), -1);
+        assert_eq!(i128_div_rounded(i128::MAX, i128::MAX - 1, mode), 1);
+        RoundingMode::set_random_source(|| 1 << 63);
+        assert_eq!(i128_div_rounded(49, 100, mode), 0);
+        assert_eq!(i128_div_rounded(51, 100, mode), 1);
+        assert_eq!(i128_div_rounded(-51, 100, mode), -1);
+        assert_eq!(i128_div_rounded(-49, 100, mode), 0);
+        RoundingMode::reset_random_source();
+    }
+
+    #[test]
+    fn test_distribution() {
+        let mode = Some(RoundingMode::RoundStochastic);
+        let n: i128 = (0..10000).map(|_| i128_div_rounded(3, 10, mode)).sum();
+        assert!((2700..=3300).contains(&n));
+        let n: i128 =
+            (0..10000).map(|_| i128_div_rounded(-3, 10, mode)).sum();
+        assert!((-3300..=-2700).contains(&n));
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[cfg(test)]
+mod stochastic_rounding_tests {
+    use super::*;
+
+    #[test]
+    fn test_random_seed() {
+        let mode = Some(RoundingMode::RoundStochastic);
+        let mut res = [[0_i128; 64]; 2];
+        for r in &mut res {
+            RoundingMode::set_random_seed(17);
+            for q in r.iter_mut() {
+                *q = i128_div_rounded(-5, 10, mode);
+            }
+        }
+        assert_eq!(res[0], res[1]);
+        assert!(res[0].contains(&0));
+        assert!(res[0].contains(&-1));
+        RoundingMode::set_random_seed(0);
+        assert_ne!(RNG_STATE.load(Ordering::Relaxed), 0);
+    }
+}
+
+#[cfg(test)]
+mod initial_default_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        for (idx, mode) in RoundingMode::ALL.iter().enumerate() {
+            assert_eq!(*mode as usize, idx);
+            assert_eq!(
+                RoundingMode::from_name(RoundingMode::NAMES[idx]),
+                *mode
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_invalid_name() {
+        let _ = RoundingMode::from_name("RoundHalf");
+    }
+
+    #[test]
+    fn test_initial_default() {
+        let mode = match option_env!("FPDEC_DEFAULT_ROUNDING_MODE") {
+            Some(name) => RoundingMode::from_name(name),
+            None => RoundingMode::RoundHalfEven,
+        };
+        assert_eq!(RoundingMode::INITIAL_DEFAULT, mode);
+        assert_eq!(RoundingMode::default(), mode);
+    }
 }
 
 #[cfg(test)]
 mod helper_tests {
     use super::*;
 
-    const TESTDATA: [(i128, i128, RoundingMode, i128); 34] = [
+    const TESTDATA: [(i128, i128, RoundingMode, i128); 44] = [
         (17, 5, RoundingMode::Round05Up, 3),
         (27, 5, RoundingMode::Round05Up, 6),
         (-17, 5, RoundingMode::Round05Up, -3),
@@ -295,10 +812,20 @@ mod helper_tests {
             RoundingMode::RoundHalfEven,
             0,
         ),
+        (19, 2, RoundingMode::RoundHalfOdd, 9),
+        (17, 2, RoundingMode::RoundHalfOdd, 9),
+        (15, 4, RoundingMode::RoundHalfOdd, 4),
+        (-25, 10, RoundingMode::RoundHalfOdd, -3),
+        (-21, 2, RoundingMode::RoundHalfOdd, -11),
         (19, 2, RoundingMode::RoundHalfUp, 10),
         (10802, 4321, RoundingMode::RoundHalfUp, 2),
         (-19, 2, RoundingMode::RoundHalfUp, -10),
         (-10802, 4321, RoundingMode::RoundHalfUp, -2),
+        (17, 5, RoundingMode::RoundToOdd, 3),
+        (21, 5, RoundingMode::RoundToOdd, 5),
+        (20, 5, RoundingMode::RoundToOdd, 4),
+        (-17, 5, RoundingMode::RoundToOdd, -3),
+        (-21, 5, RoundingMode::RoundToOdd, -5),
         (19, 2, RoundingMode::RoundUp, 10),
         (10802, 4321, RoundingMode::RoundUp, 3),
         (-19, 2, RoundingMode::RoundUp, -10),
@@ -0,0 +1,407 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::{vec, vec::Vec};
+use core::cmp::max;
+
+use fpdec_core::{
+    checked_mul_pow_ten, u128_mul_u128, u256_div_rem_u128, u256_sub,
+    MAX_N_FRAC_DIGITS,
+};
+
+use crate::{Decimal, DecimalError};
+
+impl Decimal {
+    /// Returns `self` split into parts proportional to `weights`, each with
+    /// `n_frac_digits` fractional digits, so that the parts sum up exactly
+    /// to `self`.
+    ///
+    /// The parts are determined by the largest remainder method: each part
+    /// first gets its exact share rounded towards zero, then the remaining
+    /// units (of 10^-`n_frac_digits`) are handed out one by one to the
+    /// parts with the largest remainders (and, for equal remainders, to the
+    /// ones coming first).
+    ///
+    /// If `self` has more than `n_frac_digits` fractional digits, the parts
+    /// get as many fractional digits as `self`, because otherwise they
+    /// could not sum up to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS], if any weight
+    /// is negative, if the weights sum up to zero or if the weights or the
+    /// parts can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let parts = Dec!(100).allocate(&[Dec!(1), Dec!(1), Dec!(1)], 2);
+    /// assert_eq!(parts, [Dec!(33.34), Dec!(33.33), Dec!(33.33)]);
+    /// let parts = Dec!(0.05).allocate(&[Dec!(0.3), Dec!(0.7)], 2);
+    /// assert_eq!(parts, [Dec!(0.02), Dec!(0.03)]);
+    /// ```
+    #[must_use]
+    pub fn allocate(self, weights: &[Self], n_frac_digits: u8) -> Vec<Self> {
+        let mut parts = vec![Self::ZERO; weights.len()];
+        match self.allocate_into(weights, n_frac_digits, &mut parts) {
+            Ok(()) => parts,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Writes `self` split into parts proportional to `weights` into
+    /// `parts`, in the same way as [Decimal::allocate], but without
+    /// allocating memory.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::NotANumber` if any weight is negative,
+    /// * `DecimalError::DivisionByZero` if the weights sum up to zero,
+    /// * `DecimalError::InternalOverflow` if the weights or the parts can
+    ///   not be represented by `Decimal`.
+    ///
+    /// In case of an error the content of `parts` is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` and `weights` differ in length!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// # fn main() -> Result<(), DecimalError> {
+    /// let mut parts = [Decimal::ZERO; 3];
+    /// Dec!(-10).allocate_into(&[Dec!(2), Dec!(5), Dec!(2)], 1, &mut parts)?;
+    /// assert_eq!(parts, [Dec!(-2.2), Dec!(-5.6), Dec!(-2.2)]);
+    /// # Ok(()) }
+    /// ```
+    #[allow(clippy::cast_possible_wrap)]
+    #[allow(clippy::integer_division)]
+    pub fn allocate_into(
+        self,
+        weights: &[Self],
+        n_frac_digits: u8,
+        parts: &mut [Self],
+    ) -> Result<(), DecimalError> {
+        assert_eq!(
+            weights.len(),
+            parts.len(),
+            "Number of weights and parts differ."
+        );
+        let (total, n_frac_digits) = self.n_units(n_frac_digits)?;
+        let w_n_frac_digits =
+            weights.iter().map(|w| w.n_frac_digits).max().unwrap_or(0);
+        let weight = |w: &Self| {
+            if w.is_negative() {
+                return Err(DecimalError::NotANumber);
+            }
+            checked_mul_pow_ten(w.coeff, w_n_frac_digits - w.n_frac_digits)
+                .map(i128::unsigned_abs)
+                .ok_or(DecimalError::InternalOverflow)
+        };
+        let mut sum_weights = 0_u128;
+        for w in weights {
+            sum_weights = sum_weights
+                .checked_add(weight(w)?)
+                .ok_or(DecimalError::InternalOverflow)?;
+        }
+        if sum_weights == 0 {
+            return Err(DecimalError::DivisionByZero);
+        }
+        // Exact share: |total| * w / sum_weights = quot + rem / sum_weights
+        // with quot <= |total|.
+        let product =
+            |w: &Self| Ok(u128_mul_u128(total.unsigned_abs(), weight(w)?));
+        // First pass: compute the remainders, keeping them in `parts` until
+        // the final pass, and count the units left after handing out the
+        // quotients.
+        let mut n_units_left = total.unsigned_abs();
+        for (part, w) in parts.iter_mut().zip(weights) {
+            let (hi, lo) = product(w)?;
+            let ((_, quot), rem) = u256_div_rem_u128(hi, lo, sum_weights);
+            n_units_left -= quot;
+            part.coeff = rem as i128;
+        }
+        let rem = |part: &Self| part.coeff as u128;
+        let n_rems_at_least = |threshold: u128| {
+            parts.iter().filter(|p| rem(p) >= threshold).count() as u128
+        };
+        // Here n_units_left is less than the number of parts with a non-zero
+        // remainder. The units left go to the parts having a remainder
+        // greater than `threshold` and to the first `n_ties` parts having a
+        // remainder equal to it.
+        let (threshold, mut n_ties) = if n_units_left == 0 {
+            (u128::MAX, 0)
+        } else {
+            // Find the largest remainder which at least `n_units_left`
+            // remainders are greater than or equal to.
+            let (mut low, mut high) = (0_u128, u128::MAX);
+            while low < high {
+                let mid = high - (high - low) / 2;
+                if n_rems_at_least(mid) >= n_units_left {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+            // All remainders are less than sum_weights, so low < u128::MAX.
+            (low, n_units_left - n_rems_at_least(low + 1))
+        };
+        // Final pass: quot = (|total| * w - rem) / sum_weights
+        let unit = total.signum();
+        for (part, w) in parts.iter_mut().zip(weights) {
+            let rem = rem(part);
+            let (hi, lo) = product(w)?;
+            let (hi, lo) = u256_sub(hi, lo, 0, rem);
+            let ((_, mut quot), _) = u256_div_rem_u128(hi, lo, sum_weights);
+            if rem > threshold || rem == threshold && n_ties > 0 {
+                if rem == threshold {
+                    n_ties -= 1;
+                }
+                quot += 1;
+            }
+            *part = Self {
+                coeff: unit * quot as i128,
+                n_frac_digits,
+            };
+        }
+        Ok(())
+    }
+
+    /// Returns `self` split into `n` parts, each with `n_frac_digits`
+    /// fractional digits, which differ by at most 10^-`n_frac_digits` and
+    /// sum up exactly to `self`.
+    ///
+    /// The parts coming first get the larger magnitudes. If `self` has more
+    /// than `n_frac_digits` fractional digits, the parts get as many
+    /// fractional digits as `self`, because otherwise they could not sum up
+    /// to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` equals zero, `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS] or the parts can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let parts = Dec!(100).split_evenly(3, 2);
+    /// assert_eq!(parts, [Dec!(33.34), Dec!(33.33), Dec!(33.33)]);
+    /// let parts = Dec!(-0.05).split_evenly(3, 2);
+    /// assert_eq!(parts, [Dec!(-0.02), Dec!(-0.02), Dec!(-0.01)]);
+    /// ```
+    #[must_use]
+    pub fn split_evenly(self, n: usize, n_frac_digits: u8) -> Vec<Self> {
+        let mut parts = vec![Self::ZERO; n];
+        match self.split_evenly_into(n_frac_digits, &mut parts) {
+            Ok(()) => parts,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Writes `self` split into `parts.len()` parts into `parts`, in the
+    /// same way as [Decimal::split_evenly], but without allocating memory.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::DivisionByZero` if `parts` is empty,
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InternalOverflow` if the parts can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// # fn main() -> Result<(), DecimalError> {
+    /// let mut parts = [Decimal::ZERO; 4];
+    /// Dec!(10).split_evenly_into(2, &mut parts)?;
+    /// assert_eq!(parts, [Dec!(2.50), Dec!(2.50), Dec!(2.50), Dec!(2.50)]);
+    /// # Ok(()) }
+    /// ```
+    #[allow(clippy::cast_possible_wrap)]
+    #[allow(clippy::integer_division)]
+    pub fn split_evenly_into(
+        self,
+        n_frac_digits: u8,
+        parts: &mut [Self],
+    ) -> Result<(), DecimalError> {
+        if parts.is_empty() {
+            return Err(DecimalError::DivisionByZero);
+        }
+        let (total, n_frac_digits) = self.n_units(n_frac_digits)?;
+        // parts.len() <= isize::MAX < u128::MAX
+        let n = parts.len() as u128;
+        let (quot, rem) =
+            (total.unsigned_abs() / n, total.unsigned_abs() % n);
+        let unit = total.signum();
+        for (idx, part) in parts.iter_mut().enumerate() {
+            let n_units = quot + u128::from((idx as u128) < rem);
+            *part = Self {
+                coeff: unit * n_units as i128,
+                n_frac_digits,
+            };
+        }
+        Ok(())
+    }
+
+    // Returns `self` as number of units of 10^-n and n, where n is the max
+    // of `n_frac_digits` and `self.n_frac_digits`.
+    fn n_units(self, n_frac_digits: u8) -> Result<(i128, u8), DecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(DecimalError::MaxNFracDigitsExceeded);
+        }
+        let n_frac_digits = max(n_frac_digits, self.n_frac_digits);
+        match checked_mul_pow_ten(
+            self.coeff,
+            n_frac_digits - self.n_frac_digits,
+        ) {
+            Some(n_units) => Ok((n_units, n_frac_digits)),
+            None => Err(DecimalError::InternalOverflow),
+        }
+    }
+}
+
+#[cfg(test)]
+mod allocate_tests {
+    use super::*;
+    use crate::{Dec, DivRounded, RoundingMode};
+
+    fn sum(parts: &[Decimal]) -> Decimal {
+        parts.iter().fold(Decimal::ZERO, |acc, part| acc + *part)
+    }
+
+    #[test]
+    fn test_allocate() {
+        let total = Dec!(1000);
+        let weights = [Dec!(0.2), Dec!(0.35), Dec!(0.45), Dec!(0)];
+        let parts = total.allocate(&weights, 0);
+        assert_eq!(parts, [Dec!(200), Dec!(350), Dec!(450), Dec!(0)]);
+        let total = Dec!(0.07);
+        let weights = [Dec!(1), Dec!(2), Dec!(3)];
+        let parts = total.allocate(&weights, 2);
+        // exact shares: 0.011.., 0.023.., 0.035
+        assert_eq!(parts, [Dec!(0.01), Dec!(0.02), Dec!(0.04)]);
+        assert_eq!(sum(&parts), total);
+        let total = Dec!(-1234.5678);
+        let weights = [Dec!(17), Dec!(0.5), Dec!(3.25), Dec!(99)];
+        let parts = total.allocate(&weights, 2);
+        assert_eq!(sum(&parts), total);
+        assert!(parts.iter().all(|p| p.n_frac_digits() == 4));
+        let parts = Dec!(1).allocate(&[Dec!(1); 7], 1);
+        assert_eq!(parts[..3], [Dec!(0.2), Dec!(0.2), Dec!(0.2)]);
+        assert_eq!(parts[3..], [Dec!(0.1), Dec!(0.1), Dec!(0.1), Dec!(0.1)]);
+    }
+
+    #[test]
+    fn test_allocate_large_values() {
+        let total = Decimal::MAX;
+        let weights = [Decimal::MAX, Decimal::ONE, Decimal::MAX];
+        let parts = total.allocate(&weights, 0);
+        assert_eq!(sum(&parts), total);
+        // exact shares: k + 0.25.., 0.5 - 0.00.., k + 0.25.. with
+        // k = (MAX - 1) / 2
+        assert_eq!(parts[0].coefficient(), i128::MAX >> 1);
+        assert_eq!(parts[1], Decimal::ONE);
+        assert_eq!(parts[2], parts[0]);
+    }
+
+    #[test]
+    fn test_allocate_many_parts() {
+        let total = Dec!(-10.07);
+        let weights = [Dec!(0.5); 1000];
+        let parts = total.allocate(&weights, 3);
+        assert_eq!(parts, total.split_evenly(1000, 3));
+        let total = Dec!(1000.01);
+        let weights: Vec<Decimal> =
+            (0..1000).map(|i| Decimal::new_raw(i % 7 + 1, 1)).collect();
+        let parts = total.allocate(&weights, 2);
+        assert_eq!(sum(&parts), total);
+        let sum_weights = sum(&weights);
+        for (part, w) in parts.iter().zip(&weights) {
+            let lower = (total * w).div_rounded_with_mode(
+                sum_weights,
+                2,
+                RoundingMode::RoundDown,
+            );
+            assert!(*part == lower || *part == lower + Dec!(0.01));
+        }
+    }
+
+    #[test]
+    fn test_allocate_into_errors() {
+        let mut parts = [Decimal::ZERO; 2];
+        let total = Dec!(10);
+        assert_eq!(
+            total.allocate_into(&[Dec!(0), Dec!(0)], 2, &mut parts),
+            Err(DecimalError::DivisionByZero)
+        );
+        assert_eq!(
+            total.allocate_into(&[Dec!(1), Dec!(-1)], 2, &mut parts),
+            Err(DecimalError::NotANumber)
+        );
+        assert_eq!(
+            total.allocate_into(&[Dec!(1), Dec!(1)], 19, &mut parts),
+            Err(DecimalError::MaxNFracDigitsExceeded)
+        );
+        assert_eq!(
+            Decimal::MAX.allocate_into(&[Dec!(1), Dec!(1)], 1, &mut parts),
+            Err(DecimalError::InternalOverflow)
+        );
+        assert_eq!(
+            total.allocate_into(
+                &[Decimal::MAX, Decimal::new_raw(1, 1)],
+                2,
+                &mut parts
+            ),
+            Err(DecimalError::InternalOverflow)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_allocate_into_len_mismatch() {
+        let mut parts = [Decimal::ZERO; 2];
+        let _ = Dec!(1).allocate_into(&[Dec!(1)], 2, &mut parts);
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        let parts = Dec!(10).split_evenly(3, 2);
+        assert_eq!(parts, [Dec!(3.34), Dec!(3.33), Dec!(3.33)]);
+        let parts = Dec!(-0.002).split_evenly(3, 2);
+        assert_eq!(parts, [Dec!(-0.001), Dec!(-0.001), Dec!(0)]);
+        assert_eq!(parts[2].n_frac_digits(), 3);
+        let parts = Dec!(9).split_evenly(3, 0);
+        assert_eq!(parts, [Dec!(3), Dec!(3), Dec!(3)]);
+        let parts = Decimal::MIN.split_evenly(2, 0);
+        assert_eq!(sum(&parts), Decimal::MIN);
+    }
+
+    #[test]
+    fn test_split_evenly_into_errors() {
+        let mut parts = [Decimal::ZERO; 0];
+        assert_eq!(
+            Dec!(1).split_evenly_into(2, &mut parts),
+            Err(DecimalError::DivisionByZero)
+        );
+        let mut parts = [Decimal::ZERO; 2];
+        assert_eq!(
+            Decimal::MAX.split_evenly_into(1, &mut parts),
+            Err(DecimalError::InternalOverflow)
+        );
+    }
+}
@@ -0,0 +1,413 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+use fpdec_core::ten_pow;
+
+// Unsigned int of arbitrary size, used for intermediate results which can
+// not be held by an i128 or u128.
+// The limbs are stored in little endian order, without leading zero limbs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UBig {
+    limbs: Vec<u64>,
+}
+
+impl UBig {
+    pub(crate) const fn zero() -> Self {
+        Self { limbs: Vec::new() }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_u128(x: u128) -> Self {
+        let mut res = Self {
+            limbs: vec![x as u64, (x >> 64) as u64],
+        };
+        res.trim();
+        res
+    }
+
+    // Return hi * 2^128 + lo.
+    pub(crate) fn from_u256(hi: u128, lo: u128) -> Self {
+        let mut res = Self::from_u128(hi).shl(128);
+        if res.is_zero() {
+            return Self::from_u128(lo);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            res.limbs[0] = lo as u64;
+            res.limbs[1] = (lo >> 64) as u64;
+        }
+        res
+    }
+
+    // Return 10^n.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn ten_pow(mut n: u32) -> Self {
+        let mut res = Self::from_u128(1);
+        while n > 19 {
+            res.mul_u64(ten_pow(19) as u64);
+            n -= 19;
+        }
+        res.mul_u64(ten_pow(n as u8) as u64);
+        res
+    }
+
+    // Return 2^n.
+    pub(crate) fn two_pow(n: u32) -> Self {
+        Self::from_u128(1).shl(n)
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    pub(crate) const fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub(crate) fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|l| l & 1 == 1)
+    }
+
+    // Return the number of significant bits of self.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn n_bits(&self) -> u32 {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => self.limbs.len() as u32 * 64 - top.leading_zeros(),
+        }
+    }
+
+    pub(crate) fn to_u128(&self) -> Option<u128> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(u128::from(self.limbs[0])),
+            2 => Some(
+                u128::from(self.limbs[0]) | u128::from(self.limbs[1]) << 64,
+            ),
+            _ => None,
+        }
+    }
+
+    // Return (hi, lo) with hi * 2^128 + lo = self, if self < 2^256.
+    pub(crate) fn to_u256(&self) -> Option<(u128, u128)> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        let limb =
+            |i: usize| u128::from(self.limbs.get(i).copied().unwrap_or(0));
+        Some((limb(3) << 64 | limb(2), limb(1) << 64 | limb(0)))
+    }
+
+    // Return the decimal representation of self.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn to_decimal_string(&self) -> String {
+        // 10^19 is the largest power of ten fitting into an u64.
+        let mut chunks: Vec<u64> = Vec::new();
+        let mut x = self.clone();
+        while !x.is_zero() {
+            chunks.push(x.div_u64(ten_pow(19) as u64));
+        }
+        match chunks.pop() {
+            None => "0".to_owned(),
+            Some(top) => {
+                let mut res = top.to_string();
+                for chunk in chunks.iter().rev() {
+                    res.push_str(&format!("{chunk:019}"));
+                }
+                res
+            }
+        }
+    }
+
+    // self <- self * y
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn mul_u64(&mut self, y: u64) {
+        let mut carry = 0_u128;
+        for limb in &mut self.limbs {
+            let t = u128::from(*limb) * u128::from(y) + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u64);
+        }
+        self.trim();
+    }
+
+    // self <- self / y, returning self % y.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::integer_division)]
+    pub(crate) fn div_u64(&mut self, y: u64) -> u64 {
+        debug_assert_ne!(y, 0);
+        let y = u128::from(y);
+        let mut rem = 0_u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let t = rem << 64 | u128::from(*limb);
+            *limb = (t / y) as u64;
+            rem = t % y;
+        }
+        self.trim();
+        rem as u64
+    }
+
+    #[must_use]
+    pub(crate) fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let mut limbs = vec![0_u64; self.limbs.len() + other.limbs.len()];
+        for (i, &x) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, &y) in other.limbs.iter().enumerate() {
+                let t = u128::from(x) * u128::from(y)
+                    + u128::from(limbs[i + j])
+                    + carry;
+                #[allow(clippy::cast_possible_truncation)]
+                {
+                    limbs[i + j] = t as u64;
+                }
+                carry = t >> 64;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                limbs[i + other.limbs.len()] = carry as u64;
+            }
+        }
+        let mut res = Self { limbs };
+        res.trim();
+        res
+    }
+
+    // Return self^n.
+    #[must_use]
+    pub(crate) fn pow(&self, mut n: u32) -> Self {
+        let mut res = Self::from_u128(1);
+        let mut base = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                res = res.mul(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.mul(&base);
+            }
+        }
+        res
+    }
+
+    #[must_use]
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut limbs = long.limbs.clone();
+        let mut carry = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let y = short.limbs.get(i).copied().unwrap_or(0);
+            if i >= short.limbs.len() && !carry {
+                break;
+            }
+            let (t, c1) = limb.overflowing_add(y);
+            let (t, c2) = t.overflowing_add(u64::from(carry));
+            *limb = t;
+            carry = c1 || c2;
+        }
+        if carry {
+            limbs.push(1);
+        }
+        Self { limbs }
+    }
+
+    // Return self - other.
+    // Pre-condition: self >= other
+    #[must_use]
+    pub(crate) fn sub(&self, other: &Self) -> Self {
+        debug_assert!(*self >= *other);
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let y = other.limbs.get(i).copied().unwrap_or(0);
+            if i >= other.limbs.len() && !borrow {
+                break;
+            }
+            let (t, b1) = limb.overflowing_sub(y);
+            let (t, b2) = t.overflowing_sub(u64::from(borrow));
+            *limb = t;
+            borrow = b1 || b2;
+        }
+        let mut res = Self { limbs };
+        res.trim();
+        res
+    }
+
+    #[must_use]
+    pub(crate) fn shl(&self, n: u32) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+        let n_limbs = (n >> 6) as usize;
+        let n_bits = n & 63;
+        let mut limbs = vec![0_u64; n_limbs];
+        if n_bits == 0 {
+            limbs.extend_from_slice(&self.limbs);
+        } else {
+            let mut carry = 0_u64;
+            for &limb in &self.limbs {
+                limbs.push(limb << n_bits | carry);
+                carry = limb >> (64 - n_bits);
+            }
+            limbs.push(carry);
+        }
+        let mut res = Self { limbs };
+        res.trim();
+        res
+    }
+
+    #[must_use]
+    pub(crate) fn shr(&self, n: u32) -> Self {
+        let n_limbs = (n >> 6) as usize;
+        if n_limbs >= self.limbs.len() {
+            return Self::zero();
+        }
+        let n_bits = n & 63;
+        let mut limbs = self.limbs[n_limbs..].to_vec();
+        if n_bits != 0 {
+            for i in 0..limbs.len() {
+                let hi = limbs.get(i + 1).copied().unwrap_or(0);
+                limbs[i] = limbs[i] >> n_bits | hi << (64 - n_bits);
+            }
+        }
+        let mut res = Self { limbs };
+        res.trim();
+        res
+    }
+
+    // Return (self / other, self % other).
+    // Pre-condition: other != 0
+    pub(crate) fn div_rem(&self, other: &Self) -> (Self, Self) {
+        debug_assert!(!other.is_zero());
+        if *self < *other {
+            return (Self::zero(), self.clone());
+        }
+        if other.limbs.len() == 1 {
+            let mut quot = self.clone();
+            let rem = quot.div_u64(other.limbs[0]);
+            return (quot, Self::from_u128(u128::from(rem)));
+        }
+        // Simple shift-subtract algorithm, processing one bit per step.
+        let shift = self.n_bits() - other.n_bits();
+        let mut rem = self.clone();
+        let mut quot = Self {
+            limbs: vec![0_u64; (shift >> 6) as usize + 1],
+        };
+        let mut divisor = other.shl(shift);
+        for i in (0..=shift).rev() {
+            if rem >= divisor {
+                rem = rem.sub(&divisor);
+                quot.limbs[(i >> 6) as usize] |= 1 << (i & 63);
+            }
+            divisor = divisor.shr(1);
+        }
+        quot.trim();
+        (quot, rem)
+    }
+}
+
+impl PartialOrd for UBig {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UBig {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.limbs.len().cmp(&other.limbs.len()) {
+            Ordering::Equal => {
+                self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+            }
+            ord => ord,
+        }
+    }
+}
+
+#[cfg(test)]
+mod ubig_tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div() {
+        let x = UBig::from_u128(u128::MAX);
+        let y = UBig::from_u128(12345678901234567890123456789);
+        let z = x.mul(&y).add(&UBig::from_u128(17));
+        let (q, r) = z.div_rem(&y);
+        assert_eq!(q, x);
+        assert_eq!(r.to_u128(), Some(17));
+        let (q, r) = z.div_rem(&x);
+        assert_eq!(q, y);
+        assert_eq!(r.to_u128(), Some(17));
+    }
+
+    #[test]
+    fn test_pow() {
+        let x = UBig::from_u128(10);
+        assert_eq!(x.pow(45), UBig::ten_pow(45));
+        let x = UBig::from_u128(2);
+        assert_eq!(x.pow(200), UBig::two_pow(200));
+        assert_eq!(x.pow(0).to_u128(), Some(1));
+    }
+
+    #[test]
+    fn test_shift() {
+        let x = UBig::from_u128(0x1234567890abcdef1234567890abcdef);
+        let y = x.shl(131);
+        assert_eq!(y.n_bits(), x.n_bits() + 131);
+        assert_eq!(y.shr(131), x);
+        assert_eq!(y.shr(300), UBig::zero());
+    }
+
+    #[test]
+    fn test_u256() {
+        let x = UBig::from_u256(7, u128::MAX);
+        assert_eq!(x.n_bits(), 131);
+        assert_eq!(x.to_u256(), Some((7, u128::MAX)));
+        assert_eq!(UBig::from_u256(0, 5).to_u128(), Some(5));
+        assert_eq!(UBig::two_pow(256).to_u256(), None);
+    }
+
+    #[test]
+    fn test_to_decimal_string() {
+        assert_eq!(UBig::zero().to_decimal_string(), "0");
+        assert_eq!(UBig::ten_pow(40).to_decimal_string().len(), 41);
+        assert_eq!(
+            UBig::from_u128(u128::MAX).to_decimal_string(),
+            u128::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn test_sub() {
+        let x = UBig::two_pow(256);
+        let y = x.sub(&UBig::from_u128(1));
+        assert_eq!(y.n_bits(), 256);
+        assert_eq!(y.add(&UBig::from_u128(1)), x);
+    }
+}
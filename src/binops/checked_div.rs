@@ -42,6 +42,7 @@ impl CheckedDiv<Self> for Decimal {
             rhs.coeff,
             rhs.n_frac_digits,
             n_frac_digits,
+            None,
         )?;
         normalize(&mut coeff, &mut n_frac_digits);
         Some(Self {
@@ -170,6 +171,7 @@ macro_rules! impl_div_decimal_and_int {
                     i128::from(rhs),
                     0,
                     n_frac_digits,
+                    None,
                 )?;
                 normalize(&mut coeff, &mut n_frac_digits);
                 Some(Self {
@@ -202,6 +204,7 @@ macro_rules! impl_div_decimal_and_int {
                     rhs.coeff,
                     rhs.n_frac_digits,
                     n_frac_digits,
+                    None,
                 )?;
                 normalize(&mut coeff, &mut n_frac_digits);
                 Some(Decimal {
@@ -35,6 +35,7 @@ impl Div<Self> for Decimal {
             rhs.coeff,
             rhs.n_frac_digits,
             n_frac_digits,
+            None,
         ) {
             normalize(&mut coeff, &mut n_frac_digits);
             Self::Output {
@@ -180,6 +181,7 @@ macro_rules! impl_div_decimal_and_int {
                     i128::from(rhs),
                     0,
                     n_frac_digits,
+                    None,
                 ) {
                     normalize(&mut coeff, &mut n_frac_digits);
                     Self::Output {
@@ -215,6 +217,7 @@ macro_rules! impl_div_decimal_and_int {
                     rhs.coeff,
                     rhs.n_frac_digits,
                     n_frac_digits,
+                    None,
                 ) {
                     normalize(&mut coeff, &mut n_frac_digits);
                     Self::Output {
@@ -11,7 +11,7 @@ use core::cmp::Ordering;
 
 use fpdec_core::{
     checked_mul_pow_ten, i128_div_rounded, i128_shifted_div_rounded, ten_pow,
-    MAX_N_FRAC_DIGITS,
+    with_rounding_mode, RoundingMode, MAX_N_FRAC_DIGITS,
 };
 
 use crate::{Decimal, DecimalError};
@@ -49,6 +49,45 @@ pub trait DivRounded<Rhs = Self> {
     /// assert_eq!(r.to_string(), "942.364");
     /// ```
     fn div_rounded(self, rhs: Rhs, n_frac_digits: u8) -> Self::Output;
+
+    /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according to the
+    /// given [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the resulting value can not be
+    /// represented by `Self::Output`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DivRounded, RoundingMode};
+    /// let d = Dec!(28.27093);
+    /// let q = Dec!(0.03);
+    /// let r = d.div_rounded_with_mode(q, 3, RoundingMode::RoundDown);
+    /// assert_eq!(r.to_string(), "942.364");
+    /// let r = d.div_rounded_with_mode(q, 3, RoundingMode::RoundUp);
+    /// assert_eq!(r.to_string(), "942.365");
+    /// ```
+    ///
+    /// The default implementation calls [DivRounded::div_rounded] with the
+    /// default RoundingMode temporarily set to `mode` (see
+    /// [with_rounding_mode]). Without feature `std` that mode is set
+    /// globally for the duration of the call, so it then also applies to
+    /// other threads and interrupt handlers; implementors used in such a
+    /// context should override this method.
+    #[inline]
+    fn div_rounded_with_mode(
+        self,
+        rhs: Rhs,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self::Output
+    where
+        Self: Sized,
+    {
+        with_rounding_mode(mode, || self.div_rounded(rhs, n_frac_digits))
+    }
 }
 
 #[allow(clippy::integer_division)]
@@ -58,11 +97,12 @@ pub(crate) fn checked_div_rounded(
     divisor_coeff: i128,
     divisor_n_frac_digits: u8,
     n_frac_digits: u8,
+    mode: Option<RoundingMode>,
 ) -> Option<i128> {
     let mut shift = n_frac_digits + divisor_n_frac_digits;
     match divident_n_frac_digits.cmp(&shift) {
         Ordering::Equal => {
-            Some(i128_div_rounded(divident_coeff, divisor_coeff, None))
+            Some(i128_div_rounded(divident_coeff, divisor_coeff, mode))
         }
         Ordering::Less => {
             // divident coeff needs to be shifted
@@ -71,13 +111,13 @@ pub(crate) fn checked_div_rounded(
             if let Some(shifted_divident) =
                 checked_mul_pow_ten(divident_coeff, shift)
             {
-                Some(i128_div_rounded(shifted_divident, divisor_coeff, None))
+                Some(i128_div_rounded(shifted_divident, divisor_coeff, mode))
             } else {
                 i128_shifted_div_rounded(
                     divident_coeff,
                     shift,
                     divisor_coeff,
-                    None,
+                    mode,
                 )
             }
         }
@@ -94,7 +134,7 @@ pub(crate) fn checked_div_rounded(
             Some(i128_div_rounded(
                 divident_coeff / divisor_coeff,
                 ten_pow(shift),
-                None,
+                mode,
             ))
         }
     }
@@ -103,7 +143,21 @@ pub(crate) fn checked_div_rounded(
 impl DivRounded<Self> for Decimal {
     type Output = Self;
 
+    #[inline]
     fn div_rounded(self, rhs: Self, n_frac_digits: u8) -> Self::Output {
+        self.div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    fn div_rounded_with_mode(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self::Output {
         #[allow(clippy::manual_assert)]
         if n_frac_digits > MAX_N_FRAC_DIGITS {
             panic!("{}", DecimalError::MaxNFracDigitsExceeded);
@@ -121,6 +175,7 @@ impl DivRounded<Self> for Decimal {
             rhs.coeff,
             rhs.n_frac_digits,
             n_frac_digits,
+            Some(mode),
         ) {
             Self::Output {
                 coeff,
@@ -132,7 +187,11 @@ impl DivRounded<Self> for Decimal {
     }
 }
 
-forward_ref_binop_rounded!(impl DivRounded, div_rounded);
+forward_ref_binop_rounded!(
+    impl DivRounded,
+    div_rounded,
+    div_rounded_with_mode
+);
 
 #[cfg(test)]
 mod div_rounded_decimal_tests {
@@ -244,6 +303,25 @@ mod div_rounded_decimal_tests {
         let a = DivRounded::div_rounded(&x, &y, 2);
         assert_eq!(a.coefficient(), z.coefficient());
     }
+
+    #[test]
+    fn test_div_rounded_with_mode() {
+        let x = Decimal::new_raw(17, 0);
+        let y = Decimal::new_raw(-201, 2);
+        let z = x.div_rounded_with_mode(y, 2, RoundingMode::RoundDown);
+        assert_eq!(z.coefficient(), -845);
+        let z = x.div_rounded_with_mode(y, 2, RoundingMode::RoundFloor);
+        assert_eq!(z.coefficient(), -846);
+        let z = DivRounded::div_rounded_with_mode(
+            &x,
+            &y,
+            2,
+            RoundingMode::RoundCeiling,
+        );
+        assert_eq!(z.coefficient(), -845);
+        let z = 17_i32.div_rounded_with_mode(4_i32, 0, RoundingMode::RoundUp);
+        assert_eq!(z.coefficient(), 5);
+    }
 }
 
 macro_rules! impl_div_rounded_decimal_and_int {
@@ -257,7 +335,25 @@ macro_rules! impl_div_rounded_decimal_and_int {
         impl DivRounded<$t> for Decimal {
             type Output = Self;
 
-            fn div_rounded(self, rhs: $t, n_frac_digits: u8) -> Self::Output {
+            #[inline]
+            fn div_rounded(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            fn div_rounded_with_mode(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
                 if rhs == 0 {
                     panic!("{}", DecimalError::DivisionByZero);
                 }
@@ -270,6 +366,7 @@ macro_rules! impl_div_rounded_decimal_and_int {
                     i128::from(rhs),
                     0_u8,
                     n_frac_digits,
+                    Some(mode),
                 ) {
                     Self::Output {
                         coeff,
@@ -288,8 +385,31 @@ macro_rules! impl_div_rounded_decimal_and_int {
             type Output = <Decimal as DivRounded<$t>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: $t, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(*self, rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    *self,
+                    rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
@@ -300,8 +420,31 @@ macro_rules! impl_div_rounded_decimal_and_int {
             type Output = <Decimal as DivRounded<$t>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: &$t, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(self, *rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    self,
+                    *rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
@@ -312,15 +455,56 @@ macro_rules! impl_div_rounded_decimal_and_int {
             type Output = <Decimal as DivRounded<$t>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: &$t, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(*self, *rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    *self,
+                    *rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
         impl DivRounded<Decimal> for $t {
             type Output = Decimal;
 
-            fn div_rounded(self, rhs: Decimal, n_frac_digits: u8) -> Self::Output {
+            #[inline]
+            fn div_rounded(
+                self,
+                rhs: Decimal,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            fn div_rounded_with_mode(
+                self,
+                rhs: Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
                 if rhs.eq_zero() {
                     panic!("{}", DecimalError::DivisionByZero);
                 }
@@ -333,6 +517,7 @@ macro_rules! impl_div_rounded_decimal_and_int {
                     rhs.coeff,
                     rhs.n_frac_digits,
                     n_frac_digits,
+                    Some(mode),
                 ) {
                     Self::Output {
                         coeff,
@@ -351,8 +536,31 @@ macro_rules! impl_div_rounded_decimal_and_int {
             type Output = <$t as DivRounded<Decimal>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: Decimal, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(*self, rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: Decimal,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    *self,
+                    rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
@@ -363,8 +571,31 @@ macro_rules! impl_div_rounded_decimal_and_int {
             type Output = <$t as DivRounded<Decimal>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: &Decimal, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(self, *rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: &Decimal,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: &Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    self,
+                    *rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
@@ -375,8 +606,31 @@ macro_rules! impl_div_rounded_decimal_and_int {
             type Output = <$t as DivRounded<Decimal>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: &Decimal, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(*self, *rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: &Decimal,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: &Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    *self,
+                    *rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
         )*
@@ -613,7 +867,25 @@ macro_rules! impl_div_rounded_int_and_int {
         impl DivRounded<$t> for $t {
             type Output = Decimal;
 
-            fn div_rounded(self, rhs: $t, n_frac_digits: u8) -> Self::Output {
+            #[inline]
+            fn div_rounded(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            fn div_rounded_with_mode(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
                 if rhs == 0 {
                     panic!("{}", DecimalError::DivisionByZero);
                 }
@@ -626,6 +898,7 @@ macro_rules! impl_div_rounded_int_and_int {
                     i128::from(rhs),
                     0_u8,
                     n_frac_digits,
+                    Some(mode),
                 ) {
                     Self::Output {
                         coeff,
@@ -644,8 +917,31 @@ macro_rules! impl_div_rounded_int_and_int {
             type Output = <$t as DivRounded<$t>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: $t, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(*self, rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    *self,
+                    rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
@@ -656,8 +952,31 @@ macro_rules! impl_div_rounded_int_and_int {
             type Output = <$t as DivRounded<$t>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: &$t, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(self, *rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    self,
+                    *rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
 
@@ -668,8 +987,31 @@ macro_rules! impl_div_rounded_int_and_int {
             type Output = <$t as DivRounded<$t>>::Output;
 
             #[inline(always)]
-            fn div_rounded(self, rhs: &$t, n_frac_digits: u8) -> Self::Output {
-                DivRounded::div_rounded(*self, *rhs, n_frac_digits)
+            fn div_rounded(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[inline(always)]
+            fn div_rounded_with_mode(
+                self,
+                rhs: &$t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                DivRounded::div_rounded_with_mode(
+                    *self,
+                    *rhs,
+                    n_frac_digits,
+                    mode,
+                )
             }
         }
         )*
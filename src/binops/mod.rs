@@ -49,7 +49,7 @@ macro_rules! forward_ref_binop {
 
 // Same for ops giving rounded result.
 macro_rules! forward_ref_binop_rounded {
-    (impl $imp:ident, $method:ident) => {
+    (impl $imp:ident, $method:ident, $method_with_mode:ident) => {
         impl<'a> $imp<Decimal> for &'a Decimal
         where
             Decimal: $imp<Decimal>,
@@ -60,6 +60,16 @@ macro_rules! forward_ref_binop_rounded {
             fn $method(self, rhs: Decimal, n_frac_digits: u8) -> Decimal {
                 $imp::$method(*self, rhs, n_frac_digits)
             }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Decimal {
+                $imp::$method_with_mode(*self, rhs, n_frac_digits, mode)
+            }
         }
         impl $imp<&Decimal> for Decimal
         where
@@ -71,6 +81,16 @@ macro_rules! forward_ref_binop_rounded {
             fn $method(self, rhs: &Decimal, n_frac_digits: u8) -> Decimal {
                 $imp::$method(self, *rhs, n_frac_digits)
             }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: &Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Decimal {
+                $imp::$method_with_mode(self, *rhs, n_frac_digits, mode)
+            }
         }
         impl $imp<&Decimal> for &Decimal
         where
@@ -82,6 +102,16 @@ macro_rules! forward_ref_binop_rounded {
             fn $method(self, rhs: &Decimal, n_frac_digits: u8) -> Decimal {
                 $imp::$method(*self, *rhs, n_frac_digits)
             }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: &Decimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Decimal {
+                $imp::$method_with_mode(*self, *rhs, n_frac_digits, mode)
+            }
         }
     };
 }
@@ -190,4 +220,7 @@ pub(crate) mod div;
 pub(crate) mod div_rounded;
 mod mul;
 pub(crate) mod mul_rounded;
-mod rem;
+pub(crate) mod overflowing;
+pub(crate) mod rem;
+pub(crate) mod saturating;
+pub(crate) mod sum_product;
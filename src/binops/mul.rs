@@ -27,7 +27,9 @@ impl Mul<Self> for Decimal {
         if self.eq_one() {
             return rhs;
         }
-        if let Some(res) = checked_mul_rounded(self, rhs, MAX_N_FRAC_DIGITS) {
+        if let Some(res) =
+            checked_mul_rounded(self, rhs, MAX_N_FRAC_DIGITS, None)
+        {
             res
         } else {
             panic!("{}", DecimalError::InternalOverflow);
@@ -9,7 +9,7 @@
 
 use fpdec_core::{
     i128_div_rounded, i128_mul_div_ten_pow_rounded, ten_pow,
-    MAX_N_FRAC_DIGITS,
+    with_rounding_mode, RoundingMode, MAX_N_FRAC_DIGITS,
 };
 
 use crate::{Decimal, DecimalError};
@@ -20,14 +20,51 @@ pub trait MulRounded<Rhs = Self> {
     /// The resulting type after applying `mul_rounded`.
     type Output;
 
-    /// Returns `self` * `rhs`, rounded to `n_frac_digits`.
+    /// Returns `self` * `rhs`, rounded to `n_frac_digits`, according to the
+    /// current [RoundingMode].
     fn mul_rounded(self, rhs: Rhs, n_frac_digits: u8) -> Self::Output;
+
+    /// Returns `self` * `rhs`, rounded to `n_frac_digits`, according to the
+    /// given [RoundingMode].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, MulRounded, RoundingMode};
+    /// let x = Dec!(1.25);
+    /// let y = Dec!(0.5);
+    /// assert_eq!(x.mul_rounded(y, 2), Dec!(0.62));
+    /// assert_eq!(
+    ///     x.mul_rounded_with_mode(y, 2, RoundingMode::RoundHalfUp),
+    ///     Dec!(0.63)
+    /// );
+    /// ```
+    ///
+    /// The default implementation calls [MulRounded::mul_rounded] with the
+    /// default RoundingMode temporarily set to `mode` (see
+    /// [with_rounding_mode]). Without feature `std` that mode is set
+    /// globally for the duration of the call, so it then also applies to
+    /// other threads and interrupt handlers; implementors used in such a
+    /// context should override this method.
+    #[inline]
+    fn mul_rounded_with_mode(
+        self,
+        rhs: Rhs,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self::Output
+    where
+        Self: Sized,
+    {
+        with_rounding_mode(mode, || self.mul_rounded(rhs, n_frac_digits))
+    }
 }
 
 pub(crate) fn checked_mul_rounded(
     x: Decimal,
     y: Decimal,
     n_frac_digits: u8,
+    mode: Option<RoundingMode>,
 ) -> Option<Decimal> {
     let max_n_frac_digits = x.n_frac_digits + y.n_frac_digits;
     if n_frac_digits >= max_n_frac_digits {
@@ -40,12 +77,12 @@ pub(crate) fn checked_mul_rounded(
         let shift = max_n_frac_digits - n_frac_digits;
         if let Some(coeff) = x.coeff.checked_mul(y.coeff) {
             Some(Decimal {
-                coeff: i128_div_rounded(coeff, ten_pow(shift), None),
+                coeff: i128_div_rounded(coeff, ten_pow(shift), mode),
                 n_frac_digits,
             })
         } else {
             let coeff =
-                i128_mul_div_ten_pow_rounded(x.coeff, y.coeff, shift, None)?;
+                i128_mul_div_ten_pow_rounded(x.coeff, y.coeff, shift, mode)?;
             Some(Decimal {
                 coeff,
                 n_frac_digits,
@@ -59,6 +96,20 @@ impl MulRounded<Self> for Decimal {
 
     #[inline]
     fn mul_rounded(self, rhs: Self, n_frac_digits: u8) -> Self::Output {
+        self.mul_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    #[inline]
+    fn mul_rounded_with_mode(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self::Output {
         #[allow(clippy::manual_assert)]
         if n_frac_digits > MAX_N_FRAC_DIGITS {
             panic!("{}", DecimalError::MaxNFracDigitsExceeded);
@@ -66,7 +117,9 @@ impl MulRounded<Self> for Decimal {
         if self.eq_zero() || rhs.eq_zero() {
             return Self::ZERO;
         }
-        if let Some(res) = checked_mul_rounded(self, rhs, n_frac_digits) {
+        if let Some(res) =
+            checked_mul_rounded(self, rhs, n_frac_digits, Some(mode))
+        {
             res
         } else {
             panic!("{}", DecimalError::InternalOverflow);
@@ -74,7 +127,11 @@ impl MulRounded<Self> for Decimal {
     }
 }
 
-forward_ref_binop_rounded!(impl MulRounded, mul_rounded);
+forward_ref_binop_rounded!(
+    impl MulRounded,
+    mul_rounded,
+    mul_rounded_with_mode
+);
 
 #[cfg(test)]
 mod mul_rounded_decimal_tests {
@@ -122,4 +179,21 @@ mod mul_rounded_decimal_tests {
         let a = MulRounded::mul_rounded(&x, &y, 2);
         assert_eq!(a.coefficient(), z.coefficient());
     }
+
+    #[test]
+    fn test_mul_rounded_with_mode() {
+        let x = Decimal::new_raw(125, 2);
+        let y = Decimal::new_raw(-5, 1);
+        let z = x.mul_rounded_with_mode(y, 2, RoundingMode::RoundHalfUp);
+        assert_eq!(z.coefficient(), -63);
+        let z = x.mul_rounded_with_mode(y, 2, RoundingMode::RoundHalfEven);
+        assert_eq!(z.coefficient(), -62);
+        let z = MulRounded::mul_rounded_with_mode(
+            &x,
+            &y,
+            1,
+            RoundingMode::RoundCeiling,
+        );
+        assert_eq!(z.coefficient(), -6);
+    }
 }
@@ -0,0 +1,373 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use fpdec_core::{
+    checked_mul_pow_ten, ten_pow, u128_mul_u128, u256_checked_add,
+    u256_div_rem_u128, u256_sub,
+};
+
+use crate::{
+    binops::mul_rounded::checked_mul_rounded, fused_ops::round_signed,
+    Decimal, MAX_N_FRAC_DIGITS,
+};
+
+/// Overflowing addition.
+/// Computes `self + rhs`.
+/// Returns a tuple of the result and a flag indicating whether an overflow
+/// occurred. In case of an overflow the result is saturated, i.e. it is the
+/// maximum or minimum value of the `Output` type, depending on the sign of
+/// the exact result.
+pub trait OverflowingAdd<Rhs = Self> {
+    /// The resulting type after applying `overflowing_add`.
+    type Output;
+    /// Returns `(self + rhs, false)` or, if the result can not be
+    /// represented, the saturated result and `true`.
+    fn overflowing_add(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Overflowing subtraction.
+/// Computes `self - rhs`.
+/// Returns a tuple of the result and a flag indicating whether an overflow
+/// occurred. In case of an overflow the result is saturated, i.e. it is the
+/// maximum or minimum value of the `Output` type, depending on the sign of
+/// the exact result.
+pub trait OverflowingSub<Rhs = Self> {
+    /// The resulting type after applying `overflowing_sub`.
+    type Output;
+    /// Returns `(self - rhs, false)` or, if the result can not be
+    /// represented, the saturated result and `true`.
+    fn overflowing_sub(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Overflowing multiplication.
+/// Computes `self * rhs`.
+/// Returns a tuple of the result and a flag indicating whether an overflow
+/// occurred. In case of an overflow the result is saturated, i.e. it is the
+/// maximum or minimum value of the `Output` type, depending on the sign of
+/// the exact result.
+pub trait OverflowingMul<Rhs = Self> {
+    /// The resulting type after applying `overflowing_mul`.
+    type Output;
+    /// Returns `(self * rhs, false)` or, if the result can not be
+    /// represented, the saturated result and `true`.
+    fn overflowing_mul(self, rhs: Rhs) -> Self::Output;
+}
+
+// Returns `(res, false)`, if `res` holds a value in the range of `Decimal`,
+// otherwise `(Decimal::MAX, true)` or `(Decimal::MIN, true)`, depending on
+// `is_positive`.
+#[inline]
+fn saturate(
+    res: Option<Decimal>,
+    is_positive: impl FnOnce() -> bool,
+) -> (Decimal, bool) {
+    match res {
+        // i128::MIN is not a valid coefficient
+        Some(d) if d.coeff != i128::MIN => (d, false),
+        _ if is_positive() => (Decimal::MAX, true),
+        _ => (Decimal::MIN, true),
+    }
+}
+
+// Returns `Some(x + y)` or, if adjusting the operands to a common number of
+// fractional digits overflows, `Some(x + y)` rounded according to the
+// current RoundingMode to the largest number of fractional digits possible,
+// or `None` if the magnitude of the result exceeds the range of `Decimal`.
+// The exact sum is held in 256 bits, so it is rounded only once.
+fn checked_add_rounded(x: Decimal, y: Decimal) -> Option<Decimal> {
+    let (x, y) = if x.n_frac_digits <= y.n_frac_digits {
+        (x, y)
+    } else {
+        (y, x)
+    };
+    // x.n_frac_digits <= n_frac_digits <= y.n_frac_digits
+    let mut n_frac_digits = y.n_frac_digits;
+    while checked_mul_pow_ten(x.coeff, n_frac_digits - x.n_frac_digits)
+        .is_none()
+    {
+        n_frac_digits -= 1;
+    }
+    // x + y = (x.coeff * 10^k + y.coeff) / 10^y.n_frac_digits
+    // k <= 18, so ten_pow is safe.
+    let k = y.n_frac_digits - x.n_frac_digits;
+    let (mut h, mut l) =
+        u128_mul_u128(x.coeff.unsigned_abs(), ten_pow(k) as u128);
+    let y_mag = y.coeff.unsigned_abs();
+    let neg = if x.is_negative() == y.is_negative() {
+        (h, l) = u256_checked_add(h, l, 0, y_mag)?;
+        x.is_negative()
+    } else if (h, l) < (0, y_mag) {
+        (h, l) = u256_sub(0, y_mag, h, l);
+        y.is_negative()
+    } else {
+        (h, l) = u256_sub(h, l, 0, y_mag);
+        x.is_negative()
+    };
+    let coeff = if n_frac_digits == y.n_frac_digits {
+        let mag = i128::try_from(l).ok().filter(|_| h == 0)?;
+        if neg {
+            -mag
+        } else {
+            mag
+        }
+    } else {
+        let divisor = ten_pow(y.n_frac_digits - n_frac_digits) as u128;
+        let ((qh, ql), rem) = u256_div_rem_u128(h, l, divisor);
+        if qh != 0 {
+            return None;
+        }
+        round_signed(neg, ql, rem, divisor, None)?
+    };
+    Some(Decimal {
+        coeff,
+        n_frac_digits,
+    })
+}
+
+impl OverflowingAdd<Self> for Decimal {
+    type Output = (Self, bool);
+
+    /// If adjusting the operands to a common number of fractional digits
+    /// overflows, the result is rounded to the largest number of fractional
+    /// digits possible, according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    #[inline]
+    fn overflowing_add(self, rhs: Self) -> Self::Output {
+        saturate(checked_add_rounded(self, rhs), || self > -rhs)
+    }
+}
+
+forward_ref_binop!(impl OverflowingAdd, overflowing_add);
+
+impl OverflowingSub<Self> for Decimal {
+    type Output = (Self, bool);
+
+    /// If adjusting the operands to a common number of fractional digits
+    /// overflows, the result is rounded to the largest number of fractional
+    /// digits possible, according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    #[inline]
+    fn overflowing_sub(self, rhs: Self) -> Self::Output {
+        saturate(checked_add_rounded(self, -rhs), || self > rhs)
+    }
+}
+
+forward_ref_binop!(impl OverflowingSub, overflowing_sub);
+
+impl OverflowingMul<Self> for Decimal {
+    type Output = (Self, bool);
+
+    /// The result is rounded to [MAX_N_FRAC_DIGITS] fractional digits, if
+    /// necessary, in the same way as by the operator `*`.
+    #[inline]
+    fn overflowing_mul(self, rhs: Self) -> Self::Output {
+        saturate(
+            checked_mul_rounded(self, rhs, MAX_N_FRAC_DIGITS, None),
+            || self.is_negative() == rhs.is_negative(),
+        )
+    }
+}
+
+forward_ref_binop!(impl OverflowingMul, overflowing_mul);
+
+macro_rules! impl_overflowing_decimal_and_int {
+    (impl $imp:ident, $method:ident) => {
+        impl_overflowing_decimal_and_int!(
+            impl $imp, $method, u8, i8, u16, i16, u32, i32, u64, i64, i128
+        );
+    };
+    (impl $imp:ident, $method:ident, $($t:ty),*) => {
+        $(
+        impl $imp<$t> for Decimal {
+            type Output = (Decimal, bool);
+
+            #[inline]
+            fn $method(self, rhs: $t) -> Self::Output {
+                $imp::$method(self, Decimal::from(rhs))
+            }
+        }
+
+        impl $imp<Decimal> for $t {
+            type Output = (Decimal, bool);
+
+            #[inline]
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                $imp::$method(Decimal::from(self), rhs)
+            }
+        }
+        )*
+    }
+}
+
+impl_overflowing_decimal_and_int!(impl OverflowingAdd, overflowing_add);
+forward_ref_binop_decimal_int!(impl OverflowingAdd, overflowing_add);
+
+impl_overflowing_decimal_and_int!(impl OverflowingSub, overflowing_sub);
+forward_ref_binop_decimal_int!(impl OverflowingSub, overflowing_sub);
+
+impl_overflowing_decimal_and_int!(impl OverflowingMul, overflowing_mul);
+forward_ref_binop_decimal_int!(impl OverflowingMul, overflowing_mul);
+
+#[cfg(test)]
+mod overflowing_decimal_tests {
+    use super::*;
+    use crate::{with_rounding_mode, RoundingMode};
+
+    #[test]
+    fn test_overflowing_add() {
+        let x = Decimal::new_raw(1234567890, 3);
+        let y = Decimal::new_raw(-7, 5);
+        assert_eq!(x.overflowing_add(y), (x + y, false));
+        assert_eq!((&x).overflowing_add(&y), (x + y, false));
+        let x = Decimal::new_raw(i128::MAX - 19999, 4);
+        assert_eq!(x.overflowing_add(Decimal::TWO), (Decimal::MAX, true));
+        assert_eq!((-x).overflowing_add(-Decimal::TWO), (Decimal::MIN, true));
+        assert_eq!(
+            Decimal::MIN.overflowing_add(Decimal::NEG_ONE),
+            (Decimal::MIN, true)
+        );
+    }
+
+    #[test]
+    fn test_overflowing_add_adjusting_n_frac_digits() {
+        // overflow when adjusting the number of fractional digits
+        let x = Decimal::new_raw(i128::MAX >> 10, 0);
+        let y = Decimal::new_raw(-7, 4);
+        let (z, overflow) = x.overflowing_add(y);
+        assert!(!overflow);
+        assert_eq!(z.coefficient(), (i128::MAX >> 10) * 1000 - 1);
+        assert_eq!(z.n_frac_digits(), 3);
+        let (z, overflow) = y.overflowing_add(-x);
+        assert!(!overflow);
+        assert_eq!(z.coefficient(), -(i128::MAX >> 10) * 1000 - 1);
+        assert_eq!(z.n_frac_digits(), 3);
+        let y = Decimal::new_raw(-1, 4);
+        assert_eq!(x.overflowing_add(y), (x, false));
+        assert_eq!(x.overflowing_add(y).0.n_frac_digits(), 3);
+        // rounded result exceeding the range (i128::MAX is odd, so
+        // RoundHalfEven rounds MAX + 0.5 up)
+        let y = Decimal::new_raw(5, 1);
+        assert_eq!(Decimal::MAX.overflowing_add(y), (Decimal::MAX, true));
+        let res = with_rounding_mode(RoundingMode::RoundHalfDown, || {
+            Decimal::MAX.overflowing_add(y)
+        });
+        assert_eq!(res, (Decimal::MAX, false));
+        let y = Decimal::new_raw(6, 1);
+        assert_eq!(Decimal::MAX.overflowing_add(y), (Decimal::MAX, true));
+        assert_eq!(Decimal::MIN.overflowing_add(-y), (Decimal::MIN, true));
+    }
+
+    #[test]
+    fn test_overflowing_add_rounding_once() {
+        let x = Decimal::new_raw(
+            20_000_000_000_000_000_000_000_000_000_000_000_001,
+            0,
+        );
+        let (z, overflow) = x.overflowing_add(Decimal::new_raw(5, 1));
+        assert!(!overflow);
+        assert_eq!(
+            z,
+            Decimal::new_raw(
+                20_000_000_000_000_000_000_000_000_000_000_000_002,
+                0
+            )
+        );
+        let (z, overflow) = x.overflowing_add(Decimal::new_raw(-5, 1));
+        assert!(!overflow);
+        assert_eq!(z, x - Decimal::ONE);
+        let y = Decimal::new_raw(-3, 1);
+        let res = with_rounding_mode(RoundingMode::RoundDown, || {
+            (x.overflowing_add(y), (-x).overflowing_sub(y))
+        });
+        assert_eq!(
+            res,
+            ((x - Decimal::ONE, false), (Decimal::ONE - x, false))
+        );
+        let res = with_rounding_mode(RoundingMode::RoundHalfUp, || {
+            x.overflowing_add(y)
+        });
+        assert_eq!(res, (x, false));
+    }
+
+    #[test]
+    fn test_overflowing_sub() {
+        let x = Decimal::new_raw(1234567890, 3);
+        let y = Decimal::new_raw(-7, 5);
+        assert_eq!(x.overflowing_sub(y), (x - y, false));
+        assert_eq!((&x).overflowing_sub(y), (x - y, false));
+        let x = Decimal::new_raw(i128::MIN + 99, 2);
+        assert_eq!(x.overflowing_sub(Decimal::ONE), (Decimal::MIN, true));
+        assert_eq!(
+            (-x).overflowing_sub(Decimal::NEG_ONE),
+            (Decimal::MAX, true)
+        );
+        assert_eq!(
+            Decimal::MAX.overflowing_sub(Decimal::MIN),
+            (Decimal::MAX, true)
+        );
+        // overflow when adjusting the number of fractional digits
+        let x = Decimal::new_raw(i128::MAX >> 10, 0);
+        let y = Decimal::new_raw(1, 4);
+        assert_eq!(x.overflowing_sub(y), (x, false));
+        let (z, overflow) = y.overflowing_sub(x);
+        assert!(!overflow);
+        assert_eq!(z, -x);
+        assert_eq!(z.n_frac_digits(), 3);
+    }
+
+    #[test]
+    fn test_overflowing_mul() {
+        let x = Decimal::new_raw(1234567890, 3);
+        let y = Decimal::new_raw(-7, 5);
+        assert_eq!(x.overflowing_mul(y), (x * y, false));
+        assert_eq!(x.overflowing_mul(&y), (x * y, false));
+        let x = Decimal::new_raw(1, 18);
+        let y = Decimal::new_raw(5, 1);
+        assert_eq!(x.overflowing_mul(y), (Decimal::new_raw(0, 18), false));
+        assert_eq!(
+            Decimal::MAX.overflowing_mul(Decimal::TWO),
+            (Decimal::MAX, true)
+        );
+        assert_eq!(
+            Decimal::MAX.overflowing_mul(-Decimal::TWO),
+            (Decimal::MIN, true)
+        );
+        assert_eq!(
+            Decimal::MIN.overflowing_mul(Decimal::MIN),
+            (Decimal::MAX, true)
+        );
+        assert_eq!(
+            Decimal::MIN.overflowing_mul(Decimal::ZERO),
+            (Decimal::ZERO, false)
+        );
+    }
+
+    #[test]
+    fn test_overflowing_int() {
+        let x = Decimal::new_raw(-12345, 2);
+        assert_eq!(x.overflowing_add(7_u8), (x + 7_u8, false));
+        assert_eq!(
+            OverflowingSub::overflowing_sub(7_i64, x),
+            (7_i64 - x, false)
+        );
+        assert_eq!((&x).overflowing_mul(&-3_i32), (x * -3_i32, false));
+        let x = Decimal::new_raw(i128::MAX, 2);
+        assert_eq!(x.overflowing_add(1_u8), (Decimal::MAX, true));
+        assert_eq!(
+            OverflowingSub::overflowing_sub(-1_i8, x),
+            (Decimal::MIN, true)
+        );
+        assert_eq!(x.overflowing_mul(-2_i16), (Decimal::MIN, true));
+        assert_eq!((&2_u64).overflowing_mul(x), (Decimal::MAX, true));
+        assert_eq!(
+            OverflowingAdd::overflowing_add(i128::MAX, &x),
+            (Decimal::MAX, true)
+        );
+    }
+}
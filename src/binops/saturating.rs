@@ -0,0 +1,183 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use crate::{
+    binops::overflowing::{OverflowingAdd, OverflowingMul, OverflowingSub},
+    Decimal,
+};
+
+/// Saturating addition.
+/// Computes `self + rhs`.
+/// Returns the maximum or minimum value of the `Output` type, depending on
+/// the sign of the exact result, if the result can not be represented by
+/// the `Output` type.
+pub trait SaturatingAdd<Rhs = Self> {
+    /// The resulting type after applying `saturating_add`.
+    type Output;
+    /// Returns `self + rhs`, saturated at the numeric bounds of the
+    /// `Output` type.
+    fn saturating_add(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Saturating subtraction.
+/// Computes `self - rhs`.
+/// Returns the maximum or minimum value of the `Output` type, depending on
+/// the sign of the exact result, if the result can not be represented by
+/// the `Output` type.
+pub trait SaturatingSub<Rhs = Self> {
+    /// The resulting type after applying `saturating_sub`.
+    type Output;
+    /// Returns `self - rhs`, saturated at the numeric bounds of the
+    /// `Output` type.
+    fn saturating_sub(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Saturating multiplication.
+/// Computes `self * rhs`.
+/// Returns the maximum or minimum value of the `Output` type, depending on
+/// the sign of the exact result, if the result can not be represented by
+/// the `Output` type.
+pub trait SaturatingMul<Rhs = Self> {
+    /// The resulting type after applying `saturating_mul`.
+    type Output;
+    /// Returns `self * rhs`, saturated at the numeric bounds of the
+    /// `Output` type.
+    fn saturating_mul(self, rhs: Rhs) -> Self::Output;
+}
+
+macro_rules! impl_saturating {
+    (impl $imp:ident, $method:ident, $base_imp:ident, $base_method:ident) => {
+        impl $imp<Decimal> for Decimal {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                $base_imp::$base_method(self, rhs).0
+            }
+        }
+
+        forward_ref_binop!(impl $imp, $method);
+
+        impl_saturating!(
+            impl $imp, $method, $base_imp, $base_method,
+            u8, i8, u16, i16, u32, i32, u64, i64, i128
+        );
+
+        forward_ref_binop_decimal_int!(impl $imp, $method);
+    };
+    (impl $imp:ident, $method:ident, $base_imp:ident, $base_method:ident,
+     $($t:ty),*) => {
+        $(
+        impl $imp<$t> for Decimal {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: $t) -> Self::Output {
+                $base_imp::$base_method(self, rhs).0
+            }
+        }
+
+        impl $imp<Decimal> for $t {
+            type Output = Decimal;
+
+            #[inline]
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                $base_imp::$base_method(self, rhs).0
+            }
+        }
+        )*
+    };
+}
+
+impl_saturating!(
+    impl SaturatingAdd,
+    saturating_add,
+    OverflowingAdd,
+    overflowing_add
+);
+
+impl_saturating!(
+    impl SaturatingSub,
+    saturating_sub,
+    OverflowingSub,
+    overflowing_sub
+);
+
+impl_saturating!(
+    impl SaturatingMul,
+    saturating_mul,
+    OverflowingMul,
+    overflowing_mul
+);
+
+#[cfg(test)]
+mod saturating_decimal_tests {
+    use super::*;
+
+    #[test]
+    fn test_saturating_add_sub() {
+        let x = Decimal::new_raw(1234567890, 3);
+        let y = Decimal::new_raw(-7, 5);
+        assert_eq!(x.saturating_add(y), x + y);
+        assert_eq!((&x).saturating_sub(&y), x - y);
+        let x = Decimal::new_raw(i128::MAX - 19999, 4);
+        assert_eq!(x.saturating_add(Decimal::TWO), Decimal::MAX);
+        assert_eq!(x.saturating_sub(-Decimal::TWO), Decimal::MAX);
+        assert_eq!((-x).saturating_add(-Decimal::TWO), Decimal::MIN);
+        assert_eq!((-x).saturating_sub(&Decimal::TWO), Decimal::MIN);
+        assert_eq!(Decimal::MIN.saturating_sub(Decimal::MAX), Decimal::MIN);
+        // overflow when adjusting the number of fractional digits
+        let x = Decimal::new_raw(i128::MAX >> 10, 0);
+        let y = Decimal::new_raw(1, 4);
+        assert_eq!(x.saturating_sub(y), x);
+        assert_eq!(y.saturating_sub(x), -x);
+        // the exact sum is rounded only once
+        let x = Decimal::new_raw(i128::MAX - 2, 0);
+        let y = Decimal::new_raw(15, 1);
+        assert_eq!(x.saturating_add(y), Decimal::new_raw(i128::MAX - 1, 0));
+        assert_eq!((-x).saturating_sub(y), -x.saturating_add(y));
+        assert_eq!(
+            Decimal::MAX.saturating_add(y - Decimal::ONE),
+            Decimal::MAX
+        );
+        assert_eq!(
+            Decimal::MIN.saturating_sub(y - Decimal::ONE),
+            Decimal::MIN
+        );
+    }
+
+    #[test]
+    fn test_saturating_mul() {
+        let x = Decimal::new_raw(1234567890, 3);
+        let y = Decimal::new_raw(-7, 5);
+        assert_eq!(x.saturating_mul(y), x * y);
+        assert_eq!(Decimal::MAX.saturating_mul(x), Decimal::MAX);
+        assert_eq!(Decimal::MAX.saturating_mul(y), Decimal::MIN);
+        assert_eq!(Decimal::MIN.saturating_mul(&y), Decimal::MAX);
+    }
+
+    #[test]
+    fn test_saturating_int() {
+        let x = Decimal::new_raw(-12345, 2);
+        assert_eq!(x.saturating_add(7_u8), x + 7_u8);
+        assert_eq!(SaturatingSub::saturating_sub(7_u16, &x), 7_u16 - x);
+        assert_eq!((&x).saturating_mul(-3_i32), x * -3_i32);
+        assert_eq!(Decimal::MAX.saturating_add(1_u32), Decimal::MAX);
+        assert_eq!(Decimal::MIN.saturating_sub(1_i64), Decimal::MIN);
+        assert_eq!(
+            SaturatingAdd::saturating_add(i128::MIN, Decimal::MIN),
+            Decimal::MIN
+        );
+        assert_eq!((&-2_i8).saturating_mul(&Decimal::MAX), Decimal::MIN);
+        assert_eq!(
+            SaturatingMul::saturating_mul(u64::MAX, Decimal::MAX),
+            Decimal::MAX
+        );
+    }
+}
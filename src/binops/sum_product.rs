@@ -0,0 +1,166 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{
+    borrow::Borrow,
+    iter::{Product, Sum},
+};
+
+use crate::{CheckedAdd, CheckedMul, Decimal};
+
+macro_rules! impl_sum_product_decimal {
+    (impl $imp:ident, $method:ident, $init:ident, $op:tt) => {
+        impl $imp<Self> for Decimal {
+            /// # Panics
+            ///
+            /// Panics if the result can not be represented by `Decimal`!
+            fn $method<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::$init, |acc, d| acc $op d)
+            }
+        }
+
+        impl<'a> $imp<&'a Self> for Decimal {
+            /// # Panics
+            ///
+            /// Panics if the result can not be represented by `Decimal`!
+            fn $method<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::$init, |acc, d| acc $op d)
+            }
+        }
+    };
+}
+
+impl_sum_product_decimal!(impl Sum, sum, ZERO, +);
+
+impl_sum_product_decimal!(impl Product, product, ONE, *);
+
+/// Checked summation of an iterator over `Decimal`s or references to
+/// `Decimal`s.
+pub trait CheckedSum {
+    /// Returns `Some(sum)` of all items, or `None` if an intermediate result
+    /// can not be represented by `Decimal`.
+    ///
+    /// The sum of an empty iterator is `Decimal::ZERO`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{CheckedSum, Dec, Decimal};
+    /// let amounts = [Dec!(17.5), Dec!(-0.03), Dec!(100)];
+    /// assert_eq!(amounts.iter().checked_sum(), Some(Dec!(117.47)));
+    /// assert_eq!([Decimal::MAX, Dec!(1)].iter().checked_sum(), None);
+    /// ```
+    fn checked_sum(self) -> Option<Decimal>;
+}
+
+impl<I> CheckedSum for I
+where
+    I: Iterator,
+    I::Item: Borrow<Decimal>,
+{
+    fn checked_sum(mut self) -> Option<Decimal> {
+        self.try_fold(Decimal::ZERO, |acc, d| acc.checked_add(*d.borrow()))
+    }
+}
+
+/// Checked multiplication of the items of an iterator over `Decimal`s or
+/// references to `Decimal`s.
+pub trait CheckedProduct {
+    /// Returns `Some(product)` of all items, or `None` if an intermediate
+    /// result can not be represented by `Decimal`.
+    ///
+    /// The product of an empty iterator is `Decimal::ONE`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{CheckedProduct, Dec, Decimal};
+    /// let factors = [Dec!(1.5), Dec!(-0.2), Dec!(4)];
+    /// assert_eq!(factors.into_iter().checked_product(), Some(Dec!(-1.2)));
+    /// assert_eq!([Decimal::MAX, Dec!(2)].iter().checked_product(), None);
+    /// ```
+    fn checked_product(self) -> Option<Decimal>;
+}
+
+impl<I> CheckedProduct for I
+where
+    I: Iterator,
+    I::Item: Borrow<Decimal>,
+{
+    fn checked_product(mut self) -> Option<Decimal> {
+        self.try_fold(Decimal::ONE, |acc, d| acc.checked_mul(*d.borrow()))
+    }
+}
+
+#[cfg(test)]
+mod sum_product_decimal_tests {
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_sum() {
+        let v = [Dec!(1.5), Dec!(-0.003), Dec!(27)];
+        let s: Decimal = v.iter().sum();
+        assert_eq!(s, Dec!(28.497));
+        assert_eq!(s.n_frac_digits(), 3);
+        let s: Decimal = v.into_iter().sum();
+        assert_eq!(s, Dec!(28.497));
+        let s: Decimal = [].iter().sum();
+        assert_eq!(s, Decimal::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sum_overflow() {
+        let _: Decimal = [Decimal::MAX, Decimal::ONE].into_iter().sum();
+    }
+
+    #[test]
+    fn test_product() {
+        let v = [Dec!(1.5), Dec!(-0.2), Dec!(27)];
+        let p: Decimal = v.iter().product();
+        assert_eq!(p, Dec!(-8.1));
+        assert_eq!(p.n_frac_digits(), 2);
+        let p: Decimal = v.into_iter().product();
+        assert_eq!(p, Dec!(-8.1));
+        let p: Decimal = [].iter().product();
+        assert_eq!(p, Decimal::ONE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_product_overflow() {
+        let _: Decimal = [Decimal::MAX, Decimal::TWO].into_iter().product();
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        let v = [Dec!(1.5), Dec!(-0.003), Dec!(27)];
+        assert_eq!(v.iter().checked_sum(), Some(Dec!(28.497)));
+        assert_eq!(v.into_iter().checked_sum(), Some(Dec!(28.497)));
+        assert_eq!([].iter().checked_sum(), Some(Decimal::ZERO));
+        let v = [Decimal::MAX, Decimal::ONE, Decimal::NEG_ONE];
+        assert_eq!(v.iter().checked_sum(), None);
+        let v = [Decimal::MAX, Decimal::NEG_ONE, Decimal::ONE];
+        assert_eq!(v.iter().checked_sum(), Some(Decimal::MAX));
+    }
+
+    #[test]
+    fn test_checked_product() {
+        let v = [Dec!(1.5), Dec!(-0.2), Dec!(27)];
+        assert_eq!(v.iter().checked_product(), Some(Dec!(-8.1)));
+        assert_eq!(v.into_iter().checked_product(), Some(Dec!(-8.1)));
+        assert_eq!([].iter().checked_product(), Some(Decimal::ONE));
+        let v = [Decimal::MAX, Decimal::TWO, Decimal::ZERO];
+        assert_eq!(v.iter().checked_product(), None);
+        // too many fractional digits
+        let v = [Dec!(0.0000000001); 2];
+        assert_eq!(v.iter().checked_product(), None);
+    }
+}
@@ -0,0 +1,245 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use fpdec_core::RoundingMode;
+
+use crate::{CheckedAdd, CheckedSub, Decimal, DecimalError};
+
+/// Rounding of cash amounts to the smallest unit in circulation, like 0.05
+/// for Swiss francs ("Swiss rounding") or 0.10 and 1 in other markets.
+///
+/// A `CashRounding` combines the increment to round to with the
+/// [RoundingMode] to apply, which defaults to [RoundingMode::RoundHalfUp]
+/// as mandated by most jurisdictions using cash rounding. The results
+/// report the rounding difference, so that it can be booked separately.
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{CashRounding, Dec, Decimal};
+/// let chf = CashRounding::new(Dec!(0.05));
+/// let res = chf.round(Dec!(17.975));
+/// assert_eq!(res.rounded().to_string(), "18.00");
+/// assert_eq!(res.difference().to_string(), "0.025");
+/// let res = chf.round_total([Dec!(3.42), Dec!(11.37), Dec!(0.9)]);
+/// assert_eq!(res.rounded().to_string(), "15.70");
+/// assert_eq!(res.difference().to_string(), "0.01");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CashRounding {
+    increment: Decimal,
+    rounding_mode: RoundingMode,
+}
+
+impl CashRounding {
+    /// Creates a new `CashRounding` with the given increment, using
+    /// [RoundingMode::RoundHalfUp].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `increment` is not greater than zero!
+    #[must_use]
+    pub fn new(increment: Decimal) -> Self {
+        assert!(
+            increment.is_positive(),
+            "Increment of cash rounding must be greater than zero."
+        );
+        Self {
+            increment,
+            rounding_mode: RoundingMode::RoundHalfUp,
+        }
+    }
+
+    /// Returns `self` with its rounding mode set to `mode`.
+    #[must_use]
+    #[inline]
+    pub const fn with_rounding_mode(mut self, mode: RoundingMode) -> Self {
+        self.rounding_mode = mode;
+        self
+    }
+
+    /// Increment the amounts are rounded to.
+    #[inline(always)]
+    pub const fn increment(&self) -> Decimal {
+        self.increment
+    }
+
+    /// Rounding mode used to round the amounts.
+    #[must_use]
+    #[inline(always)]
+    pub const fn rounding_mode(&self) -> RoundingMode {
+        self.rounding_mode
+    }
+
+    /// Returns `amount` rounded to an integer multiple of the increment of
+    /// `self`, together with the rounding difference.
+    ///
+    /// The rounded amount has as many fractional digits as the increment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result can not be represented by `Decimal`!
+    #[must_use]
+    pub fn round(&self, amount: Decimal) -> CashRounded {
+        match self.checked_round(amount) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `amount` rounded to an integer multiple of the increment of
+    /// `self`, together with the rounding difference, wrapped in
+    /// `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecimalError::InternalOverflow` if the result can not be
+    /// represented by `Decimal`.
+    pub fn checked_round(
+        &self,
+        amount: Decimal,
+    ) -> Result<CashRounded, DecimalError> {
+        let rounded = amount
+            .checked_round_to_increment(self.increment, self.rounding_mode)?;
+        let difference = rounded
+            .checked_sub(amount)
+            .ok_or(DecimalError::InternalOverflow)?;
+        Ok(CashRounded {
+            rounded,
+            difference,
+        })
+    }
+
+    /// Returns the exact sum of `amounts` rounded to an integer multiple of
+    /// the increment of `self`, together with the rounding difference.
+    ///
+    /// Only the total is rounded, so the difference is at most one
+    /// increment, regardless of the number of amounts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum or the result can not be represented by `Decimal`!
+    #[must_use]
+    pub fn round_total<I>(&self, amounts: I) -> CashRounded
+    where
+        I: IntoIterator<Item = Decimal>,
+    {
+        match self.checked_round_total(amounts) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the exact sum of `amounts` rounded to an integer multiple of
+    /// the increment of `self`, together with the rounding difference,
+    /// wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DecimalError::InternalOverflow` if the sum or the result
+    /// can not be represented by `Decimal`.
+    pub fn checked_round_total<I>(
+        &self,
+        amounts: I,
+    ) -> Result<CashRounded, DecimalError>
+    where
+        I: IntoIterator<Item = Decimal>,
+    {
+        let mut total = Decimal::ZERO;
+        for amount in amounts {
+            total = total
+                .checked_add(amount)
+                .ok_or(DecimalError::InternalOverflow)?;
+        }
+        self.checked_round(total)
+    }
+}
+
+/// Result of applying a [CashRounding] to an amount.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CashRounded {
+    rounded: Decimal,
+    difference: Decimal,
+}
+
+impl CashRounded {
+    /// The rounded amount.
+    #[inline(always)]
+    pub const fn rounded(&self) -> Decimal {
+        self.rounded
+    }
+
+    /// The rounding difference, i.e. the rounded amount minus the original
+    /// amount.
+    #[inline(always)]
+    pub const fn difference(&self) -> Decimal {
+        self.difference
+    }
+}
+
+#[cfg(test)]
+mod cash_rounding_tests {
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_swiss_rounding() {
+        let chf = CashRounding::new(Dec!(0.05));
+        assert_eq!(chf.rounding_mode(), RoundingMode::RoundHalfUp);
+        for (amount, rounded, difference) in [
+            (Dec!(1.02), Dec!(1.00), Dec!(-0.02)),
+            (Dec!(1.025), Dec!(1.05), Dec!(0.025)),
+            (Dec!(1.03), Dec!(1.05), Dec!(0.02)),
+            (Dec!(1.075), Dec!(1.10), Dec!(0.025)),
+            (Dec!(-1.025), Dec!(-1.05), Dec!(-0.025)),
+            (Dec!(7), Dec!(7), Dec!(0)),
+        ] {
+            let res = chf.round(amount);
+            assert_eq!(res.rounded(), rounded);
+            assert_eq!(res.rounded().n_frac_digits(), 2);
+            assert_eq!(res.difference(), difference);
+        }
+    }
+
+    #[test]
+    fn test_other_increments() {
+        let cr = CashRounding::new(Dec!(0.10));
+        let res = cr.round(Dec!(4.95));
+        assert_eq!(res.rounded(), Dec!(5.0));
+        assert_eq!(res.difference(), Dec!(0.05));
+        let cr = CashRounding::new(Dec!(1));
+        let res = cr.round(Dec!(4.49));
+        assert_eq!(res.rounded(), Dec!(4));
+        assert_eq!(res.rounded().n_frac_digits(), 0);
+        assert_eq!(res.difference(), Dec!(-0.49));
+        let cr = cr.with_rounding_mode(RoundingMode::RoundCeiling);
+        assert_eq!(cr.increment(), Dec!(1));
+        assert_eq!(cr.round(Dec!(4.01)).rounded(), Dec!(5));
+    }
+
+    #[test]
+    fn test_round_total() {
+        let chf = CashRounding::new(Dec!(0.05));
+        let amounts = [Dec!(0.02), Dec!(0.02), Dec!(0.02)];
+        let res = chf.round_total(amounts);
+        assert_eq!(res.rounded(), Dec!(0.05));
+        assert_eq!(res.difference(), Dec!(-0.01));
+        let res = chf.round_total([]);
+        assert_eq!(res.rounded(), Decimal::ZERO);
+        assert_eq!(res.difference(), Decimal::ZERO);
+        let res = chf.checked_round_total([Decimal::MAX, Decimal::ONE]);
+        assert_eq!(res.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_increment() {
+        let _ = CashRounding::new(Dec!(-0.05));
+    }
+}
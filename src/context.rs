@@ -0,0 +1,787 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::BitOr,
+};
+
+use fpdec_core::{
+    ten_pow, u128_mul_u128, u256_checked_add, u256_checked_mul_u128,
+    u256_div_rem_u128, u256_sub, RoundingMode, MAX_N_FRAC_DIGITS,
+};
+
+use crate::{
+    decimal256::{
+        u128_shifted_div_rem_code, u256_div_rem_code_ten_pow,
+        u256_round_mag_with_mode,
+    },
+    Decimal, DecimalError,
+};
+
+/// Exceptional conditions which can occur in arithmetic operations done via
+/// a [`Context`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Signal {
+    /// Non-zero digits have been discarded from the result.
+    Inexact,
+    /// Digits (zero or non-zero) have been discarded from the result.
+    Rounded,
+    /// The result would exceed the range of `Decimal`.
+    Overflow,
+    /// A division op called with a divisor equal to zero.
+    DivisionByZero,
+}
+
+impl Signal {
+    // Signals in the order of precedence, used when more than one trapped
+    // signal occurs at once.
+    const ALL: [Self; 4] = [
+        Self::DivisionByZero,
+        Self::Overflow,
+        Self::Inexact,
+        Self::Rounded,
+    ];
+
+    #[inline(always)]
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn _description(&self) -> &str {
+        match self {
+            Self::Inexact => "Non-zero digits discarded from result.",
+            Self::Rounded => "Digits discarded from result.",
+            Self::Overflow => "Result exceeds the range of Decimal.",
+            Self::DivisionByZero => "Division by Zero.",
+        }
+    }
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self._description(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Signal {}
+
+/// A set of [`Signal`]s, used for the traps and the status flags of a
+/// [`Context`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{Signal, Signals};
+/// let signals = Signal::Inexact | Signal::Overflow;
+/// assert!(signals.contains(Signal::Overflow));
+/// assert!(!signals.contains(Signal::Rounded));
+/// assert!(signals.without(Signal::Inexact).without(Signal::Overflow)
+///     .is_empty());
+/// ```
+#[must_use]
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Signals(u8);
+
+impl Signals {
+    /// Set containing no signal
+    pub const EMPTY: Self = Self(0);
+
+    /// Set containing all signals
+    pub const ALL: Self = Self(
+        Signal::Inexact.bit()
+            | Signal::Rounded.bit()
+            | Signal::Overflow.bit()
+            | Signal::DivisionByZero.bit(),
+    );
+
+    /// Returns true if `self` contains `signal`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn contains(self, signal: Signal) -> bool {
+        self.0 & signal.bit() != 0
+    }
+
+    /// Returns true if `self` does not contain any signal.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns a copy of `self` with `signal` added.
+    #[inline(always)]
+    pub const fn with(self, signal: Signal) -> Self {
+        Self(self.0 | signal.bit())
+    }
+
+    /// Returns a copy of `self` with `signal` removed.
+    #[inline(always)]
+    pub const fn without(self, signal: Signal) -> Self {
+        Self(self.0 & !signal.bit())
+    }
+}
+
+impl From<Signal> for Signals {
+    #[inline(always)]
+    fn from(signal: Signal) -> Self {
+        Self(signal.bit())
+    }
+}
+
+impl BitOr for Signals {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<Signal> for Signals {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Signal) -> Self::Output {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for Signal {
+    type Output = Signals;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Signals::from(self).with(rhs)
+    }
+}
+
+impl fmt::Debug for Signals {
+    fn fmt(&self, form: &mut Formatter<'_>) -> fmt::Result {
+        form.debug_set()
+            .entries(Signal::ALL.iter().filter(|s| self.contains(**s)))
+            .finish()
+    }
+}
+
+/// Environment for arithmetic operations on [`Decimal`] values, in the
+/// spirit of Python's `decimal.Context`.
+///
+/// A `Context` carries a [`RoundingMode`] and a maximum number of
+/// fractional digits (aka scale) to which the results of its operations are
+/// rounded. Exceptional conditions occurring during an operation are
+/// reported as [`Signal`]s:
+///
+/// * Each signal that occurs sets the corresponding status flag. The flags
+///   are sticky, i.e. they remain set until they are explicitly cleared.
+/// * If a signal is trapped, the operation returns it as error.
+/// * Otherwise the operation returns a result: the correctly rounded value
+///   in case of `Inexact` or `Rounded`, and `Decimal::MAX` or `Decimal::MIN`
+///   (according to the sign of the exact result) in case of `Overflow` or
+///   `DivisionByZero` (`Decimal::ZERO` when dividing zero by zero).
+///
+/// By default, `Overflow` and `DivisionByZero` are trapped, while `Inexact`
+/// and `Rounded` are not.
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{Context, Dec, Decimal, RoundingMode, Signal};
+/// let mut ctx = Context::new()
+///     .with_rounding_mode(RoundingMode::RoundHalfUp)
+///     .with_max_n_frac_digits(2);
+/// assert_eq!(ctx.add(Dec!(1.5), Dec!(0.25)), Ok(Dec!(1.75)));
+/// assert!(ctx.flags().is_empty());
+/// assert_eq!(ctx.div(Dec!(2), Dec!(3)), Ok(Dec!(0.67)));
+/// assert!(ctx.flags().contains(Signal::Inexact));
+/// ctx.set_trap(Signal::Inexact, true);
+/// assert_eq!(ctx.mul(Dec!(0.5), Dec!(0.05)), Err(Signal::Inexact));
+/// assert_eq!(ctx.div(Dec!(1), Dec!(0)), Err(Signal::DivisionByZero));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Context {
+    rounding_mode: RoundingMode,
+    max_n_frac_digits: u8,
+    traps: Signals,
+    flags: Signals,
+}
+
+impl Default for Context {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Context {
+    /// Creates a new `Context` using the current default [`RoundingMode`]
+    /// and [`MAX_N_FRAC_DIGITS`], trapping `Overflow` and `DivisionByZero`,
+    /// with all status flags cleared.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rounding_mode: RoundingMode::default(),
+            max_n_frac_digits: MAX_N_FRAC_DIGITS,
+            traps: Signal::Overflow | Signal::DivisionByZero,
+            flags: Signals::EMPTY,
+        }
+    }
+
+    /// Returns `self` with its rounding mode set to `mode`.
+    #[must_use]
+    #[inline]
+    pub const fn with_rounding_mode(mut self, mode: RoundingMode) -> Self {
+        self.rounding_mode = mode;
+        self
+    }
+
+    /// Returns `self` with its maximum number of fractional digits set to
+    /// `n_frac_digits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [`MAX_N_FRAC_DIGITS`]!
+    #[must_use]
+    #[inline]
+    pub fn with_max_n_frac_digits(mut self, n_frac_digits: u8) -> Self {
+        #[allow(clippy::manual_assert)]
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+        }
+        self.max_n_frac_digits = n_frac_digits;
+        self
+    }
+
+    /// Returns `self` with its traps set to `traps`.
+    #[must_use]
+    #[inline]
+    pub const fn with_traps(mut self, traps: Signals) -> Self {
+        self.traps = traps;
+        self
+    }
+
+    /// Rounding mode used by the operations of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn rounding_mode(&self) -> RoundingMode {
+        self.rounding_mode
+    }
+
+    /// Maximum number of fractional digits of the results of the operations
+    /// of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn max_n_frac_digits(&self) -> u8 {
+        self.max_n_frac_digits
+    }
+
+    /// Signals trapped by `self`.
+    #[inline(always)]
+    pub const fn traps(&self) -> Signals {
+        self.traps
+    }
+
+    /// Signals which occurred since the flags of `self` have been cleared.
+    #[inline(always)]
+    pub const fn flags(&self) -> Signals {
+        self.flags
+    }
+
+    /// Enables or disables trapping of `signal`.
+    #[inline]
+    pub const fn set_trap(&mut self, signal: Signal, enabled: bool) {
+        self.traps = if enabled {
+            self.traps.with(signal)
+        } else {
+            self.traps.without(signal)
+        };
+    }
+
+    /// Clears all status flags of `self`.
+    #[inline]
+    pub const fn clear_flags(&mut self) {
+        self.flags = Signals::EMPTY;
+    }
+
+    /// Returns `x` + `y`, rounded to the maximum number of fractional digits
+    /// of `self`, if necessary.
+    ///
+    /// # Errors
+    ///
+    /// Returns the trapped signal which occurred, if any.
+    pub fn add(&mut self, x: Decimal, y: Decimal) -> Result<Decimal, Signal> {
+        let (x_neg, (xh, xl), y_neg, (yh, yl), n_frac_digits) =
+            aligned_parts(x, y);
+        if x_neg == y_neg {
+            // x_mag, y_mag < 2¹⁸⁸ => no overflow
+            let sum = u256_checked_add(xh, xl, yh, yl).unwrap_or_default();
+            return self.finish(x_neg, sum, n_frac_digits, Signals::EMPTY);
+        }
+        match (xh, xl).cmp(&(yh, yl)) {
+            Ordering::Less => self.finish(
+                y_neg,
+                u256_sub(yh, yl, xh, xl),
+                n_frac_digits,
+                Signals::EMPTY,
+            ),
+            _ => self.finish(
+                x_neg,
+                u256_sub(xh, xl, yh, yl),
+                n_frac_digits,
+                Signals::EMPTY,
+            ),
+        }
+    }
+
+    /// Returns `x` - `y`, rounded to the maximum number of fractional digits
+    /// of `self`, if necessary.
+    ///
+    /// # Errors
+    ///
+    /// Returns the trapped signal which occurred, if any.
+    #[inline]
+    pub fn sub(&mut self, x: Decimal, y: Decimal) -> Result<Decimal, Signal> {
+        self.add(x, -y)
+    }
+
+    /// Returns `x` * `y`, rounded to the maximum number of fractional digits
+    /// of `self`, if necessary.
+    ///
+    /// # Errors
+    ///
+    /// Returns the trapped signal which occurred, if any.
+    pub fn mul(&mut self, x: Decimal, y: Decimal) -> Result<Decimal, Signal> {
+        let (x_neg, x_mag, x_n_frac_digits) = parts(x);
+        let (y_neg, y_mag, y_n_frac_digits) = parts(y);
+        self.finish(
+            x_neg != y_neg,
+            u128_mul_u128(x_mag, y_mag),
+            x_n_frac_digits + y_n_frac_digits,
+            Signals::EMPTY,
+        )
+    }
+
+    /// Returns `x` / `y`, rounded to the maximum number of fractional digits
+    /// of `self`, if necessary. Trailing fractional zeros are removed from
+    /// the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the trapped signal which occurred, if any.
+    pub fn div(&mut self, x: Decimal, y: Decimal) -> Result<Decimal, Signal> {
+        if y.eq_zero() {
+            return self.div_by_zero(x);
+        }
+        let (x_neg, x_mag, x_n_frac_digits) = parts(x);
+        let (y_neg, y_mag, y_n_frac_digits) = parts(y);
+        let neg = x_neg != y_neg;
+        // x / y = (x_mag / y_mag) * 10^(y_n_frac_digits - x_n_frac_digits)
+        // where -18 <= shift <= 36
+        let shift = i32::from(self.max_n_frac_digits)
+            + i32::from(y_n_frac_digits)
+            - i32::from(x_n_frac_digits);
+        let (quot, code) = u128_shifted_div_rem_code(x_mag, y_mag, shift);
+        let signals = if code == 0 {
+            Signals::EMPTY
+        } else {
+            Signal::Inexact | Signal::Rounded
+        };
+        // quot < 2²⁴⁸ => rounding can't overflow
+        let quot = u256_round_mag_with_mode(
+            neg,
+            quot,
+            code,
+            Some(self.rounding_mode),
+        )
+        .unwrap_or_default();
+        let (quot, n_frac_digits) =
+            strip_trailing_zeros(quot, self.max_n_frac_digits);
+        self.finish(neg, quot, n_frac_digits, signals)
+    }
+
+    /// Returns the integer multiple of `quant` nearest to `x`, according to
+    /// the rounding mode of `self`, like [`Quantize`](crate::Quantize).
+    ///
+    /// # Errors
+    ///
+    /// Returns the trapped signal which occurred, if any. `quant` being
+    /// equal to zero signals `DivisionByZero`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Context, Dec, Decimal, Signal};
+    /// let mut ctx = Context::new();
+    /// assert_eq!(ctx.quantize(Dec!(28.27093), Dec!(0.05)), Ok(Dec!(28.25)));
+    /// assert!(ctx.flags().contains(Signal::Inexact));
+    /// ```
+    pub fn quantize(
+        &mut self,
+        x: Decimal,
+        quant: Decimal,
+    ) -> Result<Decimal, Signal> {
+        if quant.eq_zero() {
+            return self.div_by_zero(x);
+        }
+        let (x_neg, x_mag, x_n_frac_digits) = parts(x);
+        let (q_neg, q_mag, q_n_frac_digits) = parts(quant);
+        // x / quant = x_mag / q_mag * 10^(q_n_frac_digits - x_n_frac_digits)
+        let (mult, code) = u128_shifted_div_rem_code(
+            x_mag,
+            q_mag,
+            i32::from(q_n_frac_digits) - i32::from(x_n_frac_digits),
+        );
+        let mut signals = Signals::EMPTY;
+        if code != 0 {
+            signals = Signal::Inexact | Signal::Rounded;
+        } else if x.n_frac_digits() > quant.n_frac_digits() {
+            signals = signals.with(Signal::Rounded);
+        }
+        // mult * q_mag <= x_mag * 10^18 + q_mag < 2¹⁸⁹ => no overflow
+        let (mh, ml) = u256_round_mag_with_mode(
+            x_neg != q_neg,
+            mult,
+            code,
+            Some(self.rounding_mode),
+        )
+        .unwrap_or_default();
+        let prod = u256_checked_mul_u128(mh, ml, q_mag).unwrap_or_default();
+        self.finish(x_neg, prod, q_n_frac_digits, signals)
+    }
+
+    // Adds `signals` to the flags of `self` and returns the first of them
+    // which is trapped, if any.
+    fn signal(&mut self, signals: Signals) -> Result<(), Signal> {
+        self.flags = self.flags | signals;
+        match Signal::ALL
+            .iter()
+            .find(|s| signals.contains(**s) && self.traps.contains(**s))
+        {
+            Some(signal) => Err(*signal),
+            None => Ok(()),
+        }
+    }
+
+    fn div_by_zero(&mut self, x: Decimal) -> Result<Decimal, Signal> {
+        self.signal(Signal::DivisionByZero.into())?;
+        Ok(saturated(x))
+    }
+
+    // Rounds the exact result given as sign, magnitude of coefficient (as
+    // (hi, lo) with hi * 2¹²⁸ + lo) and number of fractional digits to the
+    // maximum number of fractional digits of `self`, raises the signals
+    // which occurred in doing so (in addition to `signals`) and returns the
+    // result.
+    fn finish(
+        &mut self,
+        neg: bool,
+        mut mag: (u128, u128),
+        mut n_frac_digits: u8,
+        mut signals: Signals,
+    ) -> Result<Decimal, Signal> {
+        if n_frac_digits > self.max_n_frac_digits {
+            let shift = n_frac_digits - self.max_n_frac_digits;
+            let (quot, code) =
+                u256_div_rem_code_ten_pow(mag.0, mag.1, u32::from(shift));
+            signals = signals.with(Signal::Rounded);
+            if code != 0 {
+                signals = signals.with(Signal::Inexact);
+            }
+            // quot < 2²⁵⁶ / 10 => rounding can't overflow
+            mag = u256_round_mag_with_mode(
+                neg,
+                quot,
+                code,
+                Some(self.rounding_mode),
+            )
+            .unwrap_or_default();
+            n_frac_digits = self.max_n_frac_digits;
+        }
+        if let Some(coeff) = to_i128_mag(mag) {
+            self.signal(signals)?;
+            #[allow(clippy::cast_possible_wrap)]
+            let coeff = coeff as i128;
+            return Ok(Decimal::new_raw(
+                if neg { -coeff } else { coeff },
+                n_frac_digits,
+            ));
+        }
+        // Try to make the result fit by removing trailing zeros.
+        let (mag, n_frac_digits) = strip_trailing_zeros(mag, n_frac_digits);
+        match to_i128_mag(mag) {
+            Some(coeff) => {
+                self.signal(signals)?;
+                #[allow(clippy::cast_possible_wrap)]
+                let coeff = coeff as i128;
+                Ok(Decimal::new_raw(
+                    if neg { -coeff } else { coeff },
+                    n_frac_digits,
+                ))
+            }
+            None => {
+                self.signal(
+                    signals
+                        | Signal::Overflow
+                        | Signal::Inexact
+                        | Signal::Rounded,
+                )?;
+                Ok(if neg { Decimal::MIN } else { Decimal::MAX })
+            }
+        }
+    }
+}
+
+// Returns sign, magnitude of coefficient and number of fractional digits of
+// `d`.
+const fn parts(d: Decimal) -> (bool, u128, u8) {
+    (
+        d.is_negative(),
+        d.coefficient().unsigned_abs(),
+        d.n_frac_digits(),
+    )
+}
+
+// Returns sign and magnitude of coefficient of `x` and `y`, scaled to their
+// common number of fractional digits, and that number.
+#[allow(clippy::cast_sign_loss)]
+fn aligned_parts(
+    x: Decimal,
+    y: Decimal,
+) -> (bool, (u128, u128), bool, (u128, u128), u8) {
+    let (x_neg, x_mag, x_n_frac_digits) = parts(x);
+    let (y_neg, y_mag, y_n_frac_digits) = parts(y);
+    let n_frac_digits = x_n_frac_digits.max(y_n_frac_digits);
+    (
+        x_neg,
+        u128_mul_u128(
+            x_mag,
+            ten_pow(n_frac_digits - x_n_frac_digits) as u128,
+        ),
+        y_neg,
+        u128_mul_u128(
+            y_mag,
+            ten_pow(n_frac_digits - y_n_frac_digits) as u128,
+        ),
+        n_frac_digits,
+    )
+}
+
+// Returns the magnitude (hi, lo) as u128, if it does not exceed i128::MAX.
+#[allow(clippy::cast_sign_loss)]
+const fn to_i128_mag((hi, lo): (u128, u128)) -> Option<u128> {
+    if hi == 0 && lo <= i128::MAX as u128 {
+        Some(lo)
+    } else {
+        None
+    }
+}
+
+// Removes trailing zeros from the fractional part of mag * 10^-n_frac_digits.
+fn strip_trailing_zeros(
+    (mut hi, mut lo): (u128, u128),
+    mut n_frac_digits: u8,
+) -> ((u128, u128), u8) {
+    while n_frac_digits > 0 && (hi != 0 || lo != 0) {
+        let (quot, rem) = u256_div_rem_u128(hi, lo, 10);
+        if rem != 0 {
+            break;
+        }
+        (hi, lo) = quot;
+        n_frac_digits -= 1;
+    }
+    if hi == 0 && lo == 0 {
+        n_frac_digits = 0;
+    }
+    ((hi, lo), n_frac_digits)
+}
+
+// Value returned for an untrapped division by zero.
+const fn saturated(x: Decimal) -> Decimal {
+    if x.is_negative() {
+        Decimal::MIN
+    } else if x.eq_zero() {
+        Decimal::ZERO
+    } else {
+        Decimal::MAX
+    }
+}
+
+#[cfg(test)]
+mod context_tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_signals() {
+        let s = Signals::from(Signal::Inexact);
+        assert!(s.contains(Signal::Inexact));
+        assert!(!s.contains(Signal::Overflow));
+        let s = s | Signal::Overflow;
+        assert!(s.contains(Signal::Overflow));
+        assert_eq!(s.without(Signal::Inexact), Signal::Overflow.into());
+        assert!(Signals::EMPTY.is_empty());
+        assert_eq!(Signals::default(), Signals::EMPTY);
+        assert_eq!(
+            Signals::ALL,
+            Signal::Inexact
+                | Signal::Rounded
+                | Signal::Overflow
+                | Signal::DivisionByZero
+        );
+        assert_eq!(format!("{:?}", s), "{Overflow, Inexact}");
+        assert_eq!(Signal::DivisionByZero.to_string(), "Division by Zero.");
+    }
+
+    #[test]
+    fn test_new() {
+        let ctx = Context::default();
+        assert_eq!(ctx.rounding_mode(), RoundingMode::default());
+        assert_eq!(ctx.max_n_frac_digits(), MAX_N_FRAC_DIGITS);
+        assert_eq!(ctx.traps(), Signal::Overflow | Signal::DivisionByZero);
+        assert!(ctx.flags().is_empty());
+        let ctx = Context::new()
+            .with_rounding_mode(RoundingMode::RoundUp)
+            .with_max_n_frac_digits(4)
+            .with_traps(Signals::ALL);
+        assert_eq!(ctx.rounding_mode(), RoundingMode::RoundUp);
+        assert_eq!(ctx.max_n_frac_digits(), 4);
+        assert_eq!(ctx.traps(), Signals::ALL);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_n_frac_digits_exceeded() {
+        let _ = Context::new().with_max_n_frac_digits(MAX_N_FRAC_DIGITS + 1);
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.add(Dec!(17.5), Dec!(-0.001)), Ok(Dec!(17.499)));
+        assert_eq!(ctx.sub(Dec!(-17.5), Dec!(-0.001)), Ok(Dec!(-17.499)));
+        assert_eq!(ctx.sub(Dec!(0.25), Dec!(1)), Ok(Dec!(-0.75)));
+        assert!(ctx.flags().is_empty());
+        let mut ctx = Context::new()
+            .with_max_n_frac_digits(2)
+            .with_rounding_mode(RoundingMode::RoundFloor);
+        assert_eq!(ctx.add(Dec!(17.5), Dec!(-0.001)), Ok(Dec!(17.49)));
+        assert_eq!(ctx.flags(), Signal::Inexact | Signal::Rounded);
+        ctx.clear_flags();
+        assert_eq!(ctx.add(Dec!(17.5), Dec!(0.500)), Ok(Dec!(18.00)));
+        assert_eq!(ctx.flags(), Signal::Rounded.into());
+    }
+
+    #[test]
+    #[allow(clippy::integer_division)]
+    fn test_overflow() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.add(Decimal::MAX, Dec!(1)), Err(Signal::Overflow));
+        assert!(ctx.flags().contains(Signal::Overflow));
+        ctx.set_trap(Signal::Overflow, false);
+        assert_eq!(ctx.sub(Decimal::MIN, Dec!(1)), Ok(Decimal::MIN));
+        assert_eq!(ctx.mul(Decimal::MAX, Dec!(-2)), Ok(Decimal::MIN));
+        // exact result exceeds Decimal, but rounded result does not
+        let mut ctx = Context::new().with_max_n_frac_digits(0);
+        let x = Decimal::new_raw(i128::MAX, 1);
+        assert_eq!(
+            ctx.add(x, Dec!(0.5)),
+            Ok(Decimal::new_raw(i128::MAX / 10 + 1, 0))
+        );
+        assert!(!ctx.flags().contains(Signal::Overflow));
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut ctx = Context::new();
+        let x = Dec!(1.234567890123456789);
+        assert_eq!(ctx.mul(x, Dec!(2)), Ok(Dec!(2.469135780246913578)));
+        assert!(ctx.flags().is_empty());
+        assert_eq!(ctx.mul(x, x), Ok(Dec!(1.524157875323883675)));
+        assert_eq!(ctx.flags(), Signal::Inexact | Signal::Rounded);
+        let mut ctx = Context::new()
+            .with_max_n_frac_digits(2)
+            .with_rounding_mode(RoundingMode::RoundHalfUp)
+            .with_traps(Signals::ALL);
+        assert_eq!(ctx.mul(Dec!(1.5), Dec!(0.3)), Ok(Dec!(0.45)));
+        assert_eq!(ctx.mul(Dec!(-0.5), Dec!(0.05)), Err(Signal::Inexact));
+        assert_eq!(ctx.flags(), Signal::Inexact | Signal::Rounded);
+        assert_eq!(ctx.mul(Dec!(0.5), Dec!(0.20)), Err(Signal::Rounded));
+    }
+
+    #[test]
+    fn test_div() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.div(Dec!(17), Dec!(-2.00)), Ok(Dec!(-8.5)));
+        assert!(ctx.flags().is_empty());
+        assert_eq!(ctx.div(Dec!(2), Dec!(3)), Ok(Dec!(0.666666666666666667)));
+        assert_eq!(ctx.flags(), Signal::Inexact | Signal::Rounded);
+        let mut ctx = Context::new()
+            .with_max_n_frac_digits(1)
+            .with_rounding_mode(RoundingMode::RoundDown);
+        assert_eq!(ctx.div(Dec!(-2), Dec!(3)), Ok(Dec!(-0.6)));
+        assert_eq!(ctx.div(Dec!(1234.56), Dec!(0.01)), Ok(Dec!(123456)));
+        assert_eq!(ctx.div(Decimal::MAX, Dec!(0.1)), Err(Signal::Overflow));
+        let mut ctx = Context::new()
+            .with_max_n_frac_digits(0)
+            .with_rounding_mode(RoundingMode::RoundHalfEven);
+        assert_eq!(ctx.div(Dec!(5.1), Dec!(2)), Ok(Dec!(3)));
+        assert_eq!(ctx.div(Dec!(5.0), Dec!(2)), Ok(Dec!(2)));
+        assert_eq!(
+            ctx.div(Decimal::MAX, Decimal::DELTA),
+            Err(Signal::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.div(Dec!(1), Dec!(0)), Err(Signal::DivisionByZero));
+        assert_eq!(ctx.flags(), Signal::DivisionByZero.into());
+        ctx.set_trap(Signal::DivisionByZero, false);
+        assert_eq!(ctx.div(Dec!(1), Dec!(0)), Ok(Decimal::MAX));
+        assert_eq!(ctx.div(Dec!(-1), Dec!(0)), Ok(Decimal::MIN));
+        assert_eq!(ctx.div(Dec!(0), Dec!(0)), Ok(Decimal::ZERO));
+        assert_eq!(ctx.quantize(Dec!(1), Dec!(0)), Ok(Decimal::MAX));
+    }
+
+    #[test]
+    fn test_quantize() {
+        let mut ctx = Context::new();
+        assert_eq!(
+            ctx.quantize(Dec!(-28.27093), Dec!(0.05)),
+            Ok(Dec!(-28.25))
+        );
+        assert_eq!(ctx.flags(), Signal::Inexact | Signal::Rounded);
+        ctx.clear_flags();
+        assert_eq!(ctx.quantize(Dec!(28.27093), Dec!(5)), Ok(Dec!(30)));
+        ctx.clear_flags();
+        assert_eq!(ctx.quantize(Dec!(28.25), Dec!(0.05)), Ok(Dec!(28.25)));
+        assert!(ctx.flags().is_empty());
+        assert_eq!(ctx.quantize(Dec!(28.250), Dec!(0.01)), Ok(Dec!(28.25)));
+        assert_eq!(ctx.flags(), Signal::Rounded.into());
+        let mut ctx =
+            Context::new().with_rounding_mode(RoundingMode::RoundCeiling);
+        assert_eq!(ctx.quantize(Dec!(-28.27), Dec!(0.1)), Ok(Dec!(-28.2)));
+        assert_eq!(ctx.quantize(Dec!(-28.27), Dec!(-0.1)), Ok(Dec!(-28.3)));
+        let mut ctx =
+            Context::new().with_rounding_mode(RoundingMode::RoundHalfEven);
+        assert_eq!(ctx.quantize(Dec!(2.51), Dec!(5)), Ok(Dec!(5)));
+        assert_eq!(ctx.quantize(Dec!(2.50), Dec!(5)), Ok(Dec!(0)));
+        assert_eq!(
+            ctx.quantize(Decimal::MAX, Decimal::DELTA),
+            Ok(Decimal::MAX)
+        );
+    }
+}
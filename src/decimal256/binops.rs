@@ -0,0 +1,566 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{
+    cmp::Ordering,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use fpdec_core::{
+    ten_pow, u256_checked_add, u256_sub, RoundingMode, MAX_N_FRAC_DIGITS,
+};
+
+use super::{
+    div_rem_code, frac_code, limbs_div_rem_u128, round_mag_with_mode,
+    scaled_mag, u256_mul_u256, u256_round_mag_with_mode, Decimal256,
+};
+use crate::{
+    bigint::UBig, CheckedAdd, CheckedMul, CheckedSub, Decimal, DecimalError,
+    DivRounded,
+};
+
+// Implements binary operators "&T op U", "T op &U", "&T op &U"
+// based on "T op U" where T is Decimal256 and U is Decimal256 or Decimal
+macro_rules! forward_ref_binop_256 {
+    (impl $imp:ident, $method:ident, $rhs:ty) => {
+        impl<'a> $imp<$rhs> for &'a Decimal256 {
+            type Output = <Decimal256 as $imp<$rhs>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+        impl $imp<&$rhs> for Decimal256 {
+            type Output = <Decimal256 as $imp<$rhs>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+        impl $imp<&$rhs> for &Decimal256 {
+            type Output = <Decimal256 as $imp<$rhs>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+// Implements "T op= U" and "T op= &U" based on "T op U"
+macro_rules! forward_op_assign_256 {
+    (impl $imp:ident, $method:ident, $base_imp:ident, $base_method:ident,
+     $rhs:ty) => {
+        impl $imp<$rhs> for Decimal256 {
+            #[inline(always)]
+            fn $method(&mut self, rhs: $rhs) {
+                *self = $base_imp::$base_method(*self, rhs);
+            }
+        }
+        impl $imp<&$rhs> for Decimal256 {
+            #[inline(always)]
+            fn $method(&mut self, rhs: &$rhs) {
+                *self = $base_imp::$base_method(*self, *rhs);
+            }
+        }
+    };
+}
+
+impl Neg for Decimal256 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self {
+            neg: !self.neg && !self.eq_zero(),
+            ..self
+        }
+    }
+}
+
+impl Neg for &Decimal256 {
+    type Output = Decimal256;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
+
+impl CheckedAdd<Self> for Decimal256 {
+    type Output = Option<Self>;
+
+    fn checked_add(self, rhs: Self) -> Self::Output {
+        let n_frac_digits = self.n_frac_digits.max(rhs.n_frac_digits);
+        let (xh, xl) = scaled_mag(self, n_frac_digits)?;
+        let (yh, yl) = scaled_mag(rhs, n_frac_digits)?;
+        if self.neg == rhs.neg {
+            let (h, l) = u256_checked_add(xh, xl, yh, yl)?;
+            return Self::from_parts(self.neg, h, l, n_frac_digits);
+        }
+        match (xh, xl).cmp(&(yh, yl)) {
+            Ordering::Less => {
+                let (h, l) = u256_sub(yh, yl, xh, xl);
+                Self::from_parts(rhs.neg, h, l, n_frac_digits)
+            }
+            _ => {
+                let (h, l) = u256_sub(xh, xl, yh, yl);
+                Self::from_parts(self.neg, h, l, n_frac_digits)
+            }
+        }
+    }
+}
+
+forward_ref_binop_256!(impl CheckedAdd, checked_add, Decimal256);
+
+impl CheckedSub<Self> for Decimal256 {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_sub(self, rhs: Self) -> Self::Output {
+        self.checked_add(-rhs)
+    }
+}
+
+forward_ref_binop_256!(impl CheckedSub, checked_sub, Decimal256);
+
+impl CheckedMul<Self> for Decimal256 {
+    type Output = Option<Self>;
+
+    /// Returns `Some(self * rhs)`, rounded to [`MAX_N_FRAC_DIGITS`] if
+    /// necessary, or `None` if the result can not be represented by
+    /// `Decimal256`.
+    fn checked_mul(self, rhs: Self) -> Self::Output {
+        let neg = self.neg != rhs.neg;
+        let mut prod = u256_mul_u256(self.hi, self.lo, rhs.hi, rhs.lo);
+        let n_frac_digits = self.n_frac_digits + rhs.n_frac_digits;
+        if n_frac_digits <= MAX_N_FRAC_DIGITS {
+            if prod[0] != 0 || prod[1] != 0 {
+                return None;
+            }
+            return Self::from_parts(neg, prod[2], prod[3], n_frac_digits);
+        }
+        let divisor = ten_pow(n_frac_digits - MAX_N_FRAC_DIGITS) as u128;
+        let r = limbs_div_rem_u128(&mut prod, divisor);
+        if prod[0] != 0 || prod[1] != 0 {
+            return None;
+        }
+        let (hi, lo) = u256_round_mag_with_mode(
+            neg,
+            (prod[2], prod[3]),
+            frac_code(r, divisor, false),
+            None,
+        )?;
+        Self::from_parts(neg, hi, lo, MAX_N_FRAC_DIGITS)
+    }
+}
+
+forward_ref_binop_256!(impl CheckedMul, checked_mul, Decimal256);
+
+macro_rules! impl_op_256 {
+    (impl $imp:ident, $method:ident, $checked_method:ident) => {
+        impl $imp<Self> for Decimal256 {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self::Output {
+                match self.$checked_method(rhs) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        forward_ref_binop_256!(impl $imp, $method, Decimal256);
+
+        impl $imp<Decimal> for Decimal256 {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                $imp::$method(self, Self::from(rhs))
+            }
+        }
+
+        forward_ref_binop_256!(impl $imp, $method, Decimal);
+    };
+}
+
+impl_op_256!(impl Add, add, checked_add);
+impl_op_256!(impl Sub, sub, checked_sub);
+impl_op_256!(impl Mul, mul, checked_mul);
+
+forward_op_assign_256!(impl AddAssign, add_assign, Add, add, Decimal256);
+forward_op_assign_256!(impl SubAssign, sub_assign, Sub, sub, Decimal256);
+forward_op_assign_256!(impl MulAssign, mul_assign, Mul, mul, Decimal256);
+forward_op_assign_256!(impl AddAssign, add_assign, Add, add, Decimal);
+forward_op_assign_256!(impl SubAssign, sub_assign, Sub, sub, Decimal);
+forward_op_assign_256!(impl MulAssign, mul_assign, Mul, mul, Decimal);
+
+impl Decimal256 {
+    /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according to the
+    /// current [RoundingMode](crate::RoundingMode), wrapped in
+    /// `Option::Some`, or `Option::None` if `rhs` equals zero,
+    /// `n_frac_digits` exceeds [`MAX_N_FRAC_DIGITS`] or the result can not
+    /// be represented by `Decimal256`.
+    #[must_use]
+    #[inline]
+    pub fn checked_div_rounded(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+    ) -> Option<Self> {
+        self.checked_div_rounded_with_mode(rhs, n_frac_digits, None)
+    }
+
+    fn checked_div_rounded_with_mode(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: Option<RoundingMode>,
+    ) -> Option<Self> {
+        if rhs.eq_zero() || n_frac_digits > MAX_N_FRAC_DIGITS {
+            return None;
+        }
+        // self / rhs * 10^n_frac_digits
+        // = (self.mag * 10^(n_frac_digits + rhs.n_frac_digits))
+        //   / (rhs.mag * 10^self.n_frac_digits)
+        let shift = n_frac_digits + rhs.n_frac_digits;
+        let (num, den) = if shift >= self.n_frac_digits {
+            (
+                self.mag().mul(&UBig::ten_pow(u32::from(
+                    shift - self.n_frac_digits,
+                ))),
+                rhs.mag(),
+            )
+        } else {
+            (
+                self.mag(),
+                rhs.mag().mul(&UBig::ten_pow(u32::from(
+                    self.n_frac_digits - shift,
+                ))),
+            )
+        };
+        let neg = self.neg != rhs.neg;
+        let (q, code) = div_rem_code(&num, &den);
+        Self::from_ubig(
+            neg,
+            &round_mag_with_mode(neg, q, code, mode),
+            n_frac_digits,
+        )
+    }
+}
+
+impl DivRounded<Self> for Decimal256 {
+    type Output = Self;
+
+    /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according to
+    /// the current [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero, `n_frac_digits` exceeds
+    /// [`MAX_N_FRAC_DIGITS`] or the result can not be represented by
+    /// `Decimal256`!
+    #[inline]
+    fn div_rounded(self, rhs: Self, n_frac_digits: u8) -> Self::Output {
+        self.div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according to
+    /// `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero, `n_frac_digits` exceeds
+    /// [`MAX_N_FRAC_DIGITS`] or the result can not be represented by
+    /// `Decimal256`!
+    fn div_rounded_with_mode(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self::Output {
+        #[allow(clippy::manual_assert)]
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+        }
+        #[allow(clippy::manual_assert)]
+        if rhs.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        match self.checked_div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            Some(mode),
+        ) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+impl DivRounded<Decimal256> for &Decimal256 {
+    type Output = Decimal256;
+
+    #[inline(always)]
+    fn div_rounded(self, rhs: Decimal256, n_frac_digits: u8) -> Decimal256 {
+        self.div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    #[inline(always)]
+    fn div_rounded_with_mode(
+        self,
+        rhs: Decimal256,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Decimal256 {
+        DivRounded::div_rounded_with_mode(*self, rhs, n_frac_digits, mode)
+    }
+}
+
+impl DivRounded<&Self> for Decimal256 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div_rounded(self, rhs: &Self, n_frac_digits: u8) -> Self {
+        self.div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    #[inline(always)]
+    fn div_rounded_with_mode(
+        self,
+        rhs: &Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self {
+        DivRounded::div_rounded_with_mode(self, *rhs, n_frac_digits, mode)
+    }
+}
+
+impl DivRounded<&Decimal256> for &Decimal256 {
+    type Output = Decimal256;
+
+    #[inline(always)]
+    fn div_rounded(self, rhs: &Decimal256, n_frac_digits: u8) -> Decimal256 {
+        self.div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    #[inline(always)]
+    fn div_rounded_with_mode(
+        self,
+        rhs: &Decimal256,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Decimal256 {
+        DivRounded::div_rounded_with_mode(*self, *rhs, n_frac_digits, mode)
+    }
+}
+
+macro_rules! impl_sum_256 {
+    ($($t:ty),*) => {
+        $(
+        impl Sum<$t> for Decimal256 {
+            /// # Panics
+            ///
+            /// Panics if the result can not be represented by `Decimal256`!
+            fn sum<I: Iterator<Item = $t>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, d| acc + d)
+            }
+        }
+
+        impl<'a> Sum<&'a $t> for Decimal256 {
+            /// # Panics
+            ///
+            /// Panics if the result can not be represented by `Decimal256`!
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, d| acc + d)
+            }
+        }
+        )*
+    };
+}
+
+impl_sum_256!(Decimal256, Decimal);
+
+#[cfg(test)]
+mod decimal256_binops_tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_neg() {
+        let x = Decimal256::from(Dec!(-1.5));
+        assert_eq!(-x, Dec!(1.5));
+        assert_eq!(-&x, Dec!(1.5));
+        assert!(!(-Decimal256::ZERO).is_negative());
+        assert_eq!(-Decimal256::MAX, Decimal256::MIN);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_add_sub() {
+        let x = Decimal256::from(Dec!(17.5));
+        let y = Decimal256::from(Dec!(-0.001));
+        let z = x + y;
+        assert_eq!(z, Dec!(17.499));
+        assert_eq!(z.n_frac_digits(), 3);
+        assert_eq!(x - y, Dec!(17.501));
+        assert_eq!(y - x, Dec!(-17.501));
+        assert_eq!(y + x, z);
+        assert_eq!(&x + &y, z);
+        assert_eq!(x + Dec!(-0.001), z);
+        assert_eq!(x - x, Decimal256::ZERO);
+        assert!(!(x - x).is_negative());
+        let mut a = x;
+        a += y;
+        a -= Dec!(17.499);
+        a += &Decimal256::ONE;
+        assert_eq!(a, Decimal256::ONE);
+    }
+
+    #[test]
+    fn test_add_beyond_decimal() {
+        let mut total = Decimal256::ZERO;
+        for _ in 0..1000 {
+            total += Decimal::MAX;
+        }
+        assert_eq!(
+            total.to_string(),
+            "170141183460469231731687303715884105727000"
+        );
+        for _ in 0..999 {
+            total -= Decimal::MAX;
+        }
+        assert_eq!(total, Decimal::MAX);
+        let d = Decimal::new_raw(i128::MAX, 18);
+        let total: Decimal256 = [d, d, d].iter().sum();
+        assert_eq!(total.checked_into_decimal_rounded(18), None);
+        assert_eq!(
+            total.checked_into_decimal_rounded(17),
+            Some(Decimal::new_raw(
+                51042355038140769519506191114765231718,
+                17
+            ))
+        );
+    }
+
+    #[test]
+    fn test_checked_add_sub() {
+        assert!(Decimal256::MAX.checked_add(Decimal256::ONE).is_none());
+        assert!(Decimal256::MIN.checked_sub(Decimal256::ONE).is_none());
+        assert_eq!(
+            Decimal256::MAX.checked_add(Decimal256::from(-1)),
+            Decimal256::MAX.checked_sub(Decimal256::ONE)
+        );
+        // alignment exceeds 256 bits
+        let d = Decimal256::from(Decimal::DELTA);
+        assert!(Decimal256::MAX.checked_add(d).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_overflow() {
+        let _ = Decimal256::MAX + Decimal256::ONE;
+    }
+
+    #[test]
+    fn test_mul() {
+        let x = Decimal256::from(Decimal::MAX);
+        let y = x * x;
+        assert_eq!(
+            y.to_string(),
+            "28948022309329048855892746252171976962977213799489202546401021394\
+             546514198529"
+        );
+        assert!(y.checked_mul(Decimal256::from(2)).is_some());
+        assert!(y.checked_mul(Decimal256::from(4)).is_none());
+        let x = Decimal256::from(Dec!(1.5));
+        let y = Decimal256::from(Dec!(-2.50000001));
+        assert_eq!(x * y, Dec!(-3.750000015));
+        assert_eq!(x * Dec!(-2.50000001), Dec!(-3.750000015));
+        let mut a = x;
+        a *= x;
+        assert_eq!(a, Dec!(2.25));
+    }
+
+    #[test]
+    fn test_mul_rounded_to_max_n_frac_digits() {
+        let x = Decimal256::from(Decimal::new_raw(-1234567890123456789, 18));
+        let y = x * x;
+        assert_eq!(y.n_frac_digits(), 18);
+        assert_eq!(y, Decimal::new_raw(1524157875323883675, 18));
+    }
+
+    #[test]
+    fn test_mul_beyond_256_bits() {
+        // 2¹²⁸ * 10⁻¹⁸
+        let x = (Decimal256::from(u128::MAX) + Decimal256::ONE)
+            * Decimal256::from(Decimal::DELTA);
+        let z = x * x;
+        assert_eq!(z.n_frac_digits(), MAX_N_FRAC_DIGITS);
+        assert_eq!(
+            z.to_string(),
+            "115792089237316195423570985008687907853269.984665640564039458"
+        );
+        assert!(x.checked_mul(Decimal256::MAX).is_none());
+        assert!(Decimal256::MAX.checked_mul(Decimal256::MAX).is_none());
+    }
+
+    #[test]
+    fn test_div_rounded() {
+        let x = Decimal256::from(Dec!(28.27093));
+        let y = Decimal256::from(Dec!(0.03));
+        let z = x.div_rounded(y, 3);
+        assert_eq!(z, Dec!(942.364));
+        assert_eq!(z.n_frac_digits(), 3);
+        let z = (-x).div_rounded(&y, 0);
+        assert_eq!(z, Dec!(-942));
+        let x =
+            Decimal256::from(Decimal::MAX) * Decimal256::from(Decimal::MAX);
+        let z = x.div_rounded(Decimal256::from(Decimal::MAX), 0);
+        assert_eq!(z, Decimal::MAX);
+        let z =
+            Decimal256::from(Dec!(1.23456)).div_rounded(Decimal256::ONE, 2);
+        assert_eq!(z, Dec!(1.23));
+        assert!(x.checked_div_rounded(Decimal256::ZERO, 2).is_none());
+        assert!(x.checked_div_rounded(Decimal256::ONE, 19).is_none());
+        assert!(x
+            .checked_div_rounded(Decimal256::from(Dec!(0.1)), 0)
+            .is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_rounded_by_zero() {
+        let _ = Decimal256::ONE.div_rounded(Decimal256::ZERO, 2);
+    }
+}
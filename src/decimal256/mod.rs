@@ -0,0 +1,812 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{cmp::Ordering, convert::TryFrom, fmt};
+
+use fpdec_core::{
+    round_quot, ten_pow, u128_mul_u128, u256_checked_add,
+    u256_checked_mul_u128, u256_div_rem_u128, Round, RoundingMode,
+    MAX_N_FRAC_DIGITS,
+};
+
+use crate::{bigint::UBig, format::RevBuf, Decimal, DecimalError};
+
+mod binops;
+
+// Max value of the high part of the coefficient's magnitude
+const HI_MAX: u128 = i128::MAX as u128;
+
+/// Represents a decimal number as a 256-bit coefficient combined with a
+/// value (`u8`) specifying the number of fractional decimal digits.
+///
+/// `Decimal256` is meant for intermediate results which may exceed the range
+/// of [`Decimal`], for example when summing up a large number of values.
+/// It supports addition, subtraction, multiplication, rounded division and
+/// comparison with `Decimal`. The final result can be narrowed back into a
+/// `Decimal`.
+///
+/// The number of fractional digits can be in the range 0 ..
+/// [`MAX_N_FRAC_DIGITS`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{Dec, Decimal, Decimal256};
+/// let total: Decimal256 = [Decimal::MAX, Decimal::MAX, -Decimal::MAX]
+///     .iter()
+///     .sum();
+/// assert_eq!(total, Decimal::MAX);
+/// assert_eq!(Decimal::try_from(total).unwrap(), Decimal::MAX);
+/// ```
+#[must_use]
+#[derive(Copy, Clone, Default)]
+pub struct Decimal256 {
+    // sign and magnitude (hi * 2^128 + lo) of the coefficient;
+    // zero is never negative and hi <= HI_MAX
+    neg: bool,
+    hi: u128,
+    lo: u128,
+    n_frac_digits: u8,
+}
+
+impl Decimal256 {
+    // Returns None if the magnitude exceeds 2^255 - 1.
+    const fn from_parts(
+        neg: bool,
+        hi: u128,
+        lo: u128,
+        n_frac_digits: u8,
+    ) -> Option<Self> {
+        if hi > HI_MAX {
+            return None;
+        }
+        Some(Self {
+            neg: neg && (hi != 0 || lo != 0),
+            hi,
+            lo,
+            n_frac_digits,
+        })
+    }
+
+    fn from_ubig(neg: bool, mag: &UBig, n_frac_digits: u8) -> Option<Self> {
+        let (hi, lo) = mag.to_u256()?;
+        Self::from_parts(neg, hi, lo, n_frac_digits)
+    }
+
+    fn mag(self) -> UBig {
+        UBig::from_u256(self.hi, self.lo)
+    }
+
+    /// Number of fractional decimal digits of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn n_frac_digits(self) -> u8 {
+        self.n_frac_digits
+    }
+
+    /// Returns true if `self` equals zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn eq_zero(self) -> bool {
+        self.hi == 0 && self.lo == 0
+    }
+
+    /// Returns true if `self` is less than zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_negative(self) -> bool {
+        self.neg
+    }
+
+    /// Returns true if `self` is greater than zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_positive(self) -> bool {
+        !self.neg && !self.eq_zero()
+    }
+
+    /// Returns the value of `self`, rounded to `n_frac_digits` fractional
+    /// digits according to the current [RoundingMode](crate::RoundingMode),
+    /// as `Decimal`, wrapped in `Option::Some`, or `Option::None` if the
+    /// result can not be represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Decimal256, DivRounded};
+    /// let d = Decimal256::from(Decimal::MAX) * Decimal256::from(3);
+    /// let q = d.div_rounded(Decimal256::from(7), 5);
+    /// let r = q.checked_into_decimal_rounded(0).unwrap();
+    /// assert_eq!(r, Dec!(72917650054486813599294558735378902454));
+    /// assert!(d.checked_into_decimal_rounded(0).is_none());
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn checked_into_decimal_rounded(
+        self,
+        n_frac_digits: u8,
+    ) -> Option<Decimal> {
+        let n_frac_digits = n_frac_digits.min(MAX_N_FRAC_DIGITS);
+        Decimal::try_from(self.checked_round(n_frac_digits as i8)?).ok()
+    }
+
+    /// Additive identity
+    pub const ZERO: Self = Self {
+        neg: false,
+        hi: 0,
+        lo: 0,
+        n_frac_digits: 0,
+    };
+
+    /// Multiplicative identity
+    pub const ONE: Self = Self {
+        neg: false,
+        hi: 0,
+        lo: 1,
+        n_frac_digits: 0,
+    };
+
+    /// Maximum value representable by `Decimal256` = 2²⁵⁵ - 1
+    pub const MAX: Self = Self {
+        neg: false,
+        hi: HI_MAX,
+        lo: u128::MAX,
+        n_frac_digits: 0,
+    };
+
+    /// Minimum value representable by `Decimal256` = -2²⁵⁵ + 1
+    pub const MIN: Self = Self {
+        neg: true,
+        hi: HI_MAX,
+        lo: u128::MAX,
+        n_frac_digits: 0,
+    };
+}
+
+// Fractions of a unit are represented as multiples of 2⁻⁶⁴.
+const FRAC_ONE: u128 = 1 << 64;
+const FRAC_HALF: u128 = FRAC_ONE >> 1;
+
+// Round sign * (q + f), where q is an int and f is a fraction given in units
+// of 2⁻⁶⁴ by `code` (see `div_rem_code`), according to the current
+// RoundingMode, and return the magnitude of the result.
+#[inline]
+pub(crate) fn round_mag(neg: bool, q: UBig, code: u128) -> UBig {
+    round_mag_with_mode(neg, q, code, None)
+}
+
+// Same as `round_mag`, but using the given RoundingMode, if any.
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn round_mag_with_mode(
+    neg: bool,
+    mut q: UBig,
+    code: u128,
+    mode: Option<RoundingMode>,
+) -> UBig {
+    if code == 0 {
+        return q;
+    }
+    // The rounding modes only need to know the last digit of q.
+    let last = q.clone().div_u64(10) as i128;
+    let incr = if neg {
+        round_quot(-last - 1, FRAC_ONE - code, FRAC_ONE, mode) == -last - 1
+    } else {
+        round_quot(last, code, FRAC_ONE, mode) == last + 1
+    };
+    if incr {
+        q = q.add(&UBig::from_u128(1));
+    }
+    q
+}
+
+// Return (q, code) with q = x / y and code = (x % y) / y in units of 2⁻⁶⁴,
+// adjusted so that code is 0 only if x % y = 0 and code is 2⁶³ (i.e. 1/2)
+// only if x % y = y / 2.
+pub(crate) fn div_rem_code(x: &UBig, y: &UBig) -> (UBig, u128) {
+    let (q, r) = x.div_rem(y);
+    if r.is_zero() {
+        return (q, 0);
+    }
+    // r < y => frac < 2⁶⁴
+    let frac = r.shl(64).div_rem(y).0.to_u128().unwrap_or(FRAC_ONE - 1);
+    let code = match r.shl(1).cmp(y) {
+        Ordering::Less => frac.max(1),
+        Ordering::Equal => FRAC_HALF,
+        Ordering::Greater => frac.max(FRAC_HALF + 1),
+    };
+    (q, code)
+}
+
+// Return the code of r / divisor in units of 2⁻⁶⁴, adjusted like in
+// `div_rem_code`, where r < divisor. `sticky` tells whether there are
+// non-zero digits below r, i.e. whether the fraction is slightly greater
+// than r / divisor.
+pub(crate) fn frac_code(r: u128, divisor: u128, sticky: bool) -> u128 {
+    if r == 0 && !sticky {
+        return 0;
+    }
+    // r < divisor => r * 2⁶⁴ / divisor < 2⁶⁴
+    let (fh, fl) = u128_mul_u128(r, FRAC_ONE);
+    let ((_, frac), _) = u256_div_rem_u128(fh, fl, divisor);
+    let sticky_ord = if sticky {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    };
+    // r < divisor => 2 * r <=> divisor equals r <=> divisor - r
+    match r.cmp(&(divisor - r)).then(sticky_ord) {
+        Ordering::Less => frac.max(1),
+        Ordering::Equal => FRAC_HALF,
+        Ordering::Greater => frac.max(FRAC_HALF + 1),
+    }
+}
+
+// Same as `div_rem_code`, for x = hi * 2¹²⁸ + lo and y = 10^shift.
+pub(crate) fn u256_div_rem_code_ten_pow(
+    mut hi: u128,
+    mut lo: u128,
+    mut shift: u32,
+) -> ((u128, u128), u128) {
+    // Divide by the lower powers of ten first, keeping track of non-zero
+    // remainders, until the remaining divisor fits into an u128.
+    let mut sticky = false;
+    while shift > 38 {
+        let step = (shift - 38).min(38);
+        #[allow(clippy::cast_possible_truncation)]
+        let divisor = ten_pow(step as u8) as u128;
+        let ((qh, ql), r) = u256_div_rem_u128(hi, lo, divisor);
+        sticky |= r != 0;
+        (hi, lo) = (qh, ql);
+        shift -= step;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let divisor = ten_pow(shift as u8) as u128;
+    let (q, r) = u256_div_rem_u128(hi, lo, divisor);
+    (q, frac_code(r, divisor, sticky))
+}
+
+// Same as `div_rem_code`, for x * 10^shift and y, where -38 <= shift <= 38.
+// The quotient is returned as (hi, lo) with hi * 2¹²⁸ + lo.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::integer_division)]
+pub(crate) fn u128_shifted_div_rem_code(
+    x: u128,
+    y: u128,
+    shift: i32,
+) -> ((u128, u128), u128) {
+    if shift >= 0 {
+        let (hi, lo) = u128_mul_u128(x, ten_pow(shift as u8) as u128);
+        let (quot, rem) = u256_div_rem_u128(hi, lo, y);
+        (quot, frac_code(rem, y, false))
+    } else {
+        // x / (y * 10^-shift) = (x / y) / 10^-shift, where the remainder of
+        // x / y only adds to the fraction.
+        let (quot, rem) = (x / y, x % y);
+        let divisor = ten_pow(shift.unsigned_abs() as u8) as u128;
+        let (quot, frac) = (quot / divisor, quot % divisor);
+        ((0, quot), frac_code(frac, divisor, rem != 0))
+    }
+}
+
+// Same as `round_mag_with_mode`, for q = hi * 2¹²⁸ + lo. Returns None if
+// the result does not fit into 256 bits.
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn u256_round_mag_with_mode(
+    neg: bool,
+    (hi, lo): (u128, u128),
+    code: u128,
+    mode: Option<RoundingMode>,
+) -> Option<(u128, u128)> {
+    if code == 0 {
+        return Some((hi, lo));
+    }
+    // The rounding modes only need to know the last digit of q.
+    let last = u256_div_rem_u128(hi, lo, 10).1 as i128;
+    let incr = if neg {
+        round_quot(-last - 1, FRAC_ONE - code, FRAC_ONE, mode) == -last - 1
+    } else {
+        round_quot(last, code, FRAC_ONE, mode) == last + 1
+    };
+    if incr {
+        u256_checked_add(hi, lo, 0, 1)
+    } else {
+        Some((hi, lo))
+    }
+}
+
+// Round the magnitude of `d` to `n_frac_digits` < d.n_frac_digits
+// according to `mode`. Returns None if the result does not fit into 256
+// bits.
+fn rounded_mag(
+    d: Decimal256,
+    n_frac_digits: i8,
+    mode: Option<RoundingMode>,
+) -> Option<(u128, u128)> {
+    let shift = i32::from(d.n_frac_digits) - i32::from(n_frac_digits);
+    let (q, code) =
+        u256_div_rem_code_ten_pow(d.hi, d.lo, shift.unsigned_abs());
+    let (mut hi, mut lo) = u256_round_mag_with_mode(d.neg, q, code, mode)?;
+    // For n_frac_digits < 0 the result has to be shifted back.
+    let mut shift = n_frac_digits.min(0).unsigned_abs();
+    while shift > 0 && (hi != 0 || lo != 0) {
+        let step = shift.min(38);
+        (hi, lo) = u256_checked_mul_u128(hi, lo, ten_pow(step) as u128)?;
+        shift -= step;
+    }
+    Some((hi, lo))
+}
+
+// Returns (q3, q2, q1, q0) with q3 * 2³⁸⁴ + q2 * 2²⁵⁶ + q1 * 2¹²⁸ + q0 =
+// x * y, where x = xh * 2¹²⁸ + xl and y = yh * 2¹²⁸ + yl.
+pub(crate) const fn u256_mul_u256(
+    xh: u128,
+    xl: u128,
+    yh: u128,
+    yl: u128,
+) -> [u128; 4] {
+    let (ah, al) = u128_mul_u128(xl, yl);
+    let (bh, bl) = u128_mul_u128(xl, yh);
+    let (ch, cl) = u128_mul_u128(xh, yl);
+    let (dh, dl) = u128_mul_u128(xh, yh);
+    let (q1, c1) = ah.overflowing_add(bl);
+    let (q1, c2) = q1.overflowing_add(cl);
+    let (q2, c3) = bh.overflowing_add(ch);
+    let (q2, c4) = q2.overflowing_add(dl);
+    let (q2, c5) = q2.overflowing_add(c1 as u128 + c2 as u128);
+    // x * y < 2⁵¹² => no overflow here
+    let q3 = dh + c3 as u128 + c4 as u128 + c5 as u128;
+    [q3, q2, q1, al]
+}
+
+// Divide the number given by `limbs` (most significant first) in place by
+// `divisor` and return the remainder.
+pub(crate) fn limbs_div_rem_u128(limbs: &mut [u128], divisor: u128) -> u128 {
+    let mut r = 0_u128;
+    for limb in limbs.iter_mut() {
+        // r < divisor => quotient < 2¹²⁸
+        let ((_, q), rem) = u256_div_rem_u128(r, *limb, divisor);
+        *limb = q;
+        r = rem;
+    }
+    r
+}
+
+impl From<Decimal> for Decimal256 {
+    #[inline]
+    fn from(d: Decimal) -> Self {
+        Self {
+            neg: d.is_negative(),
+            hi: 0,
+            lo: d.coefficient().unsigned_abs(),
+            n_frac_digits: d.n_frac_digits(),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    () => {
+        impl_from_int!(u8, u16, u32, u64, u128);
+        impl_from_int!(i8, i16, i32, i64, i128 => signed);
+    };
+    ($($t:ty),*) => {
+        $(
+        impl From<$t> for Decimal256 {
+            #[inline]
+            fn from(i: $t) -> Self {
+                Self {
+                    neg: false,
+                    hi: 0,
+                    lo: u128::from(i),
+                    n_frac_digits: 0,
+                }
+            }
+        }
+        )*
+    };
+    ($($t:ty),* => signed) => {
+        $(
+        impl From<$t> for Decimal256 {
+            #[inline]
+            fn from(i: $t) -> Self {
+                Self {
+                    neg: i < 0,
+                    hi: 0,
+                    lo: u128::from(i.unsigned_abs()),
+                    n_frac_digits: 0,
+                }
+            }
+        }
+        )*
+    };
+}
+
+impl_from_int!();
+
+impl TryFrom<Decimal256> for Decimal {
+    type Error = DecimalError;
+
+    /// Converts `d` into a `Decimal`, without loss of precision.
+    ///
+    /// Returns `DecimalError::InternalOverflow` if `d` is out of the range
+    /// of `Decimal`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn try_from(d: Decimal256) -> Result<Self, Self::Error> {
+        let (mut hi, mut lo) = (d.hi, d.lo);
+        let mut n_frac_digits = d.n_frac_digits;
+        // remove trailing zeros, if necessary
+        while (hi != 0 || lo > i128::MAX as u128) && n_frac_digits > 0 {
+            let ((qh, ql), r) = u256_div_rem_u128(hi, lo, 10);
+            if r != 0 {
+                break;
+            }
+            (hi, lo) = (qh, ql);
+            n_frac_digits -= 1;
+        }
+        if hi != 0 || lo > i128::MAX as u128 {
+            return Err(DecimalError::InternalOverflow);
+        }
+        let coeff = if d.neg { -(lo as i128) } else { lo as i128 };
+        Ok(Self::new_raw(coeff, n_frac_digits))
+    }
+}
+
+impl PartialEq for Decimal256 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal256 {}
+
+impl PartialOrd for Decimal256 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => return Ordering::Greater,
+            (true, false) => return Ordering::Less,
+            _ => {}
+        }
+        // A scaled magnitude exceeding 256 bits is greater than the other.
+        let ord_mag = match self.n_frac_digits.cmp(&other.n_frac_digits) {
+            Ordering::Equal => (self.hi, self.lo).cmp(&(other.hi, other.lo)),
+            Ordering::Less => scaled_mag(*self, other.n_frac_digits)
+                .map_or(Ordering::Greater, |m| m.cmp(&(other.hi, other.lo))),
+            Ordering::Greater => scaled_mag(*other, self.n_frac_digits)
+                .map_or(Ordering::Less, |m| (self.hi, self.lo).cmp(&m)),
+        };
+        if self.neg {
+            ord_mag.reverse()
+        } else {
+            ord_mag
+        }
+    }
+}
+
+impl PartialEq<Decimal> for Decimal256 {
+    #[inline]
+    fn eq(&self, other: &Decimal) -> bool {
+        self.eq(&Self::from(*other))
+    }
+}
+
+impl PartialEq<Decimal256> for Decimal {
+    #[inline]
+    fn eq(&self, other: &Decimal256) -> bool {
+        Decimal256::from(*self).eq(other)
+    }
+}
+
+impl PartialOrd<Decimal> for Decimal256 {
+    #[inline]
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        self.partial_cmp(&Self::from(*other))
+    }
+}
+
+impl PartialOrd<Decimal256> for Decimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Decimal256) -> Option<Ordering> {
+        Decimal256::from(*self).partial_cmp(other)
+    }
+}
+
+impl Round for Decimal256 {
+    /// Returns a new `Decimal256` with its value rounded to `n_frac_digits`
+    /// fractional digits according to the current [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by `Decimal256`!
+    #[inline]
+    fn round(self, n_frac_digits: i8) -> Self {
+        self.round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `Decimal256` with its value rounded to `n_frac_digits`
+    /// fractional digits according to the current [RoundingMode], wrapped in
+    /// `Option::Some`, or `Option::None` if the result can not be represented
+    /// by `Decimal256`.
+    #[inline]
+    fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+        self.checked_round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `Decimal256` with its value rounded to `n_frac_digits`
+    /// fractional digits according to `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by
+    /// `Decimal256`!
+    fn round_with_mode(self, n_frac_digits: i8, mode: RoundingMode) -> Self {
+        match self.checked_round_with_mode(n_frac_digits, mode) {
+            Some(d) => d,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+
+    /// Returns a new `Decimal256` with its value rounded to `n_frac_digits`
+    /// fractional digits according to `mode`, wrapped in `Option::Some`,
+    /// or `Option::None` if the result can not be represented by
+    /// `Decimal256`.
+    #[allow(clippy::cast_sign_loss)]
+    fn checked_round_with_mode(
+        self,
+        n_frac_digits: i8,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        if n_frac_digits >= self.n_frac_digits as i8 {
+            return Some(self);
+        }
+        let (hi, lo) = rounded_mag(self, n_frac_digits, Some(mode))?;
+        Self::from_parts(self.neg, hi, lo, n_frac_digits.max(0) as u8)
+    }
+}
+
+impl fmt::Display for Decimal256 {
+    /// Formats the value using the given formatter.
+    ///
+    /// If the format specifies less fractional digits than
+    /// `self.n_frac_digits()`, the value gets rounded according to the
+    /// default rounding mode.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Decimal256};
+    /// let d = Decimal256::from(Dec!(-1234.56));
+    /// assert_eq!(format!("{}", d), "-1234.56");
+    /// assert_eq!(format!("{:10.1}", d), "   -1234.6");
+    /// ```
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[allow(clippy::cast_possible_truncation)]
+        let prec = match form.precision() {
+            Some(prec) => prec.min(MAX_N_FRAC_DIGITS as usize) as u8,
+            None => self.n_frac_digits,
+        };
+        let n_frac_digits = prec.min(self.n_frac_digits);
+        let (hi, lo) = if prec < self.n_frac_digits {
+            // prec >= 0 => the result has at most as many digits as self
+            #[allow(clippy::cast_possible_wrap)]
+            rounded_mag(*self, prec as i8, None).unwrap_or_default()
+        } else {
+            (self.hi, self.lo)
+        };
+        // 77 digits, decimal point and up to 18 additional digits from
+        // padding the fractional part
+        let mut buf = RevBuf::<96>::new();
+        if prec > 0 {
+            let ((hi, lo), frac) =
+                u256_div_rem_u128(hi, lo, ten_pow(n_frac_digits) as u128);
+            buf.push_zeros((prec - n_frac_digits) as usize);
+            buf.push_digits(frac, n_frac_digits as usize);
+            buf.push(b'.');
+            buf.push_u256_digits(hi, lo, 1);
+        } else {
+            buf.push_u256_digits(hi, lo, 1);
+        }
+        form.pad_integral(!self.neg, "", buf.as_str())
+    }
+}
+
+impl fmt::Debug for Decimal256 {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "Decimal256({})", self)
+    }
+}
+
+// Scale the magnitude of `d` to `n_frac_digits` >= d.n_frac_digits.
+#[inline]
+const fn scaled_mag(
+    d: Decimal256,
+    n_frac_digits: u8,
+) -> Option<(u128, u128)> {
+    if n_frac_digits == d.n_frac_digits {
+        Some((d.hi, d.lo))
+    } else {
+        u256_checked_mul_u128(
+            d.hi,
+            d.lo,
+            fpdec_core::ten_pow(n_frac_digits - d.n_frac_digits) as u128,
+        )
+    }
+}
+
+#[cfg(test)]
+mod decimal256_tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_consts() {
+        assert!(Decimal256::ZERO.eq_zero());
+        assert!(Decimal256::ONE.is_positive());
+        assert!(Decimal256::MIN.is_negative());
+        assert_eq!(Decimal256::default(), Decimal256::ZERO);
+        assert_eq!(
+            Decimal256::MAX.to_string(),
+            "57896044618658097711785492504343953926634992332820282019728792\
+             003956564819967"
+        );
+    }
+
+    #[test]
+    fn test_from_into_decimal() {
+        for d in [
+            Dec!(-17.5),
+            Decimal::ZERO,
+            Decimal::MAX,
+            Decimal::MIN,
+            Decimal::DELTA,
+        ] {
+            let w = Decimal256::from(d);
+            assert_eq!(w.n_frac_digits(), d.n_frac_digits());
+            assert_eq!(Decimal::try_from(w).unwrap(), d);
+        }
+        let w = Decimal256::from(Decimal::MAX) + Decimal256::ONE;
+        assert_eq!(
+            Decimal::try_from(w).unwrap_err(),
+            DecimalError::InternalOverflow
+        );
+        // trailing zeros are removed if necessary
+        let w = Decimal256::from(Decimal::MAX) * Decimal256::from(Dec!(1.00));
+        assert_eq!(w.n_frac_digits(), 2);
+        assert_eq!(Decimal::try_from(w).unwrap(), Decimal::MAX);
+    }
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(Decimal256::from(-7_i8), Dec!(-7));
+        assert_eq!(
+            Decimal256::from(i128::MIN).to_string(),
+            i128::MIN.to_string()
+        );
+        assert_eq!(
+            Decimal256::from(u128::MAX).to_string(),
+            u128::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn test_cmp() {
+        let x = Decimal256::from(Dec!(1.50));
+        let y = Decimal256::from(Dec!(1.5));
+        assert_eq!(x, y);
+        assert!(Decimal256::from(Dec!(-1.51)) < y);
+        assert!(Decimal256::from(Dec!(-1.51)) < Decimal256::from(-1));
+        assert!(Decimal256::MIN < Decimal256::from(Dec!(-0.1)));
+        assert!(Decimal256::MAX > Decimal256::from(Decimal::MAX));
+        // scaled magnitude exceeding 256 bits
+        assert!(Decimal256::MAX > Decimal256::from(Decimal::DELTA));
+        assert!(Decimal256::MIN < Decimal256::from(-Decimal::DELTA));
+        assert!(Decimal256::from(Decimal::DELTA) < Decimal256::MAX);
+        assert_eq!(x, Dec!(1.5));
+        assert_eq!(Dec!(1.5), x);
+        assert!(Dec!(1.49) < x);
+        assert!(x > Dec!(-2));
+    }
+
+    #[test]
+    fn test_round() {
+        let d = Decimal256::from(Dec!(28.27093));
+        assert_eq!(d.round(1), Dec!(28.3));
+        assert_eq!(d.round(1).n_frac_digits(), 1);
+        assert_eq!(d.round(-1), Dec!(30));
+        assert_eq!((-d).round(4), Dec!(-28.2709));
+        assert_eq!(d.checked_round(7), Some(d));
+        assert!(Decimal256::MAX.checked_round(-1).is_none());
+    }
+
+    #[test]
+    fn test_round_beyond_u128_divisor() {
+        let r = Decimal256::MAX.round_with_mode(-40, RoundingMode::RoundDown);
+        assert_eq!(
+            r.to_string(),
+            "57896044618658097711785492504343953920000000000000000000000000\
+             000000000000000"
+        );
+        assert!(Decimal256::MAX.checked_round(-40).is_none());
+        assert!(Decimal256::MAX.checked_round(-77).is_none());
+        assert_eq!(
+            Decimal256::MAX.checked_round(-78),
+            Some(Decimal256::ZERO)
+        );
+        // 5 * 10³⁹ resp. 5 * 10³⁹ + 1
+        let d = Decimal256::from(5 * ten_pow(37)) * Decimal256::from(100);
+        let r = d.round_with_mode(-40, RoundingMode::RoundHalfEven);
+        assert!(r.eq_zero());
+        let d = d + Decimal256::ONE;
+        let r = d.round_with_mode(-40, RoundingMode::RoundHalfEven);
+        assert_eq!(
+            r,
+            Decimal256::from(ten_pow(20)) * Decimal256::from(ten_pow(20))
+        );
+    }
+
+    #[test]
+    fn test_round_with_mode() {
+        let d = Decimal256::from(Dec!(-28.25));
+        let r = d.round_with_mode(1, RoundingMode::RoundHalfOdd);
+        assert_eq!(r, Dec!(-28.3));
+        let r = d.round_with_mode(0, RoundingMode::RoundToOdd);
+        assert_eq!(r, Dec!(-29));
+        let d = Decimal256::from(Dec!(0.2500001));
+        let r = d
+            .round_with_mode(3, RoundingMode::RoundToOdd)
+            .round_with_mode(1, RoundingMode::RoundHalfEven);
+        assert_eq!(r, Dec!(0.3));
+        let d = Decimal256::from(Dec!(0.25));
+        let r = d
+            .round_with_mode(3, RoundingMode::RoundToOdd)
+            .round_with_mode(1, RoundingMode::RoundHalfEven);
+        assert_eq!(r, Dec!(0.2));
+    }
+
+    #[test]
+    fn test_checked_into_decimal_rounded() {
+        let d = Decimal256::from(Dec!(-2.745));
+        assert_eq!(d.checked_into_decimal_rounded(2), Some(Dec!(-2.74)));
+        assert_eq!(d.checked_into_decimal_rounded(5), Some(Dec!(-2.745)));
+        let d = Decimal256::from(Decimal::MAX) * Decimal256::from(Dec!(0.1));
+        assert_eq!(
+            d.checked_into_decimal_rounded(1),
+            Some(Decimal::new_raw(i128::MAX, 1))
+        );
+        assert_eq!(
+            d.checked_into_decimal_rounded(0),
+            Some(Dec!(17014118346046923173168730371588410573))
+        );
+        assert!(Decimal256::MAX.checked_into_decimal_rounded(0).is_none());
+    }
+
+    #[test]
+    fn test_fmt() {
+        let d = Decimal256::from(Dec!(-0.0012));
+        assert_eq!(d.to_string(), "-0.0012");
+        assert_eq!(format!("{:.2}", d), "-0.00");
+        assert_eq!(format!("{:.6}", d), "-0.001200");
+        assert_eq!(format!("{:>10.3}", d), "    -0.001");
+        let d = Decimal256::from(17);
+        assert_eq!(format!("{:.2}", d), "17.00");
+        assert_eq!(format!("{:?}", d), "Decimal256(17)");
+        let d = Decimal256::from(Decimal::MAX) * Decimal256::from(1000);
+        assert_eq!(
+            d.to_string(),
+            "170141183460469231731687303715884105727000"
+        );
+    }
+}
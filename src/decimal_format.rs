@@ -0,0 +1,340 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::fmt;
+
+use fpdec_core::MAX_N_FRAC_DIGITS;
+
+use crate::{Decimal, DecimalError};
+
+/// Pattern used to group the digits of the integral part of a formatted
+/// [Decimal].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Grouping {
+    /// No grouping: 1234567
+    None,
+    /// Groups of three digits: 1,234,567
+    Thousands,
+    /// Group of three digits followed by groups of two digits, as used in
+    /// India (lakh and crore): 12,34,567
+    Indian,
+}
+
+impl Grouping {
+    // Returns true if a separator has to follow a digit having
+    // `n_digits_right` digits to its right.
+    const fn is_boundary(self, n_digits_right: usize) -> bool {
+        match self {
+            Self::None => false,
+            Self::Thousands => {
+                n_digits_right > 0 && n_digits_right.is_multiple_of(3)
+            }
+            Self::Indian => {
+                n_digits_right >= 3 && (n_digits_right - 3).is_multiple_of(2)
+            }
+        }
+    }
+}
+
+/// Style used to denote the sign of a formatted [Decimal].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SignStyle {
+    /// Prefix negative values with `-`, positive values without sign.
+    OnlyNegative,
+    /// Prefix negative values with `-` and all other values with `+`.
+    Always,
+    /// Enclose negative values in parentheses, as common in accounting.
+    Parentheses,
+}
+
+/// Formatting of [Decimal] values with configurable separators, digit
+/// grouping and sign style.
+///
+/// The number of fractional digits is handled the same way as the
+/// precision given to [fmt::Display]: if less digits are requested than
+/// the value has, it gets rounded according to the default
+/// [RoundingMode](crate::RoundingMode).
+///
+/// The output is written directly into a [fmt::Write], without any
+/// intermediate allocation.
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{Dec, Decimal, DecimalFormat, Grouping, SignStyle};
+/// let d = Dec!(-1234567.891);
+/// let mut s = String::new();
+/// DecimalFormat::new().write_to(d, &mut s).unwrap();
+/// assert_eq!(s, "-1,234,567.891");
+/// let fmt = DecimalFormat::new()
+///     .with_n_frac_digits(2)
+///     .with_group_separator('.')
+///     .with_decimal_separator(',')
+///     .with_sign_style(SignStyle::Parentheses);
+/// s.clear();
+/// fmt.write_to(d, &mut s).unwrap();
+/// assert_eq!(s, "(1.234.567,89)");
+/// let fmt = DecimalFormat::new().with_grouping(Grouping::Indian);
+/// s.clear();
+/// fmt.write_to(Dec!(12345678.9), &mut s).unwrap();
+/// assert_eq!(s, "1,23,45,678.9");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DecimalFormat {
+    n_frac_digits: Option<u8>,
+    decimal_separator: char,
+    group_separator: char,
+    grouping: Grouping,
+    sign_style: SignStyle,
+}
+
+impl Default for DecimalFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecimalFormat {
+    /// Creates a new `DecimalFormat` using `.` as decimal separator, `,` as
+    /// group separator, [Grouping::Thousands] and [SignStyle::OnlyNegative],
+    /// keeping the number of fractional digits of the formatted values.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            n_frac_digits: None,
+            decimal_separator: '.',
+            group_separator: ',',
+            grouping: Grouping::Thousands,
+            sign_style: SignStyle::OnlyNegative,
+        }
+    }
+
+    /// Returns `self` with its number of fractional digits set to
+    /// `n_frac_digits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS]!
+    #[must_use]
+    #[inline]
+    pub fn with_n_frac_digits(mut self, n_frac_digits: u8) -> Self {
+        #[allow(clippy::manual_assert)]
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+        }
+        self.n_frac_digits = Some(n_frac_digits);
+        self
+    }
+
+    /// Returns `self` with its decimal separator set to `sep`.
+    #[must_use]
+    #[inline]
+    pub const fn with_decimal_separator(mut self, sep: char) -> Self {
+        self.decimal_separator = sep;
+        self
+    }
+
+    /// Returns `self` with its group separator set to `sep`.
+    #[must_use]
+    #[inline]
+    pub const fn with_group_separator(mut self, sep: char) -> Self {
+        self.group_separator = sep;
+        self
+    }
+
+    /// Returns `self` with its grouping pattern set to `grouping`.
+    #[must_use]
+    #[inline]
+    pub const fn with_grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Returns `self` with its sign style set to `sign_style`.
+    #[must_use]
+    #[inline]
+    pub const fn with_sign_style(mut self, sign_style: SignStyle) -> Self {
+        self.sign_style = sign_style;
+        self
+    }
+
+    /// Number of fractional digits of the formatted values, or `None` if
+    /// the values are formatted with their own number of fractional digits.
+    #[must_use]
+    #[inline(always)]
+    pub const fn n_frac_digits(&self) -> Option<u8> {
+        self.n_frac_digits
+    }
+
+    /// Character separating the integral and the fractional part.
+    #[must_use]
+    #[inline(always)]
+    pub const fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// Character separating the groups of digits of the integral part.
+    #[must_use]
+    #[inline(always)]
+    pub const fn group_separator(&self) -> char {
+        self.group_separator
+    }
+
+    /// Pattern used to group the digits of the integral part.
+    #[must_use]
+    #[inline(always)]
+    pub const fn grouping(&self) -> Grouping {
+        self.grouping
+    }
+
+    /// Style used to denote the sign.
+    #[must_use]
+    #[inline(always)]
+    pub const fn sign_style(&self) -> SignStyle {
+        self.sign_style
+    }
+
+    /// Writes `d` formatted according to `self` into `w`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `w` fails.
+    pub fn write_to<W: fmt::Write>(
+        &self,
+        d: Decimal,
+        w: &mut W,
+    ) -> fmt::Result {
+        let prec = self.n_frac_digits.unwrap_or(d.n_frac_digits);
+        let (int, frac) = d.int_and_frac_rounded(prec);
+        let is_negative = d.coeff < 0;
+        match (self.sign_style, is_negative) {
+            (SignStyle::Parentheses, true) => w.write_char('(')?,
+            (_, true) => w.write_char('-')?,
+            (SignStyle::Always, false) => w.write_char('+')?,
+            _ => {}
+        }
+        // An i128 has at most 39 digits.
+        let mut buf = [b'0'; 39];
+        let mut int = int.unsigned_abs();
+        let mut start = buf.len();
+        loop {
+            start -= 1;
+            #[allow(clippy::cast_possible_truncation)]
+            let digit = (int % 10) as u8;
+            buf[start] += digit;
+            int /= 10;
+            if int == 0 {
+                break;
+            }
+        }
+        let digits = &buf[start..];
+        for (idx, digit) in digits.iter().enumerate() {
+            w.write_char(char::from(*digit))?;
+            if self.grouping.is_boundary(digits.len() - 1 - idx) {
+                w.write_char(self.group_separator)?;
+            }
+        }
+        if prec > 0 {
+            w.write_char(self.decimal_separator)?;
+            write!(w, "{:0width$}", frac, width = prec as usize)?;
+        }
+        if is_negative && self.sign_style == SignStyle::Parentheses {
+            w.write_char(')')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod decimal_format_tests {
+    use alloc::{format, string::String};
+
+    use super::*;
+    use crate::Dec;
+
+    fn fmt(f: &DecimalFormat, d: Decimal) -> String {
+        let mut s = String::new();
+        f.write_to(d, &mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_grouping() {
+        let f = DecimalFormat::new();
+        assert_eq!(fmt(&f, Dec!(0)), "0");
+        assert_eq!(fmt(&f, Dec!(999)), "999");
+        assert_eq!(fmt(&f, Dec!(1000)), "1,000");
+        assert_eq!(fmt(&f, Dec!(-123456.78)), "-123,456.78");
+        assert_eq!(fmt(&f, Dec!(1234567.891)), "1,234,567.891");
+        assert_eq!(
+            fmt(&f, Decimal::MIN),
+            "-170,141,183,460,469,231,731,687,303,715,884,105,727"
+        );
+        let f = f.with_grouping(Grouping::Indian);
+        assert_eq!(fmt(&f, Dec!(999)), "999");
+        assert_eq!(fmt(&f, Dec!(1000)), "1,000");
+        assert_eq!(fmt(&f, Dec!(100000)), "1,00,000");
+        assert_eq!(fmt(&f, Dec!(-12345678.9)), "-1,23,45,678.9");
+        let f = f.with_grouping(Grouping::None);
+        assert_eq!(fmt(&f, Dec!(-12345678.9)), "-12345678.9");
+    }
+
+    #[test]
+    fn test_separators() {
+        let f = DecimalFormat::new()
+            .with_group_separator('\u{202f}')
+            .with_decimal_separator(',');
+        assert_eq!(fmt(&f, Dec!(1234567.5)), "1\u{202f}234\u{202f}567,5");
+        let f = f.with_group_separator('\'').with_decimal_separator('.');
+        assert_eq!(fmt(&f, Dec!(-1234.5)), "-1'234.5");
+        assert_eq!(f.group_separator(), '\'');
+        assert_eq!(f.decimal_separator(), '.');
+    }
+
+    #[test]
+    fn test_sign_style() {
+        let f = DecimalFormat::new().with_sign_style(SignStyle::Always);
+        assert_eq!(fmt(&f, Dec!(1234.5)), "+1,234.5");
+        assert_eq!(fmt(&f, Dec!(-1234.5)), "-1,234.5");
+        assert_eq!(fmt(&f, Dec!(0.00)), "+0.00");
+        let f = f.with_sign_style(SignStyle::Parentheses);
+        assert_eq!(fmt(&f, Dec!(1234.5)), "1,234.5");
+        assert_eq!(fmt(&f, Dec!(-1234.5)), "(1,234.5)");
+        assert_eq!(f.sign_style(), SignStyle::Parentheses);
+    }
+
+    #[test]
+    fn test_n_frac_digits() {
+        let f = DecimalFormat::new();
+        assert_eq!(f.n_frac_digits(), None);
+        for (d, n, s) in [
+            (Dec!(1234.5), 3, "1,234.500"),
+            (Dec!(1234.5), 0, "1,234"),
+            (Dec!(-1234.5), 0, "-1,234"),
+            (Dec!(999.995), 2, "1,000.00"),
+            (Dec!(-0.0012347), 5, "-0.00123"),
+            (Dec!(17), 2, "17.00"),
+        ] {
+            let f = f.with_n_frac_digits(n);
+            assert_eq!(fmt(&f, d), s);
+            assert_eq!(
+                fmt(&f.with_grouping(Grouping::None), d),
+                format!("{:.*}", n as usize, d)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_n_frac_digits_exceeded() {
+        let _ =
+            DecimalFormat::new().with_n_frac_digits(MAX_N_FRAC_DIGITS + 1);
+    }
+}
@@ -0,0 +1,476 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::str::FromStr;
+
+use crate::{Decimal, ParseDecimalError};
+
+/// Lenient parsing of decimal literals as found in reports, spreadsheets or
+/// CSV files, with configurable separators and optional accounting
+/// negatives, currency symbols and percent signs.
+///
+/// The accepted literals have the form
+///
+/// `[(][+|-][<currency symbol>][+|-]<int>[<decimal separator><frac>][%][)]`
+///
+/// where the digits of `<int>` may be grouped by the group separator. All
+/// groups but the first one must consist of 3 digits, except for the
+/// Indian numbering system, where the groups between the first and the last
+/// one consist of 2 digits (as in `12,34,567`).
+/// Leading and trailing whitespace is ignored, as well as whitespace
+/// following the currency symbol or preceding the percent sign. A sign can
+/// be given only once and not together with parentheses.
+///
+/// A value followed by a percent sign is divided by 100. Exponents are not
+/// accepted.
+///
+/// After removing separators, symbols and parentheses, the remaining
+/// literal is converted like in [Decimal::from_str], so the same errors are
+/// returned, with one exception: an empty string or a string consisting
+/// only of whitespace gives `ParseDecimalError::Empty`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{Dec, Decimal, DecimalParser, ParseDecimalError};
+/// # fn main() -> Result<(), ParseDecimalError> {
+/// let parser = DecimalParser::new().with_group_separator(',');
+/// assert_eq!(parser.parse("1,234.56")?, Dec!(1234.56));
+/// let parser = DecimalParser::new()
+///     .with_decimal_separator(',')
+///     .with_group_separator('.')
+///     .with_currency_symbols(&["EUR", "€"])
+///     .with_parentheses(true)
+///     .with_percent_sign(true);
+/// assert_eq!(parser.parse("1.234,56")?, Dec!(1234.56));
+/// assert_eq!(parser.parse("(€ 1.234,56)")?, Dec!(-1234.56));
+/// assert_eq!(parser.parse("-EUR 17")?, Dec!(-17));
+/// assert_eq!(parser.parse("12,5 %")?, Dec!(0.125));
+/// assert_eq!(parser.parse("1,234.56"), Err(ParseDecimalError::Invalid));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DecimalParser<'a> {
+    decimal_separator: char,
+    group_separator: Option<char>,
+    currency_symbols: &'a [&'a str],
+    parentheses: bool,
+    percent_sign: bool,
+}
+
+impl Default for DecimalParser<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> DecimalParser<'a> {
+    /// Creates a new `DecimalParser` using `.` as decimal separator and
+    /// accepting neither group separators, currency symbols, parentheses
+    /// nor percent signs.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: None,
+            currency_symbols: &[],
+            parentheses: false,
+            percent_sign: false,
+        }
+    }
+
+    /// Returns `self` with its decimal separator set to `sep`.
+    #[must_use]
+    #[inline]
+    pub const fn with_decimal_separator(mut self, sep: char) -> Self {
+        self.decimal_separator = sep;
+        self
+    }
+
+    /// Returns `self` with its group separator set to `sep`.
+    ///
+    /// A group separator equal to the decimal separator is ignored.
+    #[must_use]
+    #[inline]
+    pub const fn with_group_separator(mut self, sep: char) -> Self {
+        self.group_separator = Some(sep);
+        self
+    }
+
+    /// Returns `self` accepting the given currency symbols in front of the
+    /// number. The symbols are tried in the given order.
+    #[must_use]
+    #[inline]
+    pub const fn with_currency_symbols(
+        mut self,
+        symbols: &'a [&'a str],
+    ) -> Self {
+        self.currency_symbols = symbols;
+        self
+    }
+
+    /// Returns `self` accepting negative values enclosed in parentheses,
+    /// if `yes` is `true`, otherwise rejecting them.
+    #[must_use]
+    #[inline]
+    pub const fn with_parentheses(mut self, yes: bool) -> Self {
+        self.parentheses = yes;
+        self
+    }
+
+    /// Returns `self` accepting a trailing percent sign, if `yes` is
+    /// `true`, otherwise rejecting it.
+    #[must_use]
+    #[inline]
+    pub const fn with_percent_sign(mut self, yes: bool) -> Self {
+        self.percent_sign = yes;
+        self
+    }
+
+    /// Character separating the integral and the fractional part.
+    #[must_use]
+    #[inline(always)]
+    pub const fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    /// Character separating the groups of digits of the integral part, if
+    /// any.
+    #[must_use]
+    #[inline(always)]
+    pub const fn group_separator(&self) -> Option<char> {
+        self.group_separator
+    }
+
+    /// Convert the literal `lit` into a `Decimal`, according to the
+    /// settings of `self`.
+    ///
+    /// # Errors
+    ///
+    /// * `lit` is empty or consists only of whitespace ->
+    ///   `ParseDecimalError::Empty`
+    /// * `lit` does not fit the form described for [DecimalParser] ->
+    ///   `ParseDecimalError::Invalid`
+    /// * The number of fractional digits of the resulting value exceeds
+    ///   [crate::MAX_N_FRAC_DIGITS] ->
+    ///   `ParseDecimalError::FracDigitLimitExceeded`
+    /// * The value exceeds the internal representation of `Decimal` ->
+    ///   `ParseDecimalError::InternalOverflow`
+    pub fn parse(&self, lit: &str) -> Result<Decimal, ParseDecimalError> {
+        let mut lit = lit.trim();
+        if lit.is_empty() {
+            return Err(ParseDecimalError::Empty);
+        }
+        let mut in_parentheses = false;
+        if self.parentheses {
+            if let Some(inner) =
+                lit.strip_prefix('(').and_then(|s| s.strip_suffix(')'))
+            {
+                lit = inner.trim();
+                in_parentheses = true;
+            }
+        }
+        let mut is_percent = false;
+        if self.percent_sign {
+            if let Some(num) = lit.strip_suffix('%') {
+                lit = num.trim_end();
+                is_percent = true;
+            }
+        }
+        let (mut sign, mut rest) = split_sign(lit);
+        if let Some(num) = self
+            .currency_symbols
+            .iter()
+            .filter(|sym| !sym.is_empty())
+            .find_map(|sym| rest.strip_prefix(sym))
+        {
+            rest = num.trim_start();
+            if sign.is_none() {
+                (sign, rest) = split_sign(rest);
+            }
+        }
+        if in_parentheses && sign.is_some() {
+            return Err(ParseDecimalError::Invalid);
+        }
+        let mut buf = LitBuf::new();
+        if in_parentheses || sign == Some('-') {
+            buf.push(b'-');
+        }
+        self.push_digits(rest, &mut buf)?;
+        if is_percent {
+            buf.push_str("e-2");
+        }
+        Decimal::from_str(buf.as_str())
+    }
+
+    // Copies the digits and the decimal point from `num` to `buf`, skipping
+    // group separators and leading zeros.
+    fn push_digits(
+        &self,
+        num: &str,
+        buf: &mut LitBuf,
+    ) -> Result<(), ParseDecimalError> {
+        let group_separator = self
+            .group_separator
+            .filter(|sep| *sep != self.decimal_separator);
+        let mut n_digits = 0_usize;
+        let mut skipped_zero = false;
+        let mut seen_point = false;
+        let mut grouped = false;
+        // Number of digits in the current group of the integral part
+        let mut group_len = 0_usize;
+        // Number of digits in the inner groups, i.e. those between the
+        // first and the last one (0 as long as no inner group was seen)
+        let mut inner_group_len = 0_usize;
+        for c in num.chars() {
+            if c.is_ascii_digit() {
+                if c == '0' && n_digits == 0 && !seen_point {
+                    skipped_zero = true;
+                } else {
+                    n_digits += 1;
+                    // 10^39 > i128::MAX
+                    if n_digits > 39 {
+                        return Err(ParseDecimalError::InternalOverflow);
+                    }
+                    // c is an ASCII digit
+                    #[allow(clippy::cast_possible_truncation)]
+                    buf.push(c as u8);
+                }
+                if !seen_point {
+                    group_len += 1;
+                }
+            } else if c == self.decimal_separator && !seen_point {
+                if grouped && group_len != 3 {
+                    return Err(ParseDecimalError::Invalid);
+                }
+                if n_digits == 0 && skipped_zero {
+                    buf.push(b'0');
+                }
+                buf.push(b'.');
+                seen_point = true;
+            } else if Some(c) == group_separator
+                && !seen_point
+                && group_len > 0
+            {
+                if grouped {
+                    // Inner groups must consist of 3 digits or, in the
+                    // Indian numbering system, of 2 digits.
+                    if !(2..=3).contains(&group_len)
+                        || (inner_group_len != 0
+                            && inner_group_len != group_len)
+                    {
+                        return Err(ParseDecimalError::Invalid);
+                    }
+                    inner_group_len = group_len;
+                }
+                grouped = true;
+                group_len = 0;
+            } else {
+                return Err(ParseDecimalError::Invalid);
+            }
+        }
+        // The last group of the integral part must consist of 3 digits.
+        if (grouped && !seen_point && group_len != 3)
+            || (n_digits == 0 && !skipped_zero)
+        {
+            return Err(ParseDecimalError::Invalid);
+        }
+        if n_digits == 0 && !seen_point {
+            buf.push(b'0');
+        }
+        Ok(())
+    }
+}
+
+// Splits a leading sign from `lit`.
+fn split_sign(lit: &str) -> (Option<char>, &str) {
+    match lit.chars().next() {
+        Some(c @ ('+' | '-')) => (Some(c), &lit[1..]),
+        _ => (None, lit),
+    }
+}
+
+// Stack buffer holding the stripped literal: sign, up to 39 digits, a
+// leading zero, the decimal point and an exponent.
+struct LitBuf {
+    buf: [u8; 48],
+    len: usize,
+}
+
+impl LitBuf {
+    const fn new() -> Self {
+        Self {
+            buf: [0; 48],
+            len: 0,
+        }
+    }
+
+    const fn push(&mut self, byte: u8) {
+        self.buf[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for byte in s.bytes() {
+            self.push(byte);
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // The buffer contains only ASCII characters.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod decimal_parser_tests {
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_default() {
+        let parser = DecimalParser::default();
+        assert_eq!(parser.parse(" -17.50 "), Ok(Dec!(-17.50)));
+        assert_eq!(parser.parse("+.5"), Ok(Dec!(0.5)));
+        assert_eq!(parser.parse("0012.5"), Ok(Dec!(12.5)));
+        assert_eq!(parser.parse("000"), Ok(Dec!(0)));
+        assert_eq!(parser.parse("00.000"), Ok(Dec!(0.000)));
+        assert_eq!(parser.parse("1,234.5"), Err(ParseDecimalError::Invalid));
+        assert_eq!(parser.parse("(12)"), Err(ParseDecimalError::Invalid));
+        assert_eq!(parser.parse("12%"), Err(ParseDecimalError::Invalid));
+        assert_eq!(parser.parse("1e3"), Err(ParseDecimalError::Invalid));
+        assert_eq!(parser.parse(""), Err(ParseDecimalError::Empty));
+        assert_eq!(parser.parse("  "), Err(ParseDecimalError::Empty));
+    }
+
+    #[test]
+    fn test_group_separator() {
+        let parser = DecimalParser::new().with_group_separator(',');
+        assert_eq!(parser.group_separator(), Some(','));
+        assert_eq!(parser.parse("1,234.56"), Ok(Dec!(1234.56)));
+        assert_eq!(parser.parse("-1,234,567"), Ok(Dec!(-1234567)));
+        assert_eq!(parser.parse("12,34,567.8"), Ok(Dec!(1234567.8)));
+        assert_eq!(parser.parse("0,000.5"), Ok(Dec!(0.5)));
+        for lit in [",123", "123,", "1,,234", "1,.5", "1.23,4", "-,1", "."] {
+            assert_eq!(parser.parse(lit), Err(ParseDecimalError::Invalid));
+        }
+        let parser = parser.with_decimal_separator(',');
+        assert_eq!(parser.parse("1,5"), Ok(Dec!(1.5)));
+    }
+
+    #[test]
+    fn test_group_sizes() {
+        let parser = DecimalParser::new().with_group_separator(',');
+        assert_eq!(parser.parse("1,23,45,678"), Ok(Dec!(12345678)));
+        assert_eq!(parser.parse("123,456,789"), Ok(Dec!(123456789)));
+        for lit in [
+            "1,5",
+            "1,23,4567",
+            "1,2345",
+            "1,234,5678.9",
+            "1,23.4",
+            "12,34,5,678",
+            "1,234,56,789",
+            "1,23,456,789",
+            "1,2,345",
+            "1,2345,678",
+        ] {
+            assert_eq!(parser.parse(lit), Err(ParseDecimalError::Invalid));
+        }
+    }
+
+    #[test]
+    fn test_german() {
+        let parser = DecimalParser::new()
+            .with_decimal_separator(',')
+            .with_group_separator('.');
+        assert_eq!(parser.decimal_separator(), ',');
+        assert_eq!(parser.parse("1.234,56"), Ok(Dec!(1234.56)));
+        assert_eq!(parser.parse("-0,05"), Ok(Dec!(-0.05)));
+        assert_eq!(parser.parse(",5"), Ok(Dec!(0.5)));
+        assert_eq!(parser.parse("1,234.56"), Err(ParseDecimalError::Invalid));
+        let parser = parser.with_group_separator('\u{a0}');
+        assert_eq!(parser.parse("1\u{a0}234,56"), Ok(Dec!(1234.56)));
+    }
+
+    #[test]
+    fn test_parentheses() {
+        let parser = DecimalParser::new().with_parentheses(true);
+        assert_eq!(parser.parse("(123.45)"), Ok(Dec!(-123.45)));
+        assert_eq!(parser.parse(" ( 123.45 ) "), Ok(Dec!(-123.45)));
+        assert_eq!(parser.parse("123.45"), Ok(Dec!(123.45)));
+        for lit in ["(-123.45)", "(+1)", "(123.45", "123.45)", "()"] {
+            assert_eq!(parser.parse(lit), Err(ParseDecimalError::Invalid));
+        }
+    }
+
+    #[test]
+    fn test_currency_symbols() {
+        let parser =
+            DecimalParser::new().with_currency_symbols(&["", "US$", "$"]);
+        assert_eq!(parser.parse("$17.5"), Ok(Dec!(17.5)));
+        assert_eq!(parser.parse("US$ 17.5"), Ok(Dec!(17.5)));
+        assert_eq!(parser.parse("-$17.5"), Ok(Dec!(-17.5)));
+        assert_eq!(parser.parse("$-17.5"), Ok(Dec!(-17.5)));
+        assert_eq!(parser.parse("17.5"), Ok(Dec!(17.5)));
+        for lit in ["-$-17.5", "$", "17.5$", "€17.5", "$$1"] {
+            assert_eq!(parser.parse(lit), Err(ParseDecimalError::Invalid));
+        }
+    }
+
+    #[test]
+    fn test_percent_sign() {
+        let parser = DecimalParser::new()
+            .with_percent_sign(true)
+            .with_parentheses(true);
+        assert_eq!(parser.parse("12.5%"), Ok(Dec!(0.125)));
+        assert_eq!(parser.parse("-3 %"), Ok(Dec!(-0.03)));
+        assert_eq!(parser.parse("(0.25%)"), Ok(Dec!(-0.0025)));
+        assert_eq!(parser.parse("100%"), Ok(Dec!(1)));
+        assert_eq!(parser.parse("%"), Err(ParseDecimalError::Invalid));
+        assert_eq!(parser.parse("12%%"), Err(ParseDecimalError::Invalid));
+        assert_eq!(
+            parser.parse("0.00000000000000001%"),
+            Err(ParseDecimalError::FracDigitLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let parser = DecimalParser::new().with_group_separator('\'');
+        assert_eq!(
+            parser
+                .parse("170'141'183'460'469'231'731'687'303'715'884'105'727"),
+            Ok(Decimal::MAX)
+        );
+        assert_eq!(
+            parser.parse(
+                "-0'170'141'183'460'469'231'731'687'303'715'884'105'727"
+            ),
+            Ok(Decimal::MIN)
+        );
+        assert_eq!(
+            parser
+                .parse("170'141'183'460'469'231'731'687'303'715'884'105'728"),
+            Err(ParseDecimalError::InternalOverflow)
+        );
+        assert_eq!(
+            parser.parse(
+                "1'000'000'000'000'000'000'000'000'000'000'000'000'000"
+            ),
+            Err(ParseDecimalError::InternalOverflow)
+        );
+        assert_eq!(
+            parser.parse("0.1234567890123456789"),
+            Err(ParseDecimalError::FracDigitLimitExceeded)
+        );
+        assert_eq!(parser.parse(&"0".repeat(100)), Ok(Decimal::ZERO));
+    }
+}
@@ -0,0 +1,403 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::cmp::max;
+
+use fpdec_core::{
+    ten_pow, u128_mul_u128, u256_checked_add, u256_checked_mul_u128,
+    u256_div_rem_u128, u256_sub,
+};
+
+use crate::{binops::rem::rem, CheckedAdd, Decimal, DecimalError};
+
+// Return x % y.
+fn rem_trunc(x: Decimal, y: Decimal) -> Result<Decimal, DecimalError> {
+    if y.eq_zero() {
+        return Err(DecimalError::DivisionByZero);
+    }
+    let (coeff, n_frac_digits) =
+        rem(x.coeff, x.n_frac_digits, y.coeff, y.n_frac_digits)?;
+    Ok(Decimal {
+        coeff,
+        n_frac_digits,
+    })
+}
+
+// Return the integral quotient x / y, truncated towards zero, and whether
+// the division is exact.
+#[allow(clippy::cast_possible_wrap)]
+fn div_trunc(x: Decimal, y: Decimal) -> Result<(i128, bool), DecimalError> {
+    if y.eq_zero() {
+        return Err(DecimalError::DivisionByZero);
+    }
+    // x / y = x.coeff * 10^(n - p) / (y.coeff * 10^(n - q))
+    let n = max(x.n_frac_digits, y.n_frac_digits);
+    let divisor = y
+        .coeff
+        .unsigned_abs()
+        .checked_mul(ten_pow(n - y.n_frac_digits) as u128);
+    let (quot, is_exact) = match divisor {
+        // |x.coeff| * 10^(n - p) < 2^127 * 10^18 < 2^256
+        Some(divisor) => {
+            let (xh, xl) = u128_mul_u128(
+                x.coeff.unsigned_abs(),
+                ten_pow(n - x.n_frac_digits) as u128,
+            );
+            match u256_div_rem_u128(xh, xl, divisor) {
+                ((0, quot), r) if quot <= i128::MAX as u128 => {
+                    (quot as i128, r == 0)
+                }
+                _ => return Err(DecimalError::InternalOverflow),
+            }
+        }
+        // |y.coeff| * 10^(n - q) >= 2^128 > |x.coeff| * 10^(n - p)
+        None => (0, x.eq_zero()),
+    };
+    if x.is_negative() == y.is_negative() {
+        Ok((quot, is_exact))
+    } else {
+        Ok((-quot, is_exact))
+    }
+}
+
+// Return the integral Euclidean quotient of x / y.
+fn euclid_quot(x: Decimal, y: Decimal) -> Result<i128, DecimalError> {
+    let (quot, is_exact) = div_trunc(x, y)?;
+    // The truncated remainder has the sign of x.
+    if is_exact || !x.is_negative() {
+        Ok(quot)
+    } else if y.is_negative() {
+        quot.checked_add(1).ok_or(DecimalError::InternalOverflow)
+    } else {
+        quot.checked_sub(1).ok_or(DecimalError::InternalOverflow)
+    }
+}
+
+// Return the Euclidean quotient of x / y.
+fn div_euclid(x: Decimal, y: Decimal) -> Result<Decimal, DecimalError> {
+    int_to_decimal(euclid_quot(x, y).ok())
+}
+
+// Return x - q * y, with the intermediate values held in 256 bits.
+fn sub_mul(x: Decimal, q: i128, y: Decimal) -> Result<Decimal, DecimalError> {
+    // x - q * y = (x.coeff * 10^(n - p) - q * y.coeff * 10^(n - r)) / 10^n
+    let n = max(x.n_frac_digits, y.n_frac_digits);
+    let (xh, xl) = u128_mul_u128(
+        x.coeff.unsigned_abs(),
+        ten_pow(n - x.n_frac_digits) as u128,
+    );
+    let (yh, yl) = u128_mul_u128(q.unsigned_abs(), y.coeff.unsigned_abs());
+    let (yh, yl) =
+        u256_checked_mul_u128(yh, yl, ten_pow(n - y.n_frac_digits) as u128)
+            .ok_or(DecimalError::InternalOverflow)?;
+    let neg_y = (q < 0) != y.is_negative();
+    let (neg, (h, l)) = if x.is_negative() != neg_y {
+        (
+            x.is_negative(),
+            u256_checked_add(xh, xl, yh, yl)
+                .ok_or(DecimalError::InternalOverflow)?,
+        )
+    } else if (xh, xl) < (yh, yl) {
+        (!neg_y, u256_sub(yh, yl, xh, xl))
+    } else {
+        (x.is_negative(), u256_sub(xh, xl, yh, yl))
+    };
+    match i128::try_from(l) {
+        Ok(mag) if h == 0 => Ok(Decimal {
+            coeff: if neg { -mag } else { mag },
+            n_frac_digits: n,
+        }),
+        _ => Err(DecimalError::InternalOverflow),
+    }
+}
+
+// Return the Euclidean remainder of x / y.
+fn rem_euclid(x: Decimal, y: Decimal) -> Result<Decimal, DecimalError> {
+    let r = rem_trunc(x, y)?;
+    if r.is_negative() {
+        r.checked_add(y.abs()).ok_or(DecimalError::InternalOverflow)
+    } else {
+        Ok(r)
+    }
+}
+
+// Return the quotient of x / y rounded towards negative infinity.
+fn div_floor(x: Decimal, y: Decimal) -> Result<Decimal, DecimalError> {
+    let (quot, is_exact) = div_trunc(x, y)?;
+    if is_exact || x.is_negative() == y.is_negative() {
+        int_to_decimal(Some(quot))
+    } else {
+        int_to_decimal(quot.checked_sub(1))
+    }
+}
+
+#[inline]
+const fn int_to_decimal(
+    coeff: Option<i128>,
+) -> Result<Decimal, DecimalError> {
+    match coeff {
+        // i128::MIN is not a valid coefficient
+        Some(coeff) if coeff != i128::MIN => Ok(Decimal {
+            coeff,
+            n_frac_digits: 0,
+        }),
+        _ => Err(DecimalError::InternalOverflow),
+    }
+}
+
+impl Decimal {
+    /// Returns the Euclidean quotient of `self` / `rhs`, i.e. the integral
+    /// value `q`, so that `self = q * rhs + r` with `0 <= r < |rhs|`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(-7.5);
+    /// assert_eq!(d.div_euclid(Dec!(2)).to_string(), "-4");
+    /// assert_eq!(d.div_euclid(-2).to_string(), "4");
+    /// assert_eq!(Dec!(7.5).div_euclid(Dec!(-0.2)).to_string(), "-37");
+    /// ```
+    pub fn div_euclid<T: Into<Self>>(self, rhs: T) -> Self {
+        match div_euclid(self, rhs.into()) {
+            Ok(quot) => quot,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `Some(q)` with `q` being the Euclidean quotient of
+    /// `self` / `rhs`, or `None` if `rhs` equals zero or `q` can not be
+    /// represented by `Decimal`.
+    #[must_use]
+    pub fn checked_div_euclid<T: Into<Self>>(self, rhs: T) -> Option<Self> {
+        div_euclid(self, rhs.into()).ok()
+    }
+
+    /// Returns the Euclidean remainder of `self` / `rhs`, i.e. the value
+    /// `r`, so that `self = q * rhs + r` with `0 <= r < |rhs|` and `q`
+    /// being an integral value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(-7.5);
+    /// assert_eq!(d.rem_euclid(Dec!(2)).to_string(), "0.5");
+    /// assert_eq!(d.rem_euclid(-2).to_string(), "0.5");
+    /// assert_eq!(Dec!(7.5).rem_euclid(Dec!(-0.2)).to_string(), "0.1");
+    /// ```
+    pub fn rem_euclid<T: Into<Self>>(self, rhs: T) -> Self {
+        match rem_euclid(self, rhs.into()) {
+            Ok(r) => r,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `Some(r)` with `r` being the Euclidean remainder of
+    /// `self` / `rhs`, or `None` if `rhs` equals zero or `r` can not be
+    /// represented by `Decimal`.
+    #[must_use]
+    pub fn checked_rem_euclid<T: Into<Self>>(self, rhs: T) -> Option<Self> {
+        rem_euclid(self, rhs.into()).ok()
+    }
+
+    /// Returns the largest integral value <= `self` / `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(-7.5);
+    /// assert_eq!(d.div_floor(Dec!(2)).to_string(), "-4");
+    /// assert_eq!(d.div_floor(-2).to_string(), "3");
+    /// assert_eq!(Dec!(7.5).div_floor(Dec!(-0.2)).to_string(), "-38");
+    /// ```
+    pub fn div_floor<T: Into<Self>>(self, rhs: T) -> Self {
+        match div_floor(self, rhs.into()) {
+            Ok(quot) => quot,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `Some(q)` with `q` being the largest integral value <=
+    /// `self` / `rhs`, or `None` if `rhs` equals zero or `q` can not be
+    /// represented by `Decimal`.
+    #[must_use]
+    pub fn checked_div_floor<T: Into<Self>>(self, rhs: T) -> Option<Self> {
+        div_floor(self, rhs.into()).ok()
+    }
+
+    /// Returns the Euclidean quotient of `self` / `rhs` together with the
+    /// Euclidean remainder, i.e. the integral value `q` and the value `r`,
+    /// so that `self = q * rhs + r` with `0 <= r < |rhs|`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let (q, r) = Dec!(-7.5).divmod(Dec!(2));
+    /// assert_eq!(q.to_string(), "-4");
+    /// assert_eq!(r.to_string(), "0.5");
+    /// let (q, r) = Dec!(7.5).divmod(-2);
+    /// assert_eq!(q.to_string(), "-3");
+    /// assert_eq!(r.to_string(), "1.5");
+    /// ```
+    pub fn divmod<T: Into<Self>>(self, rhs: T) -> (Self, Self) {
+        match self.checked_divmod_res(rhs.into()) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `Some((q, r))` with `q` being the Euclidean quotient and `r`
+    /// being the Euclidean remainder of `self` / `rhs`, or `None` if `rhs`
+    /// equals zero or the result can not be represented by `Decimal`.
+    #[must_use]
+    pub fn checked_divmod<T: Into<Self>>(
+        self,
+        rhs: T,
+    ) -> Option<(Self, Self)> {
+        self.checked_divmod_res(rhs.into()).ok()
+    }
+
+    #[inline]
+    fn checked_divmod_res(
+        self,
+        rhs: Self,
+    ) -> Result<(Self, Self), DecimalError> {
+        let quot = euclid_quot(self, rhs)?;
+        Ok((int_to_decimal(Some(quot))?, sub_mul(self, quot, rhs)?))
+    }
+}
+
+#[cfg(test)]
+mod div_mod_tests {
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_div_rem_euclid() {
+        for (x, y, q, r) in [
+            (Dec!(7.5), Dec!(2), Dec!(3), Dec!(1.5)),
+            (Dec!(-7.5), Dec!(2), Dec!(-4), Dec!(0.5)),
+            (Dec!(7.5), Dec!(-2), Dec!(-3), Dec!(1.5)),
+            (Dec!(-7.5), Dec!(-2), Dec!(4), Dec!(0.5)),
+            (Dec!(-8), Dec!(0.25), Dec!(-32), Dec!(0)),
+            (Dec!(0), Dec!(-0.3), Dec!(0), Dec!(0)),
+            (Dec!(-0.001), Dec!(5), Dec!(-1), Dec!(4.999)),
+        ] {
+            assert_eq!(x.div_euclid(y), q);
+            assert_eq!(x.rem_euclid(y), r);
+            assert_eq!(q * y + r, x);
+            assert_eq!(x.checked_div_euclid(y), Some(q));
+            assert_eq!(x.checked_rem_euclid(y), Some(r));
+        }
+        assert_eq!(Dec!(-7.5).rem_euclid(2).n_frac_digits(), 1);
+        assert_eq!(Dec!(-7.5).div_euclid(2_u8), Dec!(-4));
+        assert_eq!(Dec!(-7.5).rem_euclid(-2_i64), Dec!(0.5));
+    }
+
+    #[test]
+    fn test_div_floor() {
+        for (x, y, q) in [
+            (Dec!(7.5), Dec!(2), Dec!(3)),
+            (Dec!(-7.5), Dec!(2), Dec!(-4)),
+            (Dec!(7.5), Dec!(-2), Dec!(-4)),
+            (Dec!(-7.5), Dec!(-2), Dec!(3)),
+            (Dec!(-8), Dec!(0.25), Dec!(-32)),
+            (Dec!(0.001), Dec!(-5), Dec!(-1)),
+        ] {
+            assert_eq!(x.div_floor(y), q);
+            assert_eq!(x.checked_div_floor(y), Some(q));
+        }
+        assert_eq!(Dec!(-7.5).div_floor(2_i16), Dec!(-4));
+    }
+
+    #[test]
+    fn test_divmod() {
+        for (x, y, q, r) in [
+            (Dec!(7.5), Dec!(2), Dec!(3), Dec!(1.5)),
+            (Dec!(-7.5), Dec!(2), Dec!(-4), Dec!(0.5)),
+            (Dec!(-7), Dec!(2), Dec!(-4), Dec!(1)),
+            (Dec!(7.5), Dec!(-0.02), Dec!(-375), Dec!(0)),
+            (Dec!(-7.5), Dec!(-2.000), Dec!(4), Dec!(0.5)),
+            (Dec!(1234567.89), Dec!(0.007), Dec!(176366841), Dec!(0.003)),
+            (Dec!(-0.001), Dec!(5), Dec!(-1), Dec!(4.999)),
+        ] {
+            let (quot, rem) = x.divmod(y);
+            assert_eq!(quot, q);
+            assert_eq!(rem, r);
+            assert_eq!(quot, x.div_euclid(y));
+            assert_eq!(rem, x.rem_euclid(y));
+            assert_eq!(rem.n_frac_digits(), x.rem_euclid(y).n_frac_digits());
+            assert_eq!(q * y + r, x);
+            assert_eq!(x.checked_divmod(y), Some((q, r)));
+        }
+        assert_eq!(Dec!(-7.5).divmod(2_u32), (Dec!(-4), Dec!(0.5)));
+    }
+
+    #[test]
+    fn test_large_values() {
+        let x = Decimal::MAX;
+        let y = Decimal::new_raw(1, 18);
+        assert_eq!(x.checked_divmod(y), None);
+        assert_eq!(x.checked_div_floor(Dec!(0.5)), None);
+        assert_eq!(x.rem_euclid(y), Decimal::ZERO);
+        let y = Dec!(2.5);
+        let q = Dec!(68056473384187692692674921486353642290);
+        assert_eq!(x.divmod(y), (q, Dec!(2.0)));
+        assert_eq!((-x).divmod(y), (-q - 1, Dec!(0.5)));
+        assert_eq!((-x).div_euclid(y), -q - 1);
+        assert_eq!((-x).rem_euclid(y), Dec!(0.5));
+        let x = Decimal::new_raw(-1, 18);
+        let y = Decimal::MAX;
+        assert_eq!((-x).divmod(y), (Decimal::ZERO, -x));
+        assert_eq!(x.checked_divmod(y), None);
+        assert_eq!(x.div_floor(y), Decimal::NEG_ONE);
+        assert_eq!(x.div_euclid(y), Decimal::NEG_ONE);
+        // MAX - 10^-18 can not be represented
+        assert_eq!(x.checked_rem_euclid(y), None);
+        assert_eq!(Decimal::MIN.div_floor(Decimal::ONE), Decimal::MIN);
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let x = Dec!(17.4);
+        assert_eq!(x.checked_div_euclid(0), None);
+        assert_eq!(x.checked_rem_euclid(Decimal::ZERO), None);
+        assert_eq!(x.checked_div_floor(0_u8), None);
+        assert_eq!(x.checked_divmod(Decimal::ZERO), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rem_euclid_div_by_zero() {
+        let _ = Dec!(17.4).rem_euclid(0);
+    }
+}
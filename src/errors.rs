@@ -22,9 +22,11 @@ pub enum DecimalError {
     MaxNFracDigitsExceeded,
     /// The result would exceed the internal representation of `Decimal`.
     InternalOverflow,
-    /// Attempt to convert an infinite value to `Decimal`.
+    /// Attempt to convert an infinite value to `Decimal` or to apply an
+    /// operation whose result would be infinite.
     InfiniteValue,
-    /// Attempt to convert a 'not-a-number' value to a `Decimal`.
+    /// Attempt to convert a 'not-a-number' value to a `Decimal` or to apply
+    /// an operation whose result would not be a real number.
     NotANumber,
     /// A division op called with a divisor equal to zero.
     DivisionByZero,
@@ -0,0 +1,911 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::cmp::{max, Ordering};
+
+use fpdec_core::{i128_magnitude, round_quot, ten_pow, MAX_N_FRAC_DIGITS};
+
+use crate::{bigint::UBig, Decimal, DecimalError};
+
+// Signed binary fixed-point number, i.e. the value (-1)^neg * mag / 2^prec,
+// where the precision `prec` is given by the context.
+#[derive(Clone, Debug)]
+pub(crate) struct Fix {
+    neg: bool,
+    mag: UBig,
+}
+
+impl Fix {
+    const fn new(neg: bool, mag: UBig) -> Self {
+        let neg = neg && !mag.is_zero();
+        Self { neg, mag }
+    }
+
+    pub(crate) fn one(prec: u32) -> Self {
+        Self::new(false, UBig::two_pow(prec))
+    }
+
+    // Return coeff / 10^n_frac_digits, truncated to `prec` bits.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_dec(
+        coeff: i128,
+        n_frac_digits: u8,
+        prec: u32,
+    ) -> Self {
+        let mut mag = UBig::from_u128(coeff.unsigned_abs()).shl(prec);
+        mag.div_u64(ten_pow(n_frac_digits) as u64);
+        Self::new(coeff < 0, mag)
+    }
+
+    pub(crate) const fn is_negative(&self) -> bool {
+        self.neg
+    }
+
+    #[must_use]
+    pub(crate) fn neg(&self) -> Self {
+        Self::new(!self.neg, self.mag.clone())
+    }
+
+    #[must_use]
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        if self.neg == other.neg {
+            return Self::new(self.neg, self.mag.add(&other.mag));
+        }
+        match self.mag.cmp(&other.mag) {
+            Ordering::Less => Self::new(other.neg, other.mag.sub(&self.mag)),
+            _ => Self::new(self.neg, self.mag.sub(&other.mag)),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    #[must_use]
+    pub(crate) fn mul(&self, other: &Self, prec: u32) -> Self {
+        Self::new(self.neg != other.neg, self.mag.mul(&other.mag).shr(prec))
+    }
+
+    #[must_use]
+    pub(crate) fn div(&self, other: &Self, prec: u32) -> Self {
+        let (quot, _) = self.mag.shl(prec).div_rem(&other.mag);
+        Self::new(self.neg != other.neg, quot)
+    }
+
+    #[must_use]
+    pub(crate) fn mul_int(&self, y: i128) -> Self {
+        let mag = self.mag.mul(&UBig::from_u128(y.unsigned_abs()));
+        Self::new(self.neg != (y < 0), mag)
+    }
+
+    #[must_use]
+    pub(crate) fn div_u64(&self, y: u64) -> Self {
+        let mut mag = self.mag.clone();
+        mag.div_u64(y);
+        Self::new(self.neg, mag)
+    }
+
+    #[must_use]
+    pub(crate) fn shl(&self, n: u32) -> Self {
+        Self::new(self.neg, self.mag.shl(n))
+    }
+
+    #[must_use]
+    pub(crate) fn shr(&self, n: u32) -> Self {
+        Self::new(self.neg, self.mag.shr(n))
+    }
+
+    // Return the integral part of self, truncated towards zero, saturated
+    // at the bounds of i64.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    pub(crate) fn trunc(&self, prec: u32) -> i64 {
+        let int = self.mag.shr(prec).to_u128().unwrap_or(u128::MAX);
+        let int = int.min(i64::MAX as u128) as i64;
+        if self.neg {
+            -int
+        } else {
+            int
+        }
+    }
+}
+
+// Return atanh(1 / d), truncated to `prec` bits, with an error < 2 * n
+// ulps, where n is the number of terms summed up.
+fn atanh_inv(d: u64, prec: u32) -> UBig {
+    let mut pow = UBig::two_pow(prec);
+    pow.div_u64(d);
+    let mut sum = pow.clone();
+    let mut k = 3_u64;
+    loop {
+        pow.div_u64(d * d);
+        if pow.is_zero() {
+            break;
+        }
+        let mut term = pow.clone();
+        term.div_u64(k);
+        sum = sum.add(&term);
+        k += 2;
+    }
+    sum
+}
+
+// Return ln(2), truncated to `prec` bits, with an error < 2 ulps.
+pub(crate) fn ln2(prec: u32) -> Fix {
+    // ln(2) = 2 * atanh(1/3)
+    Fix::new(false, atanh_inv(3, prec + 16).shl(1).shr(16))
+}
+
+// Return ln(10), truncated to `prec` bits, with an error < 2 ulps.
+pub(crate) fn ln10(prec: u32) -> Fix {
+    // ln(10) = 3 * ln(2) + ln(1.25) = 6 * atanh(1/3) + 2 * atanh(1/9)
+    let mut mag = atanh_inv(3, prec + 16);
+    mag.mul_u64(3);
+    let mag = mag.add(&atanh_inv(9, prec + 16)).shl(1).shr(16);
+    Fix::new(false, mag)
+}
+
+// ln(2) * 2^64, truncated
+const LN2_64: u128 = 0xb172_17f7_d1cf_79ab;
+
+// Number of halvings applied to the reduced argument of exp.
+const EXP_N_HALVINGS: u32 = 16;
+
+// Return e^x, truncated to `prec` bits, with an error < 2 ulps.
+// Pre-condition: |x| <= 128
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_wrap)]
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::integer_division)]
+pub(crate) fn exp(x: &Fix, prec: u32) -> Fix {
+    // e^x = e^r * 2^k with k = round(x / ln(2)) and r = x - k * ln(2)
+    let x64 = x.mag.shl(64).shr(prec).to_u128().unwrap_or(u128::MAX);
+    debug_assert!(x64 <= 128 << 64);
+    let k = ((x64 + (LN2_64 >> 1)) / LN2_64) as i64;
+    let k = if x.neg { -k } else { k };
+    // The working precision covers the magnification of the result by 2^k
+    // and the magnification of the error caused by the final squarings.
+    let s = EXP_N_HALVINGS;
+    let wp = prec + max(k, 0) as u32 + s + 32;
+    let r = x.shl(wp - prec).sub(&ln2(wp).mul_int(i128::from(k))).shr(s);
+    // |r| < 2^-17, so the series converges by more than 17 bits per term.
+    let mut sum = Fix::one(wp);
+    let mut term = Fix::one(wp);
+    let mut i = 1_u64;
+    loop {
+        term = term.mul(&r, wp).div_u64(i);
+        if term.mag.is_zero() {
+            break;
+        }
+        sum = sum.add(&term);
+        i += 1;
+    }
+    for _ in 0..s {
+        sum = sum.mul(&sum, wp);
+    }
+    if k >= 0 {
+        sum.shr(wp - prec - k as u32)
+    } else {
+        sum.shr(wp - prec + k.unsigned_abs() as u32)
+    }
+}
+
+// Return ln(coeff / 10^n_frac_digits), truncated to `prec` bits, with an
+// error < 2 ulps.
+// Pre-condition: coeff > 0
+pub(crate) fn ln(coeff: u128, n_frac_digits: u8, prec: u32) -> Fix {
+    debug_assert_ne!(coeff, 0);
+    let wp = prec + 24;
+    // coeff = m * 2^e with 1 <= m < 2
+    let e = 127 - coeff.leading_zeros();
+    let m = Fix::new(false, UBig::from_u128(coeff).shl(wp).shr(e));
+    // ln(m) = 2 * atanh(z) with z = (m - 1) / (m + 1) < 1/3
+    let one = Fix::one(wp);
+    let z = m.sub(&one).div(&m.add(&one), wp);
+    let z2 = z.mul(&z, wp);
+    let mut sum = z.clone();
+    let mut pow = z;
+    let mut k = 3_u64;
+    loop {
+        pow = pow.mul(&z2, wp);
+        if pow.mag.is_zero() {
+            break;
+        }
+        sum = sum.add(&pow.div_u64(k));
+        k += 2;
+    }
+    sum.shl(1)
+        .add(&ln2(wp).mul_int(i128::from(e)))
+        .sub(&ln10(wp).mul_int(i128::from(n_frac_digits)))
+        .shr(24)
+}
+
+// Precision (in bits) used in the first approximation.
+const START_PREC: u32 = 128;
+// Precision (in bits) beyond which a result is regarded as exact.
+const MAX_PREC: u32 = 2048;
+
+// Round the value sign * (q + f), where q is an int and f is a fraction
+// classified by `code` (0: f = 0, 1: f < 1/2, 2: f = 1/2, 3: f > 1/2),
+// according to the current RoundingMode.
+// Returns None if the result does not fit into an i128.
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn round_parts(neg: bool, q: &UBig, code: u128) -> Option<i128> {
+    let q = q.to_u128()?;
+    if q > i128::MAX as u128 {
+        return None;
+    }
+    let q = q as i128;
+    if q == i128::MAX && code != 0 {
+        // Rounding away from zero would overflow, so the direction is
+        // determined from a quotient with the same residues modulo 2 and 5.
+        let (quot, rem) = if neg {
+            (i128::MIN + 10, 4 - code)
+        } else {
+            (i128::MAX - 10, code)
+        };
+        let rounded_up = round_quot(quot, rem, 4, None) != quot;
+        return match (neg, rounded_up) {
+            (false, false) => Some(i128::MAX),
+            (true, true) => Some(-i128::MAX),
+            _ => None,
+        };
+    }
+    if !neg {
+        Some(round_quot(q, code, 4, None))
+    } else if code == 0 {
+        Some(-q)
+    } else {
+        Some(round_quot(-q - 1, 4 - code, 4, None))
+    }
+}
+
+// Round `v` (with precision `prec`) to `n_frac_digits` fractional digits.
+#[allow(clippy::cast_possible_truncation)]
+fn round_fix(v: &Fix, prec: u32, n_frac_digits: u8) -> Option<i128> {
+    let mut x = v.mag.clone();
+    x.mul_u64(ten_pow(n_frac_digits) as u64);
+    let q = x.shr(prec);
+    let rem = x.sub(&q.shl(prec));
+    let code = if rem.is_zero() {
+        0
+    } else {
+        match rem.cmp(&UBig::two_pow(prec - 1)) {
+            Ordering::Less => 1,
+            Ordering::Equal => 2,
+            Ordering::Greater => 3,
+        }
+    };
+    round_parts(v.neg, &q, code)
+}
+
+// Round `v` (with precision `prec`) to `n_frac_digits` fractional digits,
+// assuming that the exact value is the nearest int or tie.
+#[allow(clippy::cast_possible_truncation)]
+fn snap_round_fix(v: &Fix, prec: u32, n_frac_digits: u8) -> Option<i128> {
+    let mut x = v.mag.shl(1);
+    x.mul_u64(ten_pow(n_frac_digits) as u64);
+    let t = x.add(&UBig::two_pow(prec - 1)).shr(prec);
+    let code = if t.is_odd() { 2 } else { 0 };
+    round_parts(v.neg, &t.shr(1), code)
+}
+
+// Return the coefficient of the value approximated by `approx`, rounded to
+// `n_frac_digits` fractional digits according to the current RoundingMode.
+// `approx(prec)` must return a value with precision `prec` together with a
+// bound for its error in ulps.
+// The precision is increased until the rounded result is unambiguous. A
+// value which can not be separated from an int or a tie at `MAX_PREC` is
+// regarded as being exactly that int or tie.
+pub(crate) fn round_approx<F>(
+    n_frac_digits: u8,
+    approx: F,
+) -> Result<i128, DecimalError>
+where
+    F: Fn(u32) -> (Fix, u64),
+{
+    let mut prec = START_PREC;
+    loop {
+        let (v, err) = approx(prec);
+        let err = Fix::new(false, UBig::from_u128(u128::from(err)));
+        let lo = round_fix(&v.sub(&err), prec, n_frac_digits);
+        let hi = round_fix(&v.add(&err), prec, n_frac_digits);
+        if lo == hi {
+            return lo.ok_or(DecimalError::InternalOverflow);
+        }
+        if prec >= MAX_PREC {
+            return snap_round_fix(&v, prec, n_frac_digits)
+                .ok_or(DecimalError::InternalOverflow);
+        }
+        prec <<= 1;
+    }
+}
+
+// Return the coefficient of e^x, where x is approximated by `arg`, rounded
+// to `n_frac_digits` fractional digits according to the current
+// RoundingMode and negated if `neg` is true.
+// `arg(prec)` must return a value with precision `prec` together with a
+// bound for its error in ulps.
+pub(crate) fn round_exp<F>(
+    neg: bool,
+    n_frac_digits: u8,
+    arg: F,
+) -> Result<i128, DecimalError>
+where
+    F: Fn(u32) -> (Fix, u64),
+{
+    let (x, _) = arg(64);
+    let int = x.trunc(64);
+    // e^89 > 2^128
+    if int >= 89 {
+        return Err(DecimalError::InternalOverflow);
+    }
+    // e^-100 < 10^-43, i.e. the result lies strictly between 0 and
+    // 10^-(n_frac_digits + 1)
+    if int <= -100 {
+        return round_parts(neg, &UBig::zero(), 1)
+            .ok_or(DecimalError::InternalOverflow);
+    }
+    // e^x < 2^128, so an error in x gets magnified by less than 2^128.
+    round_approx(n_frac_digits, |prec| {
+        let (x, err) = arg(prec + 136);
+        let v = exp(&x, prec + 136).shr(136);
+        (if neg { v.neg() } else { v }, err + 3)
+    })
+}
+
+impl Decimal {
+    /// Returns e raised to the power of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS] or the result
+    /// can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(1);
+    /// let r = d.exp_rounded(9);
+    /// assert_eq!(r.to_string(), "2.718281828");
+    /// let d = Dec!(0.05);
+    /// let r = d.exp_rounded(9);
+    /// assert_eq!(r.to_string(), "1.051271096");
+    /// ```
+    pub fn exp_rounded(self, n_frac_digits: u8) -> Self {
+        match self.checked_exp_rounded(n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns e raised to the power of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(-2.5);
+    /// let r = d.checked_exp_rounded(6)?;
+    /// assert_eq!(r.to_string(), "0.082085");
+    /// let d = Dec!(90);
+    /// let r = d.checked_exp_rounded(0);
+    /// assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_exp_rounded(
+        self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(DecimalError::MaxNFracDigitsExceeded);
+        }
+        let coeff = if self.eq_zero() {
+            ten_pow(n_frac_digits)
+        } else {
+            round_exp(false, n_frac_digits, |prec| {
+                (Fix::from_dec(self.coeff, self.n_frac_digits, prec), 1)
+            })?
+        };
+        Ok(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+
+    /// Returns the natural logarithm of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not positive or `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS]!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(2);
+    /// let r = d.ln_rounded(9);
+    /// assert_eq!(r.to_string(), "0.693147181");
+    /// let d = Dec!(0.5);
+    /// let r = d.ln_rounded(6);
+    /// assert_eq!(r.to_string(), "-0.693147");
+    /// ```
+    pub fn ln_rounded(self, n_frac_digits: u8) -> Self {
+        match self.checked_ln_rounded(n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the natural logarithm of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InfiniteValue` if `self` equals zero,
+    /// * `DecimalError::NotANumber` if `self` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(10);
+    /// let r = d.checked_ln_rounded(4)?;
+    /// assert_eq!(r.to_string(), "2.3026");
+    /// let r = Dec!(-1).checked_ln_rounded(4);
+    /// assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_ln_rounded(
+        self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        self.check_log_arg(n_frac_digits)?;
+        let coeff = if self.eq_one() {
+            0
+        } else {
+            let x = self.coeff.unsigned_abs();
+            round_approx(n_frac_digits, |prec| {
+                (ln(x, self.n_frac_digits, prec), 2)
+            })?
+        };
+        Ok(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+
+    /// Returns the logarithm of `self` to base 10, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// If `self` is a power of ten, the result is exact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not positive or `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS]!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(2);
+    /// let r = d.log10_rounded(9);
+    /// assert_eq!(r.to_string(), "0.301029996");
+    /// let d = Dec!(0.001);
+    /// let r = d.log10_rounded(2);
+    /// assert_eq!(r.to_string(), "-3.00");
+    /// ```
+    pub fn log10_rounded(self, n_frac_digits: u8) -> Self {
+        match self.checked_log10_rounded(n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the logarithm of `self` to base 10, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// If `self` is a power of ten, the result is exact.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InfiniteValue` if `self` equals zero,
+    /// * `DecimalError::NotANumber` if `self` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(1000);
+    /// let r = d.checked_log10_rounded(0)?;
+    /// assert_eq!(r.to_string(), "3");
+    /// let r = Dec!(0).checked_log10_rounded(4);
+    /// assert_eq!(r.unwrap_err(), DecimalError::InfiniteValue);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_log10_rounded(
+        self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        self.check_log_arg(n_frac_digits)?;
+        let magnitude = self.magnitude();
+        let coeff = if self.coeff == ten_pow(i128_magnitude(self.coeff)) {
+            i128::from(magnitude) * ten_pow(n_frac_digits)
+        } else {
+            let x = self.coeff.unsigned_abs();
+            round_approx(n_frac_digits, |prec| {
+                let wp = prec + 8;
+                let res =
+                    ln(x, self.n_frac_digits, wp).div(&ln10(wp), wp).shr(8);
+                (res, 2)
+            })?
+        };
+        Ok(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+
+    /// Returns the logarithm of `self` to the given `base`, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` or `base` is not positive, `base` equals one or
+    /// `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS]!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(8);
+    /// let r = d.log_rounded(Dec!(2), 2);
+    /// assert_eq!(r.to_string(), "3.00");
+    /// let d = Dec!(100);
+    /// let r = d.log_rounded(Dec!(0.5), 6);
+    /// assert_eq!(r.to_string(), "-6.643856");
+    /// ```
+    pub fn log_rounded(self, base: Self, n_frac_digits: u8) -> Self {
+        match self.checked_log_rounded(base, n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the logarithm of `self` to the given `base`, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InfiniteValue` if `self` equals zero,
+    /// * `DecimalError::NotANumber` if `self` is negative or `base` is not
+    ///   positive,
+    /// * `DecimalError::DivisionByZero` if `base` equals one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(2);
+    /// let r = d.checked_log_rounded(Dec!(4), 2)?;
+    /// assert_eq!(r.to_string(), "0.50");
+    /// let r = d.checked_log_rounded(Dec!(1), 2);
+    /// assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_log_rounded(
+        self,
+        base: Self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        self.check_log_arg(n_frac_digits)?;
+        if !base.is_positive() {
+            return Err(DecimalError::NotANumber);
+        }
+        if base.eq_one() {
+            return Err(DecimalError::DivisionByZero);
+        }
+        let coeff = if self.eq_one() {
+            0
+        } else {
+            let x = self.coeff.unsigned_abs();
+            let b = base.coeff.unsigned_abs();
+            round_approx(n_frac_digits, |prec| {
+                // |ln(base)| > 10^-19 > 2^-64 and |result| < 2^67, so an
+                // error of the divisor gets magnified by less than 2^131.
+                let wp = prec + 200;
+                let res = ln(x, self.n_frac_digits, wp)
+                    .div(&ln(b, base.n_frac_digits, wp), wp)
+                    .shr(200);
+                (res, 2)
+            })?
+        };
+        Ok(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+
+    // Check the preconditions common to all logarithm functions.
+    const fn check_log_arg(
+        self,
+        n_frac_digits: u8,
+    ) -> Result<(), DecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            Err(DecimalError::MaxNFracDigitsExceeded)
+        } else if self.eq_zero() {
+            Err(DecimalError::InfiniteValue)
+        } else if self.is_negative() {
+            Err(DecimalError::NotANumber)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod exp_tests {
+    use super::*;
+
+    #[test]
+    fn test_exp() {
+        let r = Decimal::ONE.exp_rounded(18);
+        assert_eq!(r.coefficient(), 2718281828459045235);
+        assert_eq!(r.n_frac_digits(), 18);
+        let d = Decimal::new_raw(-25, 1);
+        let r = d.exp_rounded(18);
+        assert_eq!(r.coefficient(), 82084998623898795);
+        let d = Decimal::new_raw(8802, 2);
+        let r = d.exp_rounded(0);
+        assert_eq!(r.coefficient(), 168500152058165325106471903598434881155);
+        let d = Decimal::new_raw(0, 3);
+        let r = d.exp_rounded(2);
+        assert_eq!(r.coefficient(), 100);
+        assert_eq!(r.n_frac_digits(), 2);
+    }
+
+    #[test]
+    fn test_exp_tiny() {
+        // e^-42 = 5.75...e-19
+        let d = Decimal::new_raw(-42, 0);
+        let r = d.exp_rounded(18);
+        assert_eq!(r.coefficient(), 1);
+        let r = d.exp_rounded(17);
+        assert_eq!(r.coefficient(), 0);
+        let d = Decimal::new_raw(-1_i128 << 100, 2);
+        let r = d.exp_rounded(18);
+        assert_eq!(r.coefficient(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_exp_rounding_mode() {
+        let d = Decimal::new_raw(-1_i128 << 100, 2);
+        crate::RoundingMode::set_default(crate::RoundingMode::RoundCeiling);
+        let r = d.exp_rounded(18);
+        let s = Decimal::ONE.exp_rounded(1);
+        crate::RoundingMode::set_default(crate::RoundingMode::RoundHalfEven);
+        assert_eq!(r.coefficient(), 1);
+        assert_eq!(s.coefficient(), 28);
+    }
+
+    #[test]
+    fn test_checked_exp_err() {
+        let d = Decimal::new_raw(8803, 2);
+        let r = d.checked_exp_rounded(0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let d = Decimal::new_raw(1_i128 << 100, 2);
+        let r = d.checked_exp_rounded(0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let r = Decimal::ONE.checked_exp_rounded(19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+    }
+}
+
+#[cfg(test)]
+mod ln_tests {
+    use super::*;
+
+    #[test]
+    fn test_ln() {
+        let r = Decimal::TWO.ln_rounded(18);
+        assert_eq!(r.coefficient(), 693147180559945309);
+        let d = Decimal::new_raw(1, 18);
+        let r = d.ln_rounded(18);
+        assert_eq!(r.coefficient(), -41446531673892822312);
+        let r = Decimal::MAX.ln_rounded(18);
+        assert_eq!(r.coefficient(), 88029691931113054296);
+        let d = Decimal::new_raw(1000000000000000001, 18);
+        let r = d.ln_rounded(18);
+        assert_eq!(r.coefficient(), 1);
+        let r = Decimal::new_raw(100, 2).ln_rounded(5);
+        assert_eq!(r.coefficient(), 0);
+        assert_eq!(r.n_frac_digits(), 5);
+    }
+
+    #[test]
+    fn test_checked_ln_err() {
+        let r = Decimal::ZERO.checked_ln_rounded(2);
+        assert_eq!(r.unwrap_err(), DecimalError::InfiniteValue);
+        let r = Decimal::NEG_ONE.checked_ln_rounded(2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let r = Decimal::TWO.checked_ln_rounded(19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ln_neg() {
+        let _ = Decimal::new_raw(-5, 1).ln_rounded(2);
+    }
+
+    #[test]
+    fn test_log10() {
+        let r = Decimal::TWO.log10_rounded(18);
+        assert_eq!(r.coefficient(), 301029995663981195);
+        let d = Decimal::new_raw(2, 3);
+        let r = d.log10_rounded(18);
+        assert_eq!(r.coefficient(), -2698970004336018805);
+        let r = Decimal::MAX.log10_rounded(18);
+        assert_eq!(r.coefficient(), 38230809449325611792);
+    }
+
+    #[test]
+    fn test_log10_exact() {
+        let d = Decimal::new_raw(1000, 0);
+        let r = d.log10_rounded(18);
+        assert_eq!(r.coefficient(), 3000000000000000000);
+        let d = Decimal::new_raw(1000, 5);
+        let r = d.log10_rounded(1);
+        assert_eq!(r.coefficient(), -20);
+        let d = Decimal::new_raw(1, 18);
+        let r = d.log10_rounded(0);
+        assert_eq!(r.coefficient(), -18);
+        let r = Decimal::ONE.log10_rounded(3);
+        assert_eq!(r.coefficient(), 0);
+    }
+
+    #[test]
+    fn test_log() {
+        let d = Decimal::new_raw(8, 0);
+        let r = d.log_rounded(Decimal::TWO, 18);
+        assert_eq!(r.coefficient(), 3000000000000000000);
+        let d = Decimal::new_raw(100, 0);
+        let r = d.log_rounded(Decimal::new_raw(5, 1), 18);
+        assert_eq!(r.coefficient(), -6643856189774724696);
+        let d = Decimal::new_raw(3, 0);
+        let r = d.log_rounded(Decimal::new_raw(1000000000000000001, 18), 3);
+        assert_eq!(r.coefficient(), 1098612288668109691945);
+        let d = Decimal::MAX;
+        let b = Decimal::new_raw(1000000000000000001, 18);
+        let r = d.log_rounded(b, 18);
+        assert_eq!(r.coefficient(), 88029691931113054340003325390744951286);
+        // log_4(2) = 0.5 => tie when rounded to an int
+        let r = Decimal::TWO.log_rounded(Decimal::new_raw(4, 0), 0);
+        assert_eq!(r.coefficient(), 0);
+    }
+
+    #[test]
+    fn test_checked_log_err() {
+        let d = Decimal::TWO;
+        let r = d.checked_log_rounded(Decimal::ONE, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+        let r = d.checked_log_rounded(Decimal::ZERO, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let r = d.checked_log_rounded(Decimal::NEG_ONE, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let r = Decimal::ZERO.checked_log_rounded(d, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::InfiniteValue);
+    }
+}
+
+#[cfg(test)]
+mod exp_log_tests {
+    use super::*;
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    fn to_coeff(v: &Fix, prec: u32, n_frac_digits: u8) -> i128 {
+        let mut x = v.mag.clone();
+        x.mul_u64(ten_pow(n_frac_digits) as u64);
+        let c = x.shr(prec).to_u128().unwrap() as i128;
+        if v.is_negative() {
+            -c
+        } else {
+            c
+        }
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(ln2(64).mag.to_u128(), Some(LN2_64));
+        assert_eq!(to_coeff(&ln2(128), 128, 18), 693147180559945309);
+        assert_eq!(to_coeff(&ln10(128), 128, 18), 2302585092994045684);
+    }
+
+    #[test]
+    fn test_exp() {
+        let x = Fix::one(128);
+        assert_eq!(to_coeff(&exp(&x, 128), 128, 18), 2718281828459045235);
+        let x = Fix::from_dec(-75, 1, 128);
+        assert_eq!(to_coeff(&exp(&x, 128), 128, 18), 553084370147833);
+        let x = Fix::from_dec(88, 0, 128);
+        assert_eq!(
+            to_coeff(&exp(&x, 128), 128, 0),
+            165163625499400185552832979626485876706
+        );
+    }
+
+    #[test]
+    fn test_round_parts_limits() {
+        use crate::{with_rounding_mode, RoundingMode::*};
+        let max = UBig::from_u128(i128::MAX as u128);
+        assert_eq!(round_parts(false, &max, 0), Some(i128::MAX));
+        assert_eq!(round_parts(true, &max, 0), Some(-i128::MAX));
+        let q = UBig::from_u128(i128::MAX as u128 + 1);
+        assert_eq!(round_parts(false, &q, 0), None);
+        assert_eq!(round_parts(true, &q, 0), None);
+        let max_pos = Some(i128::MAX);
+        let max_neg = Some(-i128::MAX);
+        for (mode, code, pos, neg) in [
+            (RoundDown, 3, max_pos, max_neg),
+            (RoundUp, 1, None, None),
+            (RoundFloor, 1, max_pos, None),
+            (RoundCeiling, 1, None, max_neg),
+            (RoundHalfUp, 1, max_pos, max_neg),
+            (RoundHalfUp, 2, None, None),
+            (RoundHalfDown, 2, max_pos, max_neg),
+            (RoundHalfEven, 2, None, None),
+            (RoundHalfOdd, 2, max_pos, max_neg),
+            (RoundToOdd, 1, max_pos, max_neg),
+            (Round05Up, 3, max_pos, max_neg),
+        ] {
+            with_rounding_mode(mode, || {
+                assert_eq!(round_parts(false, &max, code), pos, "{mode:?}");
+                assert_eq!(round_parts(true, &max, code), neg, "{mode:?}");
+            });
+        }
+    }
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(to_coeff(&ln(2, 0, 128), 128, 18), 693147180559945309);
+        assert_eq!(to_coeff(&ln(1, 18, 128), 128, 15), -41446531673892822);
+        let x = ln(u128::MAX >> 1, 0, 128);
+        assert_eq!(to_coeff(&x, 128, 18), 88029691931113054295);
+    }
+}
@@ -0,0 +1,448 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::{
+    cmp::Ordering,
+    ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign,
+        Sub, SubAssign,
+    },
+};
+
+use fpdec_core::{i128_div_rounded, i128_mul_div_ten_pow_rounded};
+
+use super::FixedDecimal;
+use crate::{
+    binops::div_rounded::checked_div_rounded, CheckedAdd, CheckedDiv,
+    CheckedMul, CheckedRem, CheckedSub, Decimal, DecimalError,
+};
+
+// Implements binary operators "&T op U", "T op &U", "&T op &U"
+// based on "T op U" where T and U are FixedDecimal<N>
+macro_rules! forward_ref_binop_fixed {
+    (impl $imp:ident, $method:ident) => {
+        impl<'a, const N: u8> $imp<FixedDecimal<N>> for &'a FixedDecimal<N> {
+            type Output = <FixedDecimal<N> as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: FixedDecimal<N>) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+        impl<const N: u8> $imp<&FixedDecimal<N>> for FixedDecimal<N> {
+            type Output = <FixedDecimal<N> as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &FixedDecimal<N>) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+        impl<const N: u8> $imp<&FixedDecimal<N>> for &FixedDecimal<N> {
+            type Output = <FixedDecimal<N> as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &FixedDecimal<N>) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+// Implements "T op= U" and "T op= &U" based on "T op U"
+macro_rules! forward_op_assign_fixed {
+    (impl $imp:ident, $method:ident, $base_imp:ident, $base_method:ident) => {
+        impl<const N: u8> $imp<FixedDecimal<N>> for FixedDecimal<N> {
+            #[inline(always)]
+            fn $method(&mut self, rhs: FixedDecimal<N>) {
+                *self = $base_imp::$base_method(*self, rhs);
+            }
+        }
+        impl<const N: u8> $imp<&FixedDecimal<N>> for FixedDecimal<N> {
+            #[inline(always)]
+            fn $method(&mut self, rhs: &FixedDecimal<N>) {
+                *self = $base_imp::$base_method(*self, *rhs);
+            }
+        }
+    };
+}
+
+impl<const N: u8> Neg for FixedDecimal<N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self::from_coefficient(-self.coeff)
+    }
+}
+
+impl<const N: u8> Neg for &FixedDecimal<N> {
+    type Output = FixedDecimal<N>;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        FixedDecimal::from_coefficient(-self.coeff)
+    }
+}
+
+macro_rules! impl_add_sub_fixed {
+    (impl $imp:ident, $method:ident) => {
+        impl<const N: u8> $imp<Self> for FixedDecimal<N> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $method(self, rhs: Self) -> Self::Output {
+                Self::from_coefficient($imp::$method(self.coeff, rhs.coeff))
+            }
+        }
+
+        forward_ref_binop_fixed!(impl $imp, $method);
+    };
+}
+
+impl_add_sub_fixed!(impl Add, add);
+impl_add_sub_fixed!(impl Sub, sub);
+
+impl<const N: u8> Mul<Self> for FixedDecimal<N> {
+    type Output = Self;
+
+    /// Returns `self` * `rhs`, rounded to `N` fractional digits according to
+    /// the current [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result can not be represented by `FixedDecimal<N>`!
+    fn mul(self, rhs: Self) -> Self::Output {
+        match self.checked_mul(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_fixed!(impl Mul, mul);
+
+impl<const N: u8> Div<Self> for FixedDecimal<N> {
+    type Output = Self;
+
+    /// Returns `self` / `rhs`, rounded to `N` fractional digits according to
+    /// the current [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the result can not be represented by
+    /// `FixedDecimal<N>`!
+    fn div(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::manual_assert)]
+        if rhs.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        match self.checked_div(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_fixed!(impl Div, div);
+
+impl<const N: u8> Rem<Self> for FixedDecimal<N> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero!
+    #[inline]
+    fn rem(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::manual_assert)]
+        if rhs.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        Self::from_coefficient(self.coeff % rhs.coeff)
+    }
+}
+
+forward_ref_binop_fixed!(impl Rem, rem);
+
+forward_op_assign_fixed!(impl AddAssign, add_assign, Add, add);
+forward_op_assign_fixed!(impl SubAssign, sub_assign, Sub, sub);
+forward_op_assign_fixed!(impl MulAssign, mul_assign, Mul, mul);
+forward_op_assign_fixed!(impl DivAssign, div_assign, Div, div);
+forward_op_assign_fixed!(impl RemAssign, rem_assign, Rem, rem);
+
+impl<const N: u8> CheckedAdd<Self> for FixedDecimal<N> {
+    type Output = Option<Self>;
+
+    #[inline(always)]
+    fn checked_add(self, rhs: Self) -> Self::Output {
+        self.coeff
+            .checked_add(rhs.coeff)
+            .and_then(Self::checked_from_coefficient)
+    }
+}
+
+forward_ref_binop_fixed!(impl CheckedAdd, checked_add);
+
+impl<const N: u8> CheckedSub<Self> for FixedDecimal<N> {
+    type Output = Option<Self>;
+
+    #[inline(always)]
+    fn checked_sub(self, rhs: Self) -> Self::Output {
+        self.coeff
+            .checked_sub(rhs.coeff)
+            .and_then(Self::checked_from_coefficient)
+    }
+}
+
+forward_ref_binop_fixed!(impl CheckedSub, checked_sub);
+
+impl<const N: u8> CheckedMul<Self> for FixedDecimal<N> {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_mul(self, rhs: Self) -> Self::Output {
+        i128_mul_div_ten_pow_rounded(self.coeff, rhs.coeff, N, None)
+            .and_then(Self::checked_from_coefficient)
+    }
+}
+
+forward_ref_binop_fixed!(impl CheckedMul, checked_mul);
+
+impl<const N: u8> CheckedDiv<Self> for FixedDecimal<N> {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_div(self, rhs: Self) -> Self::Output {
+        if rhs.eq_zero() {
+            return None;
+        }
+        checked_div_rounded(self.coeff, N, rhs.coeff, N, N, None)
+            .and_then(Self::checked_from_coefficient)
+    }
+}
+
+forward_ref_binop_fixed!(impl CheckedDiv, checked_div);
+
+impl<const N: u8> CheckedRem<Self> for FixedDecimal<N> {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_rem(self, rhs: Self) -> Self::Output {
+        self.coeff
+            .checked_rem(rhs.coeff)
+            .and_then(Self::checked_from_coefficient)
+    }
+}
+
+forward_ref_binop_fixed!(impl CheckedRem, checked_rem);
+
+macro_rules! impl_mul_div_fixed_and_int {
+    () => {
+        impl_mul_div_fixed_and_int!(
+            u8, i8, u16, i16, u32, i32, u64, i64, i128
+        );
+    };
+    ($($t:ty),*) => {
+        $(
+        impl<const N: u8> Mul<$t> for FixedDecimal<N> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn mul(self, rhs: $t) -> Self::Output {
+                Self::from_coefficient(self.coeff * i128::from(rhs))
+            }
+        }
+
+        impl<const N: u8> Mul<FixedDecimal<N>> for $t {
+            type Output = FixedDecimal<N>;
+
+            #[inline(always)]
+            fn mul(self, rhs: FixedDecimal<N>) -> Self::Output {
+                FixedDecimal::from_coefficient(i128::from(self) * rhs.coeff)
+            }
+        }
+
+        impl<const N: u8> Div<$t> for FixedDecimal<N> {
+            type Output = Self;
+
+            /// Returns `self` / `rhs`, rounded to `N` fractional digits
+            /// according to the current [RoundingMode](crate::RoundingMode).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs` equals zero!
+            #[inline]
+            fn div(self, rhs: $t) -> Self::Output {
+                #[allow(clippy::manual_assert)]
+                if rhs == 0 {
+                    panic!("{}", DecimalError::DivisionByZero);
+                }
+                Self::from_coefficient(
+                    i128_div_rounded(self.coeff, i128::from(rhs), None)
+                )
+            }
+        }
+
+        impl<const N: u8> MulAssign<$t> for FixedDecimal<N> {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<const N: u8> DivAssign<$t> for FixedDecimal<N> {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: $t) {
+                *self = *self / rhs;
+            }
+        }
+        )*
+    }
+}
+
+impl_mul_div_fixed_and_int!();
+
+impl<const N: u8> PartialEq<Decimal> for FixedDecimal<N> {
+    #[inline]
+    fn eq(&self, other: &Decimal) -> bool {
+        Decimal::from(*self).eq(other)
+    }
+}
+
+impl<const N: u8> PartialEq<FixedDecimal<N>> for Decimal {
+    #[inline]
+    fn eq(&self, other: &FixedDecimal<N>) -> bool {
+        self.eq(&Self::from(*other))
+    }
+}
+
+impl<const N: u8> PartialOrd<Decimal> for FixedDecimal<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Decimal::from(*self).partial_cmp(other)
+    }
+}
+
+impl<const N: u8> PartialOrd<FixedDecimal<N>> for Decimal {
+    #[inline]
+    fn partial_cmp(&self, other: &FixedDecimal<N>) -> Option<Ordering> {
+        self.partial_cmp(&Self::from(*other))
+    }
+}
+
+#[cfg(test)]
+mod fixed_decimal_binops_tests {
+    use super::*;
+    use crate::Dec;
+
+    type F8 = FixedDecimal<8>;
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_add_sub() {
+        let x = F8::from_coefficient(1750000000);
+        let y = F8::from_coefficient(-1);
+        assert_eq!((x + y).coefficient(), 1749999999);
+        assert_eq!((x - y).coefficient(), 1750000001);
+        assert_eq!((&x + y), x + y);
+        assert_eq!((x - &y), x - y);
+        assert_eq!((&x + &y), x + y);
+        let mut z = x;
+        z += y;
+        z -= &x;
+        assert_eq!(z, y);
+        assert_eq!(-y, F8::DELTA);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_mul() {
+        let x = F8::from_coefficient(150000000);
+        let y = F8::from_coefficient(-250000001);
+        // 1.5 * -2.50000001 = -3.750000015 => tie
+        assert_eq!((x * y).coefficient(), -375000002);
+        assert_eq!(&x * &y, x * y);
+        let x = F8::from_coefficient(1);
+        assert_eq!(x * x, F8::ZERO);
+        let mut z = y;
+        z *= F8::ONE;
+        assert_eq!(z, y);
+        assert_eq!((y * 3_u8).coefficient(), -750000003);
+        assert_eq!(-2_i64 * y, F8::from_coefficient(500000002));
+        let x = F8::from_coefficient(i128::MAX >> 1);
+        assert_eq!(x.checked_mul(F8::from(3)), None);
+        assert_eq!(
+            x.checked_mul(F8::from_coefficient(3)),
+            Some(x * F8::from_coefficient(3))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_overflow() {
+        let _ = F8::MAX * F8::from(2);
+    }
+
+    #[test]
+    fn test_div() {
+        let x = F8::from(2);
+        let y = F8::from(3);
+        assert_eq!((x / y).coefficient(), 66666667);
+        assert_eq!((-x / y).coefficient(), -66666667);
+        assert_eq!((x / 3_u32).coefficient(), 66666667);
+        assert_eq!(x.checked_div(F8::ZERO), None);
+        let mut z = x;
+        z /= F8::from(-4);
+        assert_eq!(z.coefficient(), -50000000);
+        z /= 2_i8;
+        assert_eq!(z.coefficient(), -25000000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_by_zero() {
+        let _ = F8::ONE / F8::ZERO;
+    }
+
+    #[test]
+    fn test_rem() {
+        let x = F8::from_coefficient(750000001);
+        let y = F8::from(2);
+        assert_eq!((x % y).coefficient(), 150000001);
+        assert_eq!((-x % y).coefficient(), -150000001);
+        assert_eq!(x.checked_rem(F8::ZERO), None);
+        let mut z = x;
+        z %= F8::ONE;
+        assert_eq!(z.coefficient(), 50000001);
+    }
+
+    #[test]
+    fn test_checked_add_sub() {
+        assert_eq!(F8::MAX.checked_add(F8::DELTA), None);
+        assert_eq!(F8::MIN.checked_sub(F8::ONE), None);
+        // i128::MIN is not a valid coefficient
+        assert_eq!(F8::MIN.checked_sub(F8::DELTA), None);
+        assert_eq!(F8::MIN.checked_add(-F8::DELTA), None);
+        assert_eq!(
+            (-F8::MAX).checked_mul(F8::from_coefficient(100000000)),
+            Some(F8::MIN)
+        );
+        assert_eq!(F8::ONE.checked_add(&F8::ONE), Some(F8::from(2)));
+        assert_eq!((&F8::ONE).checked_sub(F8::ONE), Some(F8::ZERO));
+    }
+
+    #[test]
+    fn test_cmp() {
+        let x = F8::from_coefficient(-150000000);
+        assert!(x < F8::ZERO);
+        assert_eq!(x, Dec!(-1.5));
+        assert_eq!(Dec!(-1.50), x);
+        assert!(x > Dec!(-1.6));
+        assert!(Dec!(-1.6) < x);
+    }
+}
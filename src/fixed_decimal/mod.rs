@@ -0,0 +1,635 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(feature = "serde-as-str")]
+use alloc::string::String;
+use core::{convert::TryFrom, fmt, str::FromStr};
+
+use fpdec_core::{
+    checked_mul_pow_ten, i128_div_rounded, ten_pow, ParseDecimalError, Round,
+    RoundingMode, MAX_N_FRAC_DIGITS,
+};
+
+use crate::{normalize, Decimal, DecimalError, Quantize};
+
+mod binops;
+
+/// Represents a decimal number with a fixed number `N` of fractional
+/// decimal digits as a coefficient (`i128`).
+///
+/// In contrast to [`Decimal`], the number of fractional digits is part of
+/// the type, so that it does not need to be stored and adjusted at runtime.
+/// Thus, adding or subtracting two values is a plain integer op.
+///
+/// `N` must be in the range 0 .. [`MAX_N_FRAC_DIGITS`], otherwise the code
+/// does not compile:
+///
+/// ```compile_fail
+/// # use fpdec::FixedDecimal;
+/// let d = FixedDecimal::<19>::from_coefficient(1);
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{Dec, Decimal, FixedDecimal};
+/// type Price = FixedDecimal<8>;
+/// let p = Price::try_from(Dec!(17.5)).unwrap();
+/// let q: Price = "0.00000001".parse().unwrap();
+/// assert_eq!((p + q).to_string(), "17.50000001");
+/// assert_eq!(Decimal::from(p * q), Dec!(0.00000018));
+/// ```
+#[must_use]
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "serde-as-str",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String"),
+    serde(try_from = "String")
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes),
+    archive_attr(derive(Copy, Clone))
+)]
+pub struct FixedDecimal<const N: u8> {
+    coeff: i128,
+}
+
+impl<const N: u8> FixedDecimal<N> {
+    // Referencing this const fails to compile if N is out of range.
+    const VALID_N: () = assert!(
+        N <= MAX_N_FRAC_DIGITS,
+        "More than MAX_N_FRAC_DIGITS fractional decimal digits requested."
+    );
+
+    /// Creates a new `FixedDecimal` with the given coefficient, i.e. with
+    /// the value `coeff` * 10^-`N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coeff` equals `i128::MIN`, which is outside the range of
+    /// `FixedDecimal<N>`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::FixedDecimal;
+    /// let d = FixedDecimal::<3>::from_coefficient(-12345);
+    /// assert_eq!(d.to_string(), "-12.345");
+    /// ```
+    #[inline(always)]
+    pub const fn from_coefficient(coeff: i128) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_N;
+        assert!(
+            coeff != i128::MIN,
+            "i128::MIN is not a valid coefficient of FixedDecimal."
+        );
+        Self { coeff }
+    }
+
+    // Returns a `FixedDecimal` with the given coefficient or None, if
+    // `coeff` equals i128::MIN.
+    #[inline(always)]
+    const fn checked_from_coefficient(coeff: i128) -> Option<Self> {
+        if coeff == i128::MIN {
+            None
+        } else {
+            Some(Self::from_coefficient(coeff))
+        }
+    }
+
+    /// Coefficient of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn coefficient(self) -> i128 {
+        self.coeff
+    }
+
+    /// Number of fractional decimal digits of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn n_frac_digits(self) -> u8 {
+        N
+    }
+
+    /// Returns true if `self` equals zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn eq_zero(self) -> bool {
+        self.coeff == 0
+    }
+
+    /// Returns true if `self` is less than zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_negative(self) -> bool {
+        self.coeff < 0
+    }
+
+    /// Returns true if `self` is greater than zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_positive(self) -> bool {
+        self.coeff > 0
+    }
+
+    /// Returns the value of `d`, rounded to `N` fractional digits according
+    /// to the current [RoundingMode](crate::RoundingMode), wrapped in
+    /// `Option::Some`, or `Option::None` if the result can not be
+    /// represented by `FixedDecimal<N>`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, FixedDecimal};
+    /// let d = Dec!(0.125);
+    /// let f = FixedDecimal::<2>::checked_from_decimal_rounded(d).unwrap();
+    /// assert_eq!(f.to_string(), "0.12");
+    /// ```
+    #[must_use]
+    pub fn checked_from_decimal_rounded(d: Decimal) -> Option<Self> {
+        let coeff = if d.n_frac_digits() > N {
+            i128_div_rounded(
+                d.coefficient(),
+                ten_pow(d.n_frac_digits() - N),
+                None,
+            )
+        } else {
+            checked_mul_pow_ten(d.coefficient(), N - d.n_frac_digits())?
+        };
+        Self::checked_from_coefficient(coeff)
+    }
+
+    /// Additive identity
+    pub const ZERO: Self = Self::from_coefficient(0);
+
+    /// Multiplicative identity
+    pub const ONE: Self = Self::from_coefficient(ten_pow(N));
+
+    /// Maximum value representable by `FixedDecimal<N>`
+    pub const MAX: Self = Self::from_coefficient(i128::MAX);
+
+    /// Minimum value representable by `FixedDecimal<N>`
+    pub const MIN: Self = Self::from_coefficient(i128::MIN + 1);
+
+    /// Smallest absolute difference between two non-equal values of
+    /// `FixedDecimal<N>`
+    pub const DELTA: Self = Self::from_coefficient(1);
+}
+
+impl<const N: u8> From<FixedDecimal<N>> for Decimal {
+    #[inline]
+    fn from(d: FixedDecimal<N>) -> Self {
+        Self::new_raw(d.coeff, N)
+    }
+}
+
+impl<const N: u8> TryFrom<Decimal> for FixedDecimal<N> {
+    type Error = DecimalError;
+
+    /// Converts `d` into a `FixedDecimal<N>`, without loss of precision.
+    ///
+    /// Returns `DecimalError::MaxNFracDigitsExceeded` if `d` can not be
+    /// represented with `N` fractional digits and
+    /// `DecimalError::InternalOverflow` if it is out of the range of
+    /// `FixedDecimal<N>`.
+    fn try_from(d: Decimal) -> Result<Self, Self::Error> {
+        let mut coeff = d.coefficient();
+        let mut n_frac_digits = d.n_frac_digits();
+        if n_frac_digits > N {
+            normalize(&mut coeff, &mut n_frac_digits);
+            if n_frac_digits > N {
+                return Err(DecimalError::MaxNFracDigitsExceeded);
+            }
+        }
+        checked_mul_pow_ten(coeff, N - n_frac_digits)
+            .and_then(Self::checked_from_coefficient)
+            .ok_or(DecimalError::InternalOverflow)
+    }
+}
+
+macro_rules! impl_from_int {
+    () => {
+        impl_from_int!(u8, i8, u16, i16, u32, i32, u64, i64);
+    };
+    ($($t:ty),*) => {
+        $(
+        impl<const N: u8> From<$t> for FixedDecimal<N> {
+            #[inline]
+            // |i| * 10^N < 2^64 * 10^18 < 2^127
+            fn from(i: $t) -> Self {
+                Self::from_coefficient(i128::from(i) * ten_pow(N))
+            }
+        }
+        )*
+    }
+}
+
+impl_from_int!();
+
+impl<const N: u8> fmt::Display for FixedDecimal<N> {
+    /// Formats the value using the given formatter, like the equivalent
+    /// `Decimal`, i.e. with `N` fractional digits, unless a different
+    /// precision is given.
+    #[inline]
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Decimal::from(*self), form)
+    }
+}
+
+impl<const N: u8> fmt::Debug for FixedDecimal<N> {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "FixedDecimal<{}>({})", N, self)
+    }
+}
+
+#[cfg(feature = "serde-as-str")]
+impl<const N: u8> From<FixedDecimal<N>> for String {
+    #[inline]
+    fn from(d: FixedDecimal<N>) -> Self {
+        Self::from(Decimal::from(d))
+    }
+}
+
+impl<const N: u8> FromStr for FixedDecimal<N> {
+    type Err = ParseDecimalError;
+
+    /// Convert a number literal into a `FixedDecimal<N>`.
+    ///
+    /// The literal must have the same form as accepted by
+    /// [`Decimal::from_str`](struct.Decimal.html#method.from_str). Its value
+    /// must be representable with `N` fractional digits, otherwise
+    /// `ParseDecimalError::FracDigitLimitExceeded` is returned.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use fpdec::{FixedDecimal, ParseDecimalError};
+    /// # use core::str::FromStr;
+    /// let d = FixedDecimal::<4>::from_str("38.207")?;
+    /// assert_eq!(d.to_string(), "38.2070");
+    /// let d = FixedDecimal::<4>::from_str("38.20701");
+    /// assert_eq!(d.unwrap_err(), ParseDecimalError::FracDigitLimitExceeded);
+    /// # Ok::<(), ParseDecimalError>(())
+    /// ```
+    fn from_str(lit: &str) -> Result<Self, Self::Err> {
+        match Self::try_from(Decimal::from_str(lit)?) {
+            Ok(d) => Ok(d),
+            Err(DecimalError::MaxNFracDigitsExceeded) => {
+                Err(ParseDecimalError::FracDigitLimitExceeded)
+            }
+            Err(_) => Err(ParseDecimalError::InternalOverflow),
+        }
+    }
+}
+
+impl<const N: u8> TryFrom<&str> for FixedDecimal<N> {
+    type Error = ParseDecimalError;
+
+    #[inline]
+    fn try_from(lit: &str) -> Result<Self, Self::Error> {
+        Self::from_str(lit)
+    }
+}
+
+#[cfg(feature = "serde-as-str")]
+impl<const N: u8> TryFrom<String> for FixedDecimal<N> {
+    type Error = ParseDecimalError;
+
+    #[inline]
+    fn try_from(lit: String) -> Result<Self, Self::Error> {
+        Self::from_str(lit.as_str())
+    }
+}
+
+impl<const N: u8> Round for FixedDecimal<N> {
+    /// Returns a new `FixedDecimal<N>` with its value rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by
+    /// `FixedDecimal<N>`!
+    #[inline]
+    fn round(self, n_frac_digits: i8) -> Self {
+        self.round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `FixedDecimal<N>` with its value rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode], wrapped in `Option::Some`, or `Option::None` if the
+    /// result can not be represented by `FixedDecimal<N>`.
+    #[inline]
+    fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+        self.checked_round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `FixedDecimal<N>` with its value rounded to
+    /// `n_frac_digits` fractional digits according to `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by
+    /// `FixedDecimal<N>`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{FixedDecimal, Round, RoundingMode};
+    /// let d: FixedDecimal<5> = "28.27093".parse().unwrap();
+    /// let r = d.round_with_mode(1, RoundingMode::RoundHalfUp);
+    /// assert_eq!(r.to_string(), "28.30000");
+    /// let r = d.round_with_mode(1, RoundingMode::RoundDown);
+    /// assert_eq!(r.to_string(), "28.20000");
+    /// ```
+    fn round_with_mode(self, n_frac_digits: i8, mode: RoundingMode) -> Self {
+        match self.checked_round_with_mode(n_frac_digits, mode) {
+            Some(d) => d,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+
+    /// Returns a new `FixedDecimal<N>` with its value rounded to
+    /// `n_frac_digits` fractional digits according to `mode`, wrapped in
+    /// `Option::Some`, or `Option::None` if the result can not be
+    /// represented by `FixedDecimal<N>`.
+    #[allow(clippy::cast_sign_loss)]
+    fn checked_round_with_mode(
+        self,
+        n_frac_digits: i8,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        if n_frac_digits >= N as i8 {
+            Some(self)
+        } else if n_frac_digits < N as i8 - 38 {
+            Some(Self::ZERO)
+        } else {
+            // 0 < shift <= 38
+            let shift = (N as i8 - n_frac_digits) as u8;
+            let divisor = ten_pow(shift);
+            i128_div_rounded(self.coeff, divisor, Some(mode))
+                .checked_mul(divisor)
+                .and_then(Self::checked_from_coefficient)
+        }
+    }
+}
+
+impl<const N: u8> Quantize<Self> for FixedDecimal<N> {
+    type Output = Self;
+
+    /// Returns the integer multiple of `quant` nearest to `self`, according
+    /// to the current [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quant` equals zero or the resulting value can not be
+    /// represented by `FixedDecimal<N>`!
+    #[inline]
+    fn quantize(self, quant: Self) -> Self::Output {
+        self.quantize_with_mode(quant, RoundingMode::default())
+    }
+
+    /// Returns the integer multiple of `quant` nearest to `self`, according
+    /// to `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quant` equals zero or the resulting value can not be
+    /// represented by `FixedDecimal<N>`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{FixedDecimal, Quantize, RoundingMode};
+    /// let d: FixedDecimal<5> = "28.27093".parse().unwrap();
+    /// let q: FixedDecimal<5> = "0.05".parse().unwrap();
+    /// assert_eq!(d.quantize(q).to_string(), "28.25000");
+    /// let r = d.quantize_with_mode(q, RoundingMode::RoundUp);
+    /// assert_eq!(r.to_string(), "28.30000");
+    /// ```
+    fn quantize_with_mode(
+        self,
+        quant: Self,
+        mode: RoundingMode,
+    ) -> Self::Output {
+        #[allow(clippy::manual_assert)]
+        if quant.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        match i128_div_rounded(self.coeff, quant.coeff, Some(mode))
+            .checked_mul(quant.coeff)
+            .and_then(Self::checked_from_coefficient)
+        {
+            Some(d) => d,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<const N: u8> ArchivedFixedDecimal<N> {
+    /// Coefficient of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn coefficient(self) -> i128 {
+        self.coeff
+    }
+
+    /// Number of fractional decimal digits of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn n_frac_digits(self) -> u8 {
+        N
+    }
+}
+
+#[cfg(test)]
+mod fixed_decimal_tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_consts() {
+        assert_eq!(FixedDecimal::<8>::ONE.coefficient(), 100000000);
+        assert_eq!(FixedDecimal::<0>::ONE.coefficient(), 1);
+        assert_eq!(FixedDecimal::<8>::DELTA.to_string(), "0.00000001");
+        assert_eq!(FixedDecimal::<3>::MAX.coefficient(), i128::MAX);
+        assert_eq!(FixedDecimal::<3>::default(), FixedDecimal::<3>::ZERO);
+        assert_eq!(FixedDecimal::<8>::ONE.n_frac_digits(), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_coefficient_min() {
+        let _ = FixedDecimal::<2>::from_coefficient(i128::MIN);
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(core::mem::size_of::<FixedDecimal<8>>(), 16);
+    }
+
+    #[test]
+    fn test_from_into_decimal() {
+        let d = Dec!(-17.5);
+        let f = FixedDecimal::<8>::try_from(d).unwrap();
+        assert_eq!(f.coefficient(), -1750000000);
+        assert_eq!(Decimal::from(f), d);
+        let d = Dec!(1.2340000000);
+        let f = FixedDecimal::<3>::try_from(d).unwrap();
+        assert_eq!(f.coefficient(), 1234);
+        let d = Dec!(1.2345);
+        let r = FixedDecimal::<3>::try_from(d);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+        let r = FixedDecimal::<3>::try_from(Decimal::MAX);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    fn test_from_decimal_rounded() {
+        let d = Dec!(-1.2345);
+        let f = FixedDecimal::<3>::checked_from_decimal_rounded(d).unwrap();
+        assert_eq!(f.coefficient(), -1234);
+        let f = FixedDecimal::<5>::checked_from_decimal_rounded(d).unwrap();
+        assert_eq!(f.coefficient(), -123450);
+        let r = FixedDecimal::<1>::checked_from_decimal_rounded(Decimal::MAX);
+        assert!(r.is_none());
+    }
+
+    #[test]
+    fn test_from_int() {
+        let f = FixedDecimal::<18>::from(i64::MIN);
+        assert_eq!(f.coefficient(), i128::from(i64::MIN) * ten_pow(18));
+        let f = FixedDecimal::<2>::from(7_u8);
+        assert_eq!(f.coefficient(), 700);
+    }
+
+    #[test]
+    fn test_fmt() {
+        let f = FixedDecimal::<4>::from_coefficient(-12345);
+        assert_eq!(f.to_string(), "-1.2345");
+        assert_eq!(format!("{:.2}", f), "-1.23");
+        assert_eq!(format!("{:?}", f), "FixedDecimal<4>(-1.2345)");
+        let f = FixedDecimal::<0>::from_coefficient(17);
+        assert_eq!(f.to_string(), "17");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let f = FixedDecimal::<4>::from_str("-1.5e1").unwrap();
+        assert_eq!(f.coefficient(), -150000);
+        let f = FixedDecimal::<2>::from_str("3.14000").unwrap();
+        assert_eq!(f.coefficient(), 314);
+        let r = FixedDecimal::<2>::from_str("3.141");
+        assert_eq!(r.unwrap_err(), ParseDecimalError::FracDigitLimitExceeded);
+        let r = FixedDecimal::<2>::from_str("1e37");
+        assert_eq!(r.unwrap_err(), ParseDecimalError::InternalOverflow);
+        let r = FixedDecimal::<2>::try_from("x");
+        assert_eq!(r.unwrap_err(), ParseDecimalError::Invalid);
+    }
+
+    #[test]
+    fn test_round() {
+        let f = FixedDecimal::<4>::from_coefficient(-12345);
+        assert_eq!(f.round(2).coefficient(), -12300);
+        assert_eq!(f.round(3).coefficient(), -12340);
+        assert_eq!(f.round(5), f);
+        assert_eq!(f.round(-1), FixedDecimal::ZERO);
+        assert_eq!(f.round(-40), FixedDecimal::ZERO);
+        assert!(FixedDecimal::<2>::MAX.checked_round(-1).is_none());
+    }
+
+    #[test]
+    fn test_quantize() {
+        let f = FixedDecimal::<3>::from_coefficient(28271);
+        let q = FixedDecimal::<3>::from_coefficient(50);
+        assert_eq!(f.quantize(q).coefficient(), 28250);
+        let q = FixedDecimal::<3>::from_coefficient(-3000);
+        assert_eq!(f.quantize(q).coefficient(), 27000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quantize_by_zero() {
+        let f = FixedDecimal::<3>::from_coefficient(28271);
+        let _ = f.quantize(FixedDecimal::ZERO);
+    }
+}
+
+#[cfg(feature = "serde-as-str")]
+#[cfg(test)]
+mod serde_json_tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        for f in [
+            FixedDecimal::<8>::MIN,
+            FixedDecimal::<8>::ZERO,
+            FixedDecimal::<8>::DELTA,
+            FixedDecimal::<8>::from_coefficient(-1234567890123),
+            FixedDecimal::<8>::MAX,
+        ] {
+            let s = serde_json::to_value(f).unwrap();
+            assert_eq!(
+                f,
+                serde_json::from_value::<FixedDecimal<8>>(s).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_as_str() {
+        let f = FixedDecimal::<3>::from_coefficient(-1500);
+        let s = serde_json::to_value(f).unwrap();
+        assert_eq!(s, "-1.500");
+        let s = serde_json::Value::from("1.2345");
+        assert!(serde_json::from_value::<FixedDecimal<3>>(s).is_err());
+    }
+}
+
+#[cfg(feature = "rkyv")]
+#[cfg(test)]
+mod rkyv_tests {
+    use rkyv::{self, Deserialize};
+
+    use super::*;
+
+    fn roundtrip(value: FixedDecimal<8>) -> FixedDecimal<8> {
+        let bytes = rkyv::to_bytes::<_, 256>(&value)
+            .expect("Scratch space size is not enough to serialize value");
+        let archived =
+            rkyv::check_archived_root::<FixedDecimal<8>>(&bytes[..]).unwrap();
+        assert_eq!(archived.coefficient(), value.coefficient());
+        assert_eq!(archived.n_frac_digits(), 8);
+        archived
+            .deserialize(&mut rkyv::Infallible)
+            .expect("Deserialization is infallible")
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for f in [
+            FixedDecimal::<8>::MIN,
+            FixedDecimal::<8>::ZERO,
+            FixedDecimal::<8>::DELTA,
+            FixedDecimal::<8>::from_coefficient(-1234567890123),
+            FixedDecimal::<8>::MAX,
+        ] {
+            assert_eq!(f, roundtrip(f));
+        }
+    }
+}
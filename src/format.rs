@@ -7,16 +7,16 @@
 // $Source$
 // $Revision$
 
-use alloc::{
-    format,
-    string::{String, ToString},
-};
+use alloc::string::String;
 use core::{
     cmp::{min, Ordering},
     fmt,
 };
 
-use fpdec_core::{i128_div_mod_floor, i128_div_rounded, ten_pow};
+use fpdec_core::{
+    i128_div_mod_floor, i128_div_rounded, i128_magnitude, ten_pow,
+    u256_div_rem_u128,
+};
 
 #[cfg(feature = "rkyv")]
 use crate::ArchivedDecimal;
@@ -24,24 +24,180 @@ use crate::{Decimal, MAX_N_FRAC_DIGITS};
 
 impl From<Decimal> for String {
     fn from(d: Decimal) -> Self {
-        if d.n_frac_digits == 0 {
-            format!("{}", d.coefficient())
+        Self::from(d.to_str_buf(&mut [0_u8; Decimal::MAX_STR_LEN]))
+    }
+}
+
+// Stack buffer used to render the string representation of a decimal from
+// right to left, without heap allocation.
+pub(crate) struct RevBuf<const LEN: usize = 58> {
+    buf: [u8; LEN],
+    start: usize,
+}
+
+impl<const LEN: usize> RevBuf<LEN> {
+    // The default length covers the 39 digits of an i128, decimal point and
+    // up to 18 additional digits from padding the fractional part.
+    pub(crate) const fn new() -> Self {
+        Self {
+            buf: [b'0'; LEN],
+            start: LEN,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn push(&mut self, byte: u8) {
+        self.start -= 1;
+        self.buf[self.start] = byte;
+    }
+
+    pub(crate) fn push_zeros(&mut self, n: usize) {
+        for _ in 0..n {
+            self.push(b'0');
+        }
+    }
+
+    // Pushes the digits of `n`, padded with leading zeros to at least
+    // `width` digits. For `n` == 0 and `width` == 0 nothing is pushed.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const fn push_digits(&mut self, mut n: u128, width: usize) {
+        let end = self.start;
+        while n > 0 || end - self.start < width {
+            self.push(b'0' + (n % 10) as u8);
+            n /= 10;
+        }
+    }
+
+    // Pushes the digits of `hi` * 2¹²⁸ + `lo`, padded with leading zeros to
+    // at least `width` digits.
+    pub(crate) fn push_u256_digits(
+        &mut self,
+        mut hi: u128,
+        mut lo: u128,
+        width: usize,
+    ) {
+        // 10³⁸ is the greatest power of ten fitting into an u128.
+        const CHUNK: u128 = ten_pow(38) as u128;
+        let end = self.start;
+        while hi != 0 {
+            let ((qh, ql), r) = u256_div_rem_u128(hi, lo, CHUNK);
+            self.push_digits(r, 38);
+            (hi, lo) = (qh, ql);
+        }
+        self.push_digits(lo, width.saturating_sub(end - self.start));
+    }
+
+    // Pushes the digits of `coeff`, followed by `n_trailing_zeros` zeros,
+    // with a decimal point in front of the last `n_frac_digits` of them.
+    // The fractional part is padded with leading zeros if necessary; for
+    // `n_frac_digits` <= 0 zeros are appended instead of a decimal point.
+    #[allow(clippy::cast_sign_loss)]
+    pub(crate) fn push_decimal(
+        &mut self,
+        coeff: u128,
+        n_trailing_zeros: usize,
+        n_frac_digits: i32,
+    ) {
+        if n_frac_digits <= 0 {
+            self.push_zeros(
+                n_trailing_zeros + n_frac_digits.unsigned_abs() as usize,
+            );
+            self.push_digits(coeff, 1);
+        } else if n_frac_digits as usize <= n_trailing_zeros {
+            self.push_zeros(n_frac_digits as usize);
+            self.push(b'.');
+            self.push_zeros(n_trailing_zeros - n_frac_digits as usize);
+            self.push_digits(coeff, 1);
         } else {
+            // Number of fractional digits taken from the coefficient
+            let n_coeff_frac_digits =
+                n_frac_digits as usize - n_trailing_zeros;
+            #[allow(clippy::cast_possible_truncation)]
+            #[allow(clippy::integer_division)]
             let (int, frac) =
-                i128_div_mod_floor(d.coeff.abs(), ten_pow(d.n_frac_digits));
-            format!(
-                "{}{}.{:0width$}",
-                if d.coeff >= 0 { "" } else { "-" },
-                int,
-                frac,
-                width = d.n_frac_digits() as usize
-            )
+                match 10_u128.checked_pow(n_coeff_frac_digits as u32) {
+                    Some(divisor) => (coeff / divisor, coeff % divisor),
+                    None => (0, coeff),
+                };
+            self.push_zeros(n_trailing_zeros);
+            self.push_digits(frac, n_coeff_frac_digits);
+            self.push(b'.');
+            self.push_digits(int, 1);
         }
     }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.buf[self.start..]
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // The buffer contains only ASCII characters.
+        core::str::from_utf8(self.as_bytes()).unwrap_or_default()
+    }
+}
+
+impl Decimal {
+    /// Maximum length of the string representation of a `Decimal`, as
+    /// returned by [Decimal::to_str_buf]: sign, 39 digits and decimal
+    /// point.
+    pub const MAX_STR_LEN: usize = 41;
+
+    /// Writes the string representation of `self` into `buf` and returns
+    /// it as `&str`, without any heap allocation.
+    ///
+    /// The result is the same as the one given by [fmt::Display] without
+    /// a precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string representation of `self` does not fit into
+    /// `buf`! A buffer of length [Decimal::MAX_STR_LEN] is always
+    /// sufficient.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let mut buf = [0_u8; Decimal::MAX_STR_LEN];
+    /// assert_eq!(Dec!(-1234.560).to_str_buf(&mut buf), "-1234.560");
+    /// let mut buf = [0_u8; 4];
+    /// assert_eq!(Dec!(0.75).to_str_buf(&mut buf), "0.75");
+    /// ```
+    pub fn to_str_buf<const N: usize>(self, buf: &mut [u8; N]) -> &str {
+        let mut rev_buf = self.to_rev_buf(self.n_frac_digits);
+        if self.coeff < 0 {
+            rev_buf.push(b'-');
+        }
+        let bytes = rev_buf.as_bytes();
+        let len = bytes.len();
+        assert!(
+            len <= N,
+            "Buffer too small for string representation of Decimal."
+        );
+        buf[..len].copy_from_slice(bytes);
+        // The buffer contains only ASCII characters.
+        core::str::from_utf8(&buf[..len]).unwrap_or_default()
+    }
+
+    // Renders the absolute value of `self`, rounded to `prec` fractional
+    // digits according to the default rounding mode. `prec` must not
+    // exceed MAX_N_FRAC_DIGITS.
+    fn to_rev_buf(self, prec: u8) -> RevBuf {
+        let (int, frac) = self.int_and_frac_rounded(prec);
+        let mut buf = RevBuf::new();
+        if prec > 0 {
+            buf.push_digits(frac.unsigned_abs(), prec as usize);
+            buf.push(b'.');
+        }
+        buf.push_digits(int.unsigned_abs(), 1);
+        buf
+    }
 }
 
 #[cfg(test)]
 mod test_into_string {
+    use alloc::format;
+
     use super::*;
     use crate::Dec;
 
@@ -57,6 +213,40 @@ mod test_into_string {
         let s: String = d.into();
         assert_eq!(s, "-1234567890123456789000.00700");
     }
+
+    #[test]
+    fn test_to_str_buf() {
+        let mut buf = [0_u8; Decimal::MAX_STR_LEN];
+        for d in [
+            Decimal::ZERO,
+            Dec!(0.000),
+            Dec!(-0.5),
+            Dec!(-17),
+            Dec!(1234567.000890),
+            Decimal::DELTA,
+            -Decimal::DELTA,
+            Decimal::MAX,
+            Decimal::MIN,
+            Decimal::new_raw(i128::MIN + 1, 18),
+        ] {
+            assert_eq!(d.to_str_buf(&mut buf), format!("{}", d));
+        }
+        assert_eq!(
+            Decimal::new_raw(i128::MIN + 1, 18)
+                .to_str_buf(&mut buf)
+                .len(),
+            Decimal::MAX_STR_LEN
+        );
+        let mut buf = [0_u8; 5];
+        assert_eq!(Dec!(-0.75).to_str_buf(&mut buf), "-0.75");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_str_buf_too_small() {
+        let mut buf = [0_u8; 4];
+        let _ = Dec!(-0.75).to_str_buf(&mut buf);
+    }
 }
 
 macro_rules! impl_debug {
@@ -90,6 +280,8 @@ impl_debug!(ArchivedDecimal, "ArchivedDecimal");
 
 #[cfg(test)]
 mod test_fmt_debug {
+    use alloc::format;
+
     use super::*;
     use crate::Dec;
 
@@ -119,6 +311,11 @@ impl fmt::Display for Decimal {
     /// `self.n_frac_digits()`, the value gets rounded according to the
     /// default rounding mode.
     ///
+    /// With the alternate flag (`#`), the precision gives the number of
+    /// significant digits instead of the number of fractional digits (see
+    /// [Decimal::round_sig]). Trailing zeros are kept, so that exactly that
+    /// many digits are shown (at least those of the integral part).
+    ///
     /// # Examples:
     ///
     /// ```rust
@@ -128,65 +325,230 @@ impl fmt::Display for Decimal {
     /// assert_eq!(format!("{}", d), "-1234.56");
     /// assert_eq!(format!("{:014.3}", d), "-000001234.560");
     /// assert_eq!(format!("{:10.1}", d), "   -1234.6");
+    /// assert_eq!(format!("{:#.5}", d), "-1234.6");
+    /// assert_eq!(format!("{:#.2}", d), "-1200");
+    /// assert_eq!(format!("{:#.3}", Dec!(0.0015)), "0.00150");
     /// ```
     fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let tmp: String;
+        if let (true, Some(n_sig_digits)) =
+            (form.alternate(), form.precision())
+        {
+            return self.fmt_sig(form, n_sig_digits);
+        }
         #[allow(clippy::cast_possible_truncation)]
         let prec = match form.precision() {
             Some(prec) => min(prec, MAX_N_FRAC_DIGITS as usize) as u8,
             None => self.n_frac_digits,
         };
-        if self.n_frac_digits == 0 {
-            if prec > 0 {
-                tmp = format!(
-                    "{}.{:0width$}",
-                    self.coeff.abs(),
-                    0,
-                    width = prec as usize
+        form.pad_integral(self.coeff >= 0, "", self.to_rev_buf(prec).as_str())
+    }
+}
+
+impl Decimal {
+    // Returns the integral and the fractional part of the absolute value of
+    // `self`, rounded to `prec` fractional digits according to the default
+    // rounding mode. `prec` must not exceed MAX_N_FRAC_DIGITS.
+    pub(crate) fn int_and_frac_rounded(&self, prec: u8) -> (i128, i128) {
+        match prec.cmp(&(self.n_frac_digits)) {
+            Ordering::Equal => i128_div_mod_floor(
+                self.coeff.abs(),
+                ten_pow(self.n_frac_digits),
+            ),
+            Ordering::Less => {
+                // Important: first round, then take abs() !
+                let coeff = i128_div_rounded(
+                    self.coeff,
+                    ten_pow(self.n_frac_digits - prec),
+                    None,
                 );
-            } else {
-                tmp = self.coeff.abs().to_string();
+                i128_div_mod_floor(coeff.abs(), ten_pow(prec))
             }
-        } else {
-            let (int, frac) = match prec.cmp(&(self.n_frac_digits)) {
-                Ordering::Equal => i128_div_mod_floor(
+            Ordering::Greater => {
+                let (int, frac) = i128_div_mod_floor(
                     self.coeff.abs(),
                     ten_pow(self.n_frac_digits),
-                ),
-                Ordering::Less => {
-                    // Important: first round, then take abs() !
-                    let coeff = i128_div_rounded(
-                        self.coeff,
-                        ten_pow(self.n_frac_digits - prec),
-                        None,
-                    );
-                    i128_div_mod_floor(coeff.abs(), ten_pow(prec))
-                }
-                Ordering::Greater => {
-                    let (int, frac) = i128_div_mod_floor(
-                        self.coeff.abs(),
-                        ten_pow(self.n_frac_digits),
-                    );
-                    (int, frac * ten_pow(prec - self.n_frac_digits))
-                }
-            };
-            if prec > 0 {
-                tmp = format!(
-                    "{}.{:0width$}",
-                    int,
-                    frac,
-                    width = prec as usize
                 );
+                (int, frac * ten_pow(prec - self.n_frac_digits))
+            }
+        }
+    }
+
+    // Formats `self` with `n_sig_digits` significant digits.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn fmt_sig(
+        &self,
+        form: &mut fmt::Formatter<'_>,
+        n_sig_digits: usize,
+    ) -> fmt::Result {
+        // An i128 has at most 39 significant digits.
+        let n_sig_digits = n_sig_digits.clamp(1, 39) as i32;
+        let mut n_frac_digits =
+            n_sig_digits - 1 - i32::from(self.magnitude());
+        let mut coeff = self.coeff;
+        let mut n_trailing_zeros = 0_usize;
+        if n_frac_digits < i32::from(self.n_frac_digits) {
+            // 0 < shift <= 38
+            let shift = (i32::from(self.n_frac_digits) - n_frac_digits) as u8;
+            coeff = i128_div_rounded(coeff, ten_pow(shift), None);
+            if i32::from(i128_magnitude(coeff)) >= n_sig_digits {
+                // Rounding has added a digit in front, so drop the last
+                // (zero) digit.
+                coeff /= 10;
+                n_frac_digits -= 1;
+            }
+        } else {
+            // Instead of shifting the coefficient, which may overflow, the
+            // missing digits are appended as zeros.
+            n_trailing_zeros =
+                (n_frac_digits - i32::from(self.n_frac_digits)) as usize;
+        }
+        let mut buf: RevBuf = RevBuf::new();
+        buf.push_decimal(
+            coeff.unsigned_abs(),
+            n_trailing_zeros,
+            n_frac_digits,
+        );
+        form.pad_integral(self.coeff >= 0, "", buf.as_str())
+    }
+}
+
+impl Decimal {
+    // Returns the significant digits of `self` as coefficient, the number of
+    // zeros to be appended to it and the exponent of the first digit. If
+    // `n_sig_digits` is given, the coefficient gets rounded according to
+    // the default rounding mode or padded with zeros, so that exactly that
+    // many digits result. Otherwise trailing zeros are removed.
+    #[allow(clippy::cast_possible_truncation)]
+    fn sig_digits_and_exp(
+        &self,
+        n_sig_digits: Option<usize>,
+    ) -> (u128, usize, i32) {
+        if self.coeff == 0 {
+            return (0, n_sig_digits.unwrap_or(1).max(1) - 1, 0);
+        }
+        let mut coeff = self.coeff;
+        while coeff % 10 == 0 {
+            coeff /= 10;
+        }
+        let mut exp = i32::from(self.magnitude());
+        let mut n_trailing_zeros = 0_usize;
+        if let Some(n_sig_digits) = n_sig_digits {
+            let n_digits = i128_magnitude(coeff) as usize + 1;
+            if n_digits > n_sig_digits {
+                // 0 < shift <= 38
+                let shift = (n_digits - n_sig_digits) as u8;
+                coeff = i128_div_rounded(coeff, ten_pow(shift), None);
+                if i128_magnitude(coeff) as usize >= n_sig_digits {
+                    // Rounding has added a digit in front, so drop the last
+                    // (zero) digit.
+                    coeff /= 10;
+                    exp += 1;
+                }
+            } else {
+                n_trailing_zeros = n_sig_digits - n_digits;
+            }
+        }
+        (coeff.unsigned_abs(), n_trailing_zeros, exp)
+    }
+
+    // Formats `self` in scientific notation or, if the alternate flag is
+    // set, in engineering notation, using `exp_char` as exponent marker.
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    fn fmt_exp(
+        &self,
+        form: &mut fmt::Formatter<'_>,
+        exp_char: u8,
+    ) -> fmt::Result {
+        // An i128 has at most 39 significant digits.
+        let n_sig_digits = form.precision().map(|prec| {
+            if form.alternate() {
+                prec.clamp(1, 39)
             } else {
-                tmp = int.to_string();
+                prec.saturating_add(1).min(39)
             }
+        });
+        let (coeff, n_trailing_zeros, exp) =
+            self.sig_digits_and_exp(n_sig_digits);
+        let (exp, n_int_digits) = if form.alternate() {
+            let eng_exp = exp.div_euclid(3) * 3;
+            (eng_exp, exp - eng_exp + 1)
+        } else {
+            (exp, 1)
+        };
+        let n_digits = i32::from(i128_magnitude(coeff as i128))
+            + 1
+            + n_trailing_zeros as i32;
+        let mut buf: RevBuf = RevBuf::new();
+        buf.push_digits(u128::from(exp.unsigned_abs()), 1);
+        if exp < 0 {
+            buf.push(b'-');
         }
-        form.pad_integral(self.coeff >= 0, "", &tmp)
+        buf.push(exp_char);
+        buf.push_decimal(coeff, n_trailing_zeros, n_digits - n_int_digits);
+        form.pad_integral(self.coeff >= 0, "", buf.as_str())
+    }
+}
+
+impl fmt::LowerExp for Decimal {
+    /// Formats the value in scientific notation, using `e` as exponent
+    /// marker.
+    ///
+    /// If a precision is given, the mantissa is shown with exactly that
+    /// many fractional digits (but not more than 38), rounded according to
+    /// the default rounding mode if necessary. Otherwise all significant
+    /// digits are shown.
+    ///
+    /// With the alternate flag (`#`), the value is formatted in engineering
+    /// notation, i.e. with an exponent being a multiple of 3 and one to
+    /// three integral digits in the mantissa. In that case the precision
+    /// gives the number of significant digits, like it does for
+    /// [fmt::Display].
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use core::fmt;
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(-1234.50);
+    /// assert_eq!(format!("{:e}", d), "-1.2345e3");
+    /// assert_eq!(format!("{:.2e}", d), "-1.23e3");
+    /// assert_eq!(format!("{:>10.1e}", d), "    -1.2e3");
+    /// let d = Dec!(0.0000275);
+    /// assert_eq!(format!("{:e}", d), "2.75e-5");
+    /// assert_eq!(format!("{:#e}", d), "27.5e-6");
+    /// assert_eq!(format!("{:#.2e}", d), "28e-6");
+    /// ```
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_exp(form, b'e')
+    }
+}
+
+impl fmt::UpperExp for Decimal {
+    /// Formats the value in scientific notation, using `E` as exponent
+    /// marker.
+    ///
+    /// See [fmt::LowerExp] for details.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use core::fmt;
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(17400000);
+    /// assert_eq!(format!("{:E}", d), "1.74E7");
+    /// assert_eq!(format!("{:#E}", d), "17.4E6");
+    /// ```
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_exp(form, b'E')
     }
 }
 
 #[cfg(test)]
 mod test_fmt_display {
+    use alloc::{format, string::ToString};
+
     use super::*;
     use crate::Dec;
 
@@ -242,4 +604,159 @@ mod test_fmt_display {
         assert_eq!(format!("{:10.5}", d), "  -0.00123");
         assert_eq!(format!("{:010.6}", d), "-00.001235");
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fmt_decimal_with_rounding_mode() {
+        use crate::{with_rounding_mode, RoundingMode};
+        let d = Dec!(0.25);
+        let s = with_rounding_mode(RoundingMode::RoundHalfOdd, || {
+            format!("{:.1}", d)
+        });
+        assert_eq!(s, "0.3");
+        let d = Dec!(-0.21);
+        let s = with_rounding_mode(RoundingMode::RoundToOdd, || {
+            format!("{:.1}", d)
+        });
+        assert_eq!(s, "-0.3");
+    }
+
+    #[test]
+    fn test_fmt_decimal_sig_digits() {
+        let d = Dec!(28.27093);
+        assert_eq!(format!("{:#.3}", d), "28.3");
+        assert_eq!(format!("{:#.1}", d), "30");
+        assert_eq!(format!("{:#.0}", d), "30");
+        assert_eq!(format!("{:#.8}", d), "28.270930");
+        assert_eq!(format!("{:#}", d), "28.27093");
+        assert_eq!(format!("{:>#8.3}", d), "    28.3");
+        assert_eq!(format!("{:#08.3}", -d), "-00028.3");
+        let d = Dec!(-0.000999);
+        assert_eq!(format!("{:#.2}", d), "-0.0010");
+        assert_eq!(format!("{:#.4}", d), "-0.0009990");
+        let d = Dec!(999.5);
+        assert_eq!(format!("{:#.3}", d), "1000");
+        assert_eq!(format!("{:#.4}", d), "999.5");
+    }
+
+    #[test]
+    fn test_fmt_decimal_sig_digits_limits() {
+        let d = Decimal::ZERO;
+        assert_eq!(format!("{:#.3}", d), "0.00");
+        let d = Decimal::new_raw(1, 18);
+        assert_eq!(format!("{:#.3}", d), "0.00000000000000000100");
+        let d = Decimal::MAX;
+        assert_eq!(
+            format!("{:#.2}", d),
+            "170000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            format!("{:#.1}", d),
+            "200000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            format!("{:#.50}", Dec!(1.5)),
+            format!("{:#.39}", Dec!(1.5))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_exp {
+    use alloc::format;
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_fmt_sci() {
+        let d = Dec!(1234.5);
+        assert_eq!(format!("{:e}", d), "1.2345e3");
+        assert_eq!(format!("{:E}", d), "1.2345E3");
+        assert_eq!(format!("{:.2e}", d), "1.23e3");
+        assert_eq!(format!("{:.0e}", d), "1e3");
+        assert_eq!(format!("{:.6e}", d), "1.234500e3");
+        assert_eq!(format!("{:+e}", d), "+1.2345e3");
+        assert_eq!(format!("{:e}", Dec!(1.50)), "1.5e0");
+        assert_eq!(format!("{:e}", Dec!(1200)), "1.2e3");
+        let d = Dec!(-0.00012);
+        assert_eq!(format!("{:e}", d), "-1.2e-4");
+        assert_eq!(format!("{:10.1e}", d), "   -1.2e-4");
+        assert_eq!(format!("{:<10.1e}", d), "-1.2e-4   ");
+        assert_eq!(format!("{:010.1e}", d), "-0001.2e-4");
+        assert_eq!(format!("{:.2e}", Dec!(9.995)), "1.00e1");
+        assert_eq!(format!("{:.2e}", Dec!(-9.995)), "-1.00e1");
+    }
+
+    #[test]
+    fn test_fmt_sci_limits() {
+        assert_eq!(format!("{:e}", Decimal::ZERO), "0e0");
+        assert_eq!(format!("{:.2e}", Dec!(0.000)), "0.00e0");
+        assert_eq!(format!("{:e}", Decimal::DELTA), "1e-18");
+        assert_eq!(
+            format!("{:e}", Decimal::MAX),
+            "1.70141183460469231731687303715884105727e38"
+        );
+        assert_eq!(
+            format!("{:e}", Decimal::MIN),
+            "-1.70141183460469231731687303715884105727e38"
+        );
+        assert_eq!(format!("{:.3e}", Decimal::MAX), "1.701e38");
+        assert_eq!(format!("{:.0e}", Decimal::MIN), "-2e38");
+        assert_eq!(
+            format!("{:.40e}", Dec!(1)),
+            format!("1.{}e0", "0".repeat(38))
+        );
+    }
+
+    #[test]
+    fn test_fmt_eng() {
+        assert_eq!(format!("{:#e}", Dec!(1234.5)), "1.2345e3");
+        assert_eq!(format!("{:#e}", Dec!(12345)), "12.345e3");
+        assert_eq!(format!("{:#E}", Dec!(123456.7)), "123.4567E3");
+        assert_eq!(format!("{:#e}", Dec!(0.00012)), "120e-6");
+        assert_eq!(format!("{:#e}", Dec!(-0.0012)), "-1.2e-3");
+        assert_eq!(format!("{:#e}", Dec!(0.012)), "12e-3");
+        assert_eq!(format!("{:#e}", Dec!(7)), "7e0");
+        assert_eq!(format!("{:#e}", Decimal::DELTA), "1e-18");
+        assert_eq!(
+            format!("{:#e}", Decimal::MAX),
+            "170.141183460469231731687303715884105727e36"
+        );
+        assert_eq!(format!("{:#e}", Decimal::ZERO), "0e0");
+        assert_eq!(format!("{:#.3e}", Decimal::ZERO), "0.00e0");
+    }
+
+    #[test]
+    fn test_fmt_eng_sig_digits() {
+        let d = Dec!(12345);
+        assert_eq!(format!("{:#.2e}", d), "12e3");
+        assert_eq!(format!("{:#.1e}", d), "10e3");
+        assert_eq!(format!("{:#.0e}", d), "10e3");
+        assert_eq!(format!("{:#.7e}", d), "12.34500e3");
+        assert_eq!(format!("{:#.3e}", Dec!(10000)), "10.0e3");
+        assert_eq!(format!("{:#.2e}", Dec!(100000)), "100e3");
+        assert_eq!(format!("{:#.5e}", Dec!(0.00012)), "120.00e-6");
+        assert_eq!(format!("{:#.3e}", Dec!(999.5)), "1.00e3");
+        assert_eq!(format!("{:#.3e}", Dec!(-999.4)), "-999e0");
+        assert_eq!(format!("{:>#10.3e}", Dec!(-0.5678)), "   -568e-3");
+        assert_eq!(format!("{:#010.3e}", Dec!(0.5678)), "0000568e-3");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fmt_exp_with_rounding_mode() {
+        use crate::{with_rounding_mode, RoundingMode};
+        let d = Dec!(2.5);
+        assert_eq!(format!("{:.0e}", d), "2e0");
+        let s = with_rounding_mode(RoundingMode::RoundHalfUp, || {
+            format!("{:.0e}", d)
+        });
+        assert_eq!(s, "3e0");
+        let d = Dec!(-1234);
+        let s = with_rounding_mode(RoundingMode::RoundFloor, || {
+            format!("{:#.2e}", d)
+        });
+        assert_eq!(s, "-1.3e3");
+    }
 }
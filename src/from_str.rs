@@ -8,11 +8,13 @@
 // $Revision$
 
 use alloc::string::String;
-use core::{convert::TryFrom, str::FromStr};
+use core::{convert::TryFrom, iter, str::FromStr};
 
-use fpdec_core::{checked_mul_pow_ten, str_to_dec};
+use fpdec_core::{checked_mul_pow_ten, str_to_dec, Round, RoundingMode};
 
-use crate::{Decimal, ParseDecimalError, MAX_N_FRAC_DIGITS};
+use crate::{
+    fused_ops::round_signed, Decimal, ParseDecimalError, MAX_N_FRAC_DIGITS,
+};
 
 impl FromStr for Decimal {
     type Err = ParseDecimalError;
@@ -96,6 +98,348 @@ impl TryFrom<String> for Decimal {
     }
 }
 
+impl Decimal {
+    /// Convert a number literal into a `Decimal`, rounded to `n_frac_digits`
+    /// fractional digits according to the given [RoundingMode].
+    ///
+    /// The literal must have the same form as accepted by
+    /// [Decimal::from_str], but it may have any number of digits and any
+    /// exponent. If it has less fractional digits than `n_frac_digits`, its
+    /// own number of fractional digits is kept, like in
+    /// [Round::round_with_mode].
+    ///
+    /// # Errors
+    ///
+    /// * An empty string has been given as `lit` ->
+    ///   `ParseDecimalError::Empty`
+    /// * `lit` does not have the form accepted by [Decimal::from_str] ->
+    ///   `ParseDecimalError::Invalid`
+    /// * `n_frac_digits` exceeds [crate::MAX_N_FRAC_DIGITS] ->
+    ///   `ParseDecimalError::FracDigitLimitExceeded`
+    /// * The rounded value exceeds the internal representation of
+    ///   `Decimal` -> ParseDecimalError::InternalOverflow
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use fpdec::{Decimal, ParseDecimalError, RoundingMode};
+    /// # fn main() -> Result<(), ParseDecimalError> {
+    /// let lit = "0.12345678901234567890123";
+    /// let mode = RoundingMode::RoundHalfUp;
+    /// let d = Decimal::from_str_rounded(lit, 18, mode)?;
+    /// assert_eq!(d.to_string(), "0.123456789012345679");
+    /// let d = Decimal::from_str_rounded(lit, 4, mode)?;
+    /// assert_eq!(d.to_string(), "0.1235");
+    /// let d = Decimal::from_str_rounded("-2.5", 4, mode)?;
+    /// assert_eq!(d.to_string(), "-2.5");
+    /// # Ok(()) }
+    /// ```
+    pub fn from_str_rounded(
+        lit: &str,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Result<Self, ParseDecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(ParseDecimalError::FracDigitLimitExceeded);
+        }
+        match Self::from_str(lit) {
+            // n_frac_digits <= MAX_N_FRAC_DIGITS <= i8::MAX
+            #[allow(clippy::cast_possible_wrap)]
+            Ok(d) => d
+                .checked_round_with_mode(n_frac_digits as i8, mode)
+                .ok_or(ParseDecimalError::InternalOverflow),
+            Err(
+                ParseDecimalError::FracDigitLimitExceeded
+                | ParseDecimalError::InternalOverflow,
+            ) => parse_rounded(lit, n_frac_digits, mode),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+// Split a literal in the form accepted by `Decimal::from_str` into sign,
+// integral digits, fractional digits and the saturated exponent.
+fn split_lit(
+    lit: &str,
+) -> Result<(bool, &str, &str, isize), ParseDecimalError> {
+    let (is_negative, rest) = match lit.as_bytes().first() {
+        None => return Err(ParseDecimalError::Empty),
+        Some(b'-') => (true, &lit[1..]),
+        Some(b'+') => (false, &lit[1..]),
+        Some(_) => (false, lit),
+    };
+    let (mantissa, exp_lit) = match rest.find(['e', 'E']) {
+        Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+        None => (rest, None),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if int.len() + frac.len() == 0 || !is_digits(int) || !is_digits(frac) {
+        return Err(ParseDecimalError::Invalid);
+    }
+    let mut exp = 0_isize;
+    if let Some(exp_lit) = exp_lit {
+        let (exp_is_negative, exp_digits) = match exp_lit.as_bytes().first() {
+            Some(b'-') => (true, &exp_lit[1..]),
+            Some(b'+') => (false, &exp_lit[1..]),
+            _ => (false, exp_lit),
+        };
+        if exp_digits.is_empty() || !is_digits(exp_digits) {
+            return Err(ParseDecimalError::Invalid);
+        }
+        for b in exp_digits.bytes() {
+            exp =
+                exp.saturating_mul(10).saturating_add(isize::from(b - b'0'));
+        }
+        if exp_is_negative {
+            exp = -exp;
+        }
+    }
+    Ok((is_negative, int, frac, exp))
+}
+
+// Accumulate the given decimal digits into an u128, or return None in case
+// of an overflow.
+fn accum_digits(mut digits: impl Iterator<Item = u8>) -> Option<u128> {
+    digits.try_fold(0_u128, |acc, b| {
+        acc.checked_mul(10)?.checked_add(u128::from(b - b'0'))
+    })
+}
+
+// Convert a literal with an arbitrary number of digits into a `Decimal`,
+// rounded to `n_frac_digits` according to `mode`.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_wrap)]
+#[allow(clippy::cast_sign_loss)]
+fn parse_rounded(
+    lit: &str,
+    n_frac_digits: u8,
+    mode: RoundingMode,
+) -> Result<Decimal, ParseDecimalError> {
+    let (is_negative, int, frac, exp) = split_lit(lit)?;
+    // value = <digits> * 10 ^ exp
+    let exp = exp.saturating_sub(frac.len() as isize);
+    let digits = int.bytes().chain(frac.bytes()).skip_while(|b| *b == b'0');
+    let n_digits = digits.clone().count();
+    let n_dropped_digits = exp
+        .saturating_neg()
+        .saturating_sub(isize::from(n_frac_digits));
+    if n_dropped_digits <= 0 {
+        // No rounding needed.
+        let n_frac_digits = (-exp).max(0) as u8;
+        let shift = exp + isize::from(n_frac_digits);
+        if n_digits == 0 {
+            return Ok(Decimal {
+                coeff: 0,
+                n_frac_digits,
+            });
+        }
+        return accum_digits(digits)
+            .and_then(|mag| i128::try_from(mag).ok())
+            .filter(|_| shift <= 38)
+            .and_then(|coeff| checked_mul_pow_ten(coeff, shift as u8))
+            .map(|coeff| Decimal {
+                coeff: if is_negative { -coeff } else { coeff },
+                n_frac_digits,
+            })
+            .ok_or(ParseDecimalError::InternalOverflow);
+    }
+    let n_dropped_digits = n_dropped_digits as usize;
+    let n_quot_digits = n_digits.saturating_sub(n_dropped_digits);
+    let quot = accum_digits(digits.clone().take(n_quot_digits))
+        .ok_or(ParseDecimalError::InternalOverflow)?;
+    // The dropped digits are condensed into at most 38 digits: the first 37
+    // ones, followed by a 1 if any of the remaining ones is not zero.
+    let n_zeros = n_dropped_digits.saturating_sub(n_digits).min(38);
+    let mut dropped =
+        iter::repeat_n(b'0', n_zeros).chain(digits.skip(n_quot_digits));
+    let mut rem = 0_u128;
+    let mut divisor = 1_u128;
+    for b in dropped.by_ref().take(37) {
+        rem = rem * 10 + u128::from(b - b'0');
+        divisor *= 10;
+    }
+    if dropped.any(|b| b != b'0') {
+        rem = rem * 10 + 1;
+        divisor *= 10;
+    }
+    round_signed(is_negative, quot, rem, divisor, Some(mode))
+        .map(|coeff| Decimal {
+            coeff,
+            n_frac_digits,
+        })
+        .ok_or(ParseDecimalError::InternalOverflow)
+}
+
+#[cfg(test)]
+mod from_str_rounded_tests {
+    use alloc::format;
+
+    use super::*;
+
+    #[test]
+    fn test_excess_frac_digits() {
+        let lit = "0.1234567890123456789";
+        assert_eq!(
+            Decimal::from_str(lit),
+            Err(ParseDecimalError::FracDigitLimitExceeded)
+        );
+        let d =
+            Decimal::from_str_rounded(lit, 18, RoundingMode::RoundHalfEven)
+                .unwrap();
+        assert_eq!(d.coefficient(), 123456789012345679);
+        assert_eq!(d.n_frac_digits(), 18);
+        let lit = "-0.1234567890123456785";
+        let d =
+            Decimal::from_str_rounded(lit, 18, RoundingMode::RoundHalfEven)
+                .unwrap();
+        assert_eq!(d.coefficient(), -123456789012345678);
+        let d = Decimal::from_str_rounded(lit, 18, RoundingMode::RoundHalfUp)
+            .unwrap();
+        assert_eq!(d.coefficient(), -123456789012345679);
+        let lit = "-0.12345678901234567850000000000000000000000000000000001";
+        let d =
+            Decimal::from_str_rounded(lit, 18, RoundingMode::RoundHalfEven)
+                .unwrap();
+        assert_eq!(d.coefficient(), -123456789012345679);
+        let d = Decimal::from_str_rounded(lit, 2, RoundingMode::RoundFloor)
+            .unwrap();
+        assert_eq!(d.coefficient(), -13);
+        assert_eq!(d.n_frac_digits(), 2);
+    }
+
+    #[test]
+    fn test_overlong_mantissa() {
+        let lit = "12345678901234567890123456789012345678.90123456789012345";
+        assert_eq!(
+            Decimal::from_str(lit),
+            Err(ParseDecimalError::InternalOverflow)
+        );
+        let d =
+            Decimal::from_str_rounded(lit, 0, RoundingMode::RoundHalfEven)
+                .unwrap();
+        assert_eq!(d.coefficient(), 12345678901234567890123456789012345679);
+        assert_eq!(d.n_frac_digits(), 0);
+        let lit = "0000000000000000000000000000000000000000000012.5000000000";
+        let d =
+            Decimal::from_str_rounded(lit, 4, RoundingMode::RoundHalfEven)
+                .unwrap();
+        assert_eq!(d.coefficient(), 125000);
+        assert_eq!(d.n_frac_digits(), 4);
+    }
+
+    #[test]
+    fn test_exponent() {
+        let d = Decimal::from_str_rounded(
+            "123456789e-30",
+            18,
+            RoundingMode::RoundHalfEven,
+        )
+        .unwrap();
+        assert_eq!(d, Decimal::ZERO);
+        assert_eq!(d.n_frac_digits(), 18);
+        let d = Decimal::from_str_rounded(
+            "123456789e-30",
+            18,
+            RoundingMode::RoundUp,
+        )
+        .unwrap();
+        assert_eq!(d, Decimal::DELTA);
+        let d = Decimal::from_str_rounded(
+            "-1e-1000000000000000000000",
+            2,
+            RoundingMode::RoundFloor,
+        )
+        .unwrap();
+        assert_eq!(d.coefficient(), -1);
+        let d =
+            Decimal::from_str_rounded("1.5e-1", 0, RoundingMode::RoundHalfUp)
+                .unwrap();
+        assert_eq!(d, Decimal::ZERO);
+        let d = Decimal::from_str_rounded(
+            "2.5E+37",
+            0,
+            RoundingMode::RoundHalfUp,
+        )
+        .unwrap();
+        assert_eq!(d.coefficient(), 25 * 10_i128.pow(36));
+        let d = Decimal::from_str_rounded(
+            "0.0e999",
+            2,
+            RoundingMode::RoundHalfUp,
+        )
+        .unwrap();
+        assert_eq!(d, Decimal::ZERO);
+        for lit in ["1e39", "-1.5e400", "1e999999999999999999999"] {
+            assert_eq!(
+                Decimal::from_str_rounded(lit, 2, RoundingMode::RoundHalfUp),
+                Err(ParseDecimalError::InternalOverflow)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        let d =
+            Decimal::from_str_rounded("2.5", 0, RoundingMode::RoundHalfEven)
+                .unwrap();
+        assert_eq!(d.coefficient(), 2);
+        let d = Decimal::from_str_rounded("-1.5", 4, RoundingMode::RoundDown)
+            .unwrap();
+        assert_eq!(d.coefficient(), -15);
+        assert_eq!(d.n_frac_digits(), 1);
+    }
+
+    #[test]
+    fn test_limits() {
+        let lit = "170141183460469231731687303715884105727.5";
+        assert_eq!(
+            Decimal::from_str_rounded(lit, 0, RoundingMode::RoundDown),
+            Ok(Decimal::MAX)
+        );
+        assert_eq!(
+            Decimal::from_str_rounded(lit, 0, RoundingMode::RoundHalfUp),
+            Err(ParseDecimalError::InternalOverflow)
+        );
+        assert_eq!(
+            Decimal::from_str_rounded(
+                &format!("-{lit}"),
+                0,
+                RoundingMode::RoundCeiling
+            ),
+            Ok(Decimal::MIN)
+        );
+        assert_eq!(
+            Decimal::from_str_rounded("1", 19, RoundingMode::RoundHalfUp),
+            Err(ParseDecimalError::FracDigitLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            Decimal::from_str_rounded("", 2, RoundingMode::RoundHalfUp),
+            Err(ParseDecimalError::Empty)
+        );
+        for lit in [
+            " 1",
+            "+",
+            "1.2.3",
+            ".e5",
+            "1e",
+            "0.12345678901234567890x",
+            "0.12345678901234567890e1.5",
+            "1234567890123456789012345678901234567890 ",
+        ] {
+            assert_eq!(
+                Decimal::from_str_rounded(lit, 2, RoundingMode::RoundHalfUp),
+                Err(ParseDecimalError::Invalid),
+                "{lit}"
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::ToString;
@@ -0,0 +1,616 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::cmp::{max, Ordering};
+
+use fpdec_core::{
+    round_quot, ten_pow, u128_mul_u128, u256_checked_add,
+    u256_checked_mul_u128, u256_div_rem_u128, u256_sub, RoundingMode,
+    MAX_N_FRAC_DIGITS,
+};
+
+use crate::{
+    bigint::UBig,
+    decimal256::{div_rem_code, round_mag_with_mode},
+    Decimal, DecimalError,
+};
+
+// Round sign * (quot + rem / divisor) to an integer according to `mode` (or
+// the current RoundingMode, if `mode` is None) and return its magnitude, or
+// None if it exceeds the range of u128.
+// Pre-condition: 0 < divisor <= i128::MAX and rem < divisor
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn round_mag(
+    neg: bool,
+    quot: u128,
+    rem: u128,
+    divisor: u128,
+    mode: Option<RoundingMode>,
+) -> Option<u128> {
+    // The rounding modes only need to know the last digit of quot.
+    let last = (quot % 10) as i128;
+    let incr = rem != 0
+        && if neg {
+            round_quot(-last - 1, divisor - rem, divisor, mode) == -last - 1
+        } else {
+            round_quot(last, rem, divisor, mode) == last + 1
+        };
+    quot.checked_add(u128::from(incr))
+}
+
+// Round sign * (quot + rem / divisor) to an integer according to `mode` (or
+// the current RoundingMode, if `mode` is None) and return the result, or
+// None if it exceeds the range of coefficients.
+// Pre-condition: 0 < divisor <= i128::MAX and rem < divisor
+pub(crate) fn round_signed(
+    neg: bool,
+    quot: u128,
+    rem: u128,
+    divisor: u128,
+    mode: Option<RoundingMode>,
+) -> Option<i128> {
+    let mag =
+        i128::try_from(round_mag(neg, quot, rem, divisor, mode)?).ok()?;
+    Some(if neg { -mag } else { mag })
+}
+
+// Convert the magnitude of a coefficient to a signed coefficient, if
+// possible.
+#[inline]
+fn to_coeff(neg: bool, mag: u128) -> Result<i128, DecimalError> {
+    match i128::try_from(mag) {
+        Ok(mag) if neg => Ok(-mag),
+        Ok(mag) => Ok(mag),
+        Err(_) => Err(DecimalError::InternalOverflow),
+    }
+}
+
+// Return x * y + z, rounded to `n_frac_digits` according to `mode`, with the
+// intermediate result held in 256 bits.
+fn checked_mul_add_rounded(
+    x: Decimal,
+    y: Decimal,
+    z: Decimal,
+    n_frac_digits: u8,
+    mode: Option<RoundingMode>,
+) -> Result<Decimal, DecimalError> {
+    if n_frac_digits > MAX_N_FRAC_DIGITS {
+        return Err(DecimalError::MaxNFracDigitsExceeded);
+    }
+    // x * y + z = (x.coeff * y.coeff * 10^(t - s) + z.coeff * 10^(t - r))
+    //             / 10^t
+    let s = x.n_frac_digits + y.n_frac_digits;
+    let r = z.n_frac_digits;
+    let t = max(s, r);
+    let neg_xy = x.is_negative() != y.is_negative();
+    let (mut h, mut l) =
+        u128_mul_u128(x.coeff.unsigned_abs(), y.coeff.unsigned_abs());
+    if t > s {
+        // Overflow here implies that the result can not be represented,
+        // because |x.coeff * y.coeff| >= 2^256 / 10^(t - s) >= 2^196.
+        (h, l) = u256_checked_mul_u128(h, l, ten_pow(t - s) as u128)
+            .ok_or(DecimalError::InternalOverflow)?;
+    }
+    // t - r <= 36, so ten_pow is safe.
+    let (zh, zl) =
+        u128_mul_u128(z.coeff.unsigned_abs(), ten_pow(t - r) as u128);
+    let neg = if neg_xy == z.is_negative() {
+        (h, l) = u256_checked_add(h, l, zh, zl)
+            .ok_or(DecimalError::InternalOverflow)?;
+        neg_xy
+    } else {
+        match (h, l).cmp(&(zh, zl)) {
+            Ordering::Less => {
+                (h, l) = u256_sub(zh, zl, h, l);
+                z.is_negative()
+            }
+            _ => {
+                (h, l) = u256_sub(h, l, zh, zl);
+                neg_xy
+            }
+        }
+    };
+    if n_frac_digits >= t {
+        // no need for rounding
+        if h != 0 {
+            return Err(DecimalError::InternalOverflow);
+        }
+        return Ok(Decimal {
+            coeff: to_coeff(neg, l)?,
+            n_frac_digits: t,
+        });
+    }
+    // t - n_frac_digits <= 36, so ten_pow is safe.
+    let divisor = ten_pow(t - n_frac_digits) as u128;
+    let ((qh, ql), rem) = u256_div_rem_u128(h, l, divisor);
+    if qh != 0 {
+        return Err(DecimalError::InternalOverflow);
+    }
+    match round_signed(neg, ql, rem, divisor, mode) {
+        Some(coeff) => Ok(Decimal {
+            coeff,
+            n_frac_digits,
+        }),
+        None => Err(DecimalError::InternalOverflow),
+    }
+}
+
+// Return x * y / z, rounded to `n_frac_digits` according to `mode`, with the
+// intermediate result held in 256 bits.
+fn checked_mul_div_rounded(
+    x: Decimal,
+    y: Decimal,
+    z: Decimal,
+    n_frac_digits: u8,
+    mode: Option<RoundingMode>,
+) -> Result<Decimal, DecimalError> {
+    if n_frac_digits > MAX_N_FRAC_DIGITS {
+        return Err(DecimalError::MaxNFracDigitsExceeded);
+    }
+    if z.eq_zero() {
+        return Err(DecimalError::DivisionByZero);
+    }
+    // x * y / z * 10^n = x.coeff * y.coeff * 10^(r + n - s) / z.coeff
+    let s = x.n_frac_digits + y.n_frac_digits;
+    let r = z.n_frac_digits;
+    let neg = (x.is_negative() != y.is_negative()) != z.is_negative();
+    let (mut h, mut l) =
+        u128_mul_u128(x.coeff.unsigned_abs(), y.coeff.unsigned_abs());
+    let mut divisor = z.coeff.unsigned_abs();
+    if r + n_frac_digits >= s {
+        // Overflow here implies that the result can not be represented,
+        // because |x.coeff * y.coeff * 10^(r + n - s)| >= 2^256 and
+        // |z.coeff| < 2^127.
+        // r + n - s <= 36, so ten_pow is safe.
+        (h, l) = u256_checked_mul_u128(
+            h,
+            l,
+            ten_pow(r + n_frac_digits - s) as u128,
+        )
+        .ok_or(DecimalError::InternalOverflow)?;
+    } else {
+        // s - r - n <= 36, so ten_pow is safe.
+        let shift = ten_pow(s - r - n_frac_digits);
+        match z.coeff.unsigned_abs().checked_mul(shift as u128) {
+            Some(d) if d <= i128::MAX as u128 => divisor = d,
+            _ => {
+                // The divisor exceeds 127 bits, so the quotient is less
+                // than 2^127, but the division needs more than 256 bits.
+                let (quot, code) = div_rem_code(
+                    &UBig::from_u256(h, l),
+                    &UBig::from_u128(divisor).mul(&UBig::ten_pow(u32::from(
+                        s - r - n_frac_digits,
+                    ))),
+                );
+                let mag = round_mag_with_mode(neg, quot, code, mode)
+                    .to_u128()
+                    .ok_or(DecimalError::InternalOverflow)?;
+                return Ok(Decimal {
+                    coeff: to_coeff(neg, mag)?,
+                    n_frac_digits,
+                });
+            }
+        }
+    }
+    let ((qh, ql), rem) = u256_div_rem_u128(h, l, divisor);
+    if qh != 0 {
+        return Err(DecimalError::InternalOverflow);
+    }
+    match round_signed(neg, ql, rem, divisor, mode) {
+        Some(coeff) => Ok(Decimal {
+            coeff,
+            n_frac_digits,
+        }),
+        None => Err(DecimalError::InternalOverflow),
+    }
+}
+
+impl Decimal {
+    /// Returns `self` * `y` + `z`, rounded to `n_frac_digits` fractional
+    /// digits according to the current [RoundingMode].
+    ///
+    /// The product is not rounded before the addition, so the result is
+    /// rounded only once. If `n_frac_digits` is greater than the number of
+    /// fractional digits needed to represent the exact result, the result
+    /// is not rounded and keeps its number of fractional digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS] or the result
+    /// can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let price = Dec!(19.995);
+    /// let qty = Dec!(3);
+    /// let fee = Dec!(0.0125);
+    /// let r = price.mul_add_rounded(qty, fee, 2);
+    /// assert_eq!(r.to_string(), "60.00");
+    /// let r = price.mul_add_rounded(qty, fee, 6);
+    /// assert_eq!(r.to_string(), "59.9975");
+    /// ```
+    pub fn mul_add_rounded(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+    ) -> Self {
+        match checked_mul_add_rounded(self, y, z, n_frac_digits, None) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `self` * `y` + `z`, rounded once to `n_frac_digits`
+    /// fractional digits according to the given [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS] or the result
+    /// can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, RoundingMode};
+    /// let x = Dec!(1.25);
+    /// let y = Dec!(0.5);
+    /// let z = Dec!(-0.001);
+    /// let mode = RoundingMode::RoundHalfUp;
+    /// let r = x.mul_add_rounded_with_mode(y, z, 2, mode);
+    /// assert_eq!(r.to_string(), "0.62");
+    /// let mode = RoundingMode::RoundUp;
+    /// let r = x.mul_add_rounded_with_mode(y, z, 2, mode);
+    /// assert_eq!(r.to_string(), "0.63");
+    /// ```
+    pub fn mul_add_rounded_with_mode(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self {
+        match checked_mul_add_rounded(self, y, z, n_frac_digits, Some(mode)) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `self` * `y` + `z`, rounded once to `n_frac_digits`
+    /// fractional digits according to the current [RoundingMode], wrapped
+    /// in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let x = Decimal::MAX;
+    /// // the product exceeds the range of Decimal, but the result does not
+    /// let r = x.checked_mul_add_rounded(Dec!(2), -x, 0)?;
+    /// assert_eq!(r, Decimal::MAX);
+    /// let r = x.checked_mul_add_rounded(Dec!(2), x, 0);
+    /// assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_mul_add_rounded(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        checked_mul_add_rounded(self, y, z, n_frac_digits, None)
+    }
+
+    /// Returns `self` * `y` + `z`, rounded once to `n_frac_digits`
+    /// fractional digits according to the given [RoundingMode], wrapped in
+    /// `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    pub fn checked_mul_add_rounded_with_mode(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Result<Self, DecimalError> {
+        checked_mul_add_rounded(self, y, z, n_frac_digits, Some(mode))
+    }
+
+    /// Returns `self` * `y` / `z`, rounded to `n_frac_digits` fractional
+    /// digits according to the current [RoundingMode].
+    ///
+    /// The product is neither rounded nor limited to the range of `Decimal`
+    /// before the division, so the result is rounded only once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `z` equals zero, `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS] or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let price = Dec!(17.89);
+    /// let qty = Dec!(7);
+    /// let rate = Dec!(1.0825);
+    /// let r = price.mul_div_rounded(qty, rate, 2);
+    /// assert_eq!(r.to_string(), "115.69");
+    /// ```
+    pub fn mul_div_rounded(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+    ) -> Self {
+        match checked_mul_div_rounded(self, y, z, n_frac_digits, None) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `self` * `y` / `z`, rounded once to `n_frac_digits`
+    /// fractional digits according to the given [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `z` equals zero, `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS] or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, RoundingMode};
+    /// let x = Dec!(10);
+    /// let y = Dec!(0.25);
+    /// let z = Dec!(3);
+    /// let mode = RoundingMode::RoundDown;
+    /// let r = x.mul_div_rounded_with_mode(y, z, 3, mode);
+    /// assert_eq!(r.to_string(), "0.833");
+    /// let mode = RoundingMode::RoundUp;
+    /// let r = x.mul_div_rounded_with_mode(y, z, 3, mode);
+    /// assert_eq!(r.to_string(), "0.834");
+    /// ```
+    pub fn mul_div_rounded_with_mode(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self {
+        match checked_mul_div_rounded(self, y, z, n_frac_digits, Some(mode)) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `self` * `y` / `z`, rounded once to `n_frac_digits`
+    /// fractional digits according to the current [RoundingMode], wrapped
+    /// in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::DivisionByZero` if `z` equals zero,
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let x = Decimal::MAX;
+    /// // the product exceeds the range of Decimal, but the result does not
+    /// let r = x.checked_mul_div_rounded(Dec!(3), Dec!(4), 0)?;
+    /// assert_eq!(r.coefficient(), i128::MAX / 4 * 3 + 2);
+    /// let r = x.checked_mul_div_rounded(Dec!(3), Decimal::ZERO, 0);
+    /// assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_mul_div_rounded(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        checked_mul_div_rounded(self, y, z, n_frac_digits, None)
+    }
+
+    /// Returns `self` * `y` / `z`, rounded once to `n_frac_digits`
+    /// fractional digits according to the given [RoundingMode], wrapped in
+    /// `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::DivisionByZero` if `z` equals zero,
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    pub fn checked_mul_div_rounded_with_mode(
+        self,
+        y: Self,
+        z: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Result<Self, DecimalError> {
+        checked_mul_div_rounded(self, y, z, n_frac_digits, Some(mode))
+    }
+}
+
+#[cfg(test)]
+mod fused_ops_tests {
+    use super::*;
+    use crate::{Dec, MulRounded};
+
+    #[test]
+    fn test_mul_add_rounded() {
+        let x = Dec!(-0.2469);
+        let y = Dec!(0.5);
+        let z = Dec!(-0.00001);
+        let mode = RoundingMode::RoundHalfUp;
+        // exact: -0.12344
+        let r = x.mul_add_rounded_with_mode(y, -z, 4, mode);
+        assert_eq!(r, Dec!(-0.1234));
+        assert_eq!(r.n_frac_digits(), 4);
+        // rounding the product first gives a different result
+        let p = x.mul_rounded_with_mode(y, 4, mode);
+        assert_eq!(p - z, Dec!(-0.12349));
+        let r = x.mul_add_rounded_with_mode(y, z, 0, mode);
+        assert_eq!(r, Dec!(0));
+        let r = x.mul_add_rounded(y, z, 9);
+        assert_eq!(r, Dec!(-0.12346));
+        assert_eq!(r.n_frac_digits(), 5);
+        let r = x.mul_add_rounded(y, -x * y, 3);
+        assert_eq!(r, Decimal::ZERO);
+        let r = Dec!(0.5).mul_add_rounded(Dec!(3), Decimal::NEG_ONE, 0);
+        assert_eq!(r, Dec!(0));
+        let r = Dec!(0.5).mul_add_rounded(Dec!(-3), Decimal::ONE, 0);
+        assert_eq!(r, Dec!(0));
+        let r = Dec!(0.5).mul_add_rounded(Dec!(-3), Decimal::NEG_ONE, 0);
+        assert_eq!(r, Dec!(-2));
+    }
+
+    #[test]
+    fn test_mul_add_rounded_large_intermediate() {
+        let x = Decimal::new_raw(i128::MAX, 18);
+        let y = Decimal::new_raw(i128::MAX, 18);
+        let z = Decimal::new_raw(-i128::MAX, 0);
+        // x * y ~ 2.9e40, x * y + z ~ 2.9e40 - 1.7e38
+        let r = x.checked_mul_add_rounded(y, z, 0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let y = Decimal::new_raw(1, 18);
+        let z = Decimal::new_raw(-1, 18);
+        // exact: 170.141183460469231730687303715884105727
+        let r = x.mul_add_rounded(y, z, 18);
+        assert_eq!(r, Dec!(170.141183460469231731));
+        let r =
+            Decimal::MIN.checked_mul_add_rounded(Dec!(2), Decimal::MAX, 0);
+        assert_eq!(r.unwrap(), Decimal::MIN);
+        let z = Decimal::new_raw(1, 18);
+        let r = Decimal::MAX.checked_mul_add_rounded(Decimal::ONE, z, 18);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let r = Decimal::MAX.checked_mul_add_rounded(Decimal::ONE, z, 0);
+        assert_eq!(r.unwrap(), Decimal::MAX);
+    }
+
+    #[test]
+    fn test_mul_add_rounded_errors() {
+        let x = Dec!(1.5);
+        let r = x.checked_mul_add_rounded(x, x, 19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+        let mode = RoundingMode::RoundUp;
+        let r = Decimal::MAX.checked_mul_add_rounded_with_mode(
+            Decimal::ONE,
+            Dec!(0.5),
+            0,
+            mode,
+        );
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let mode = RoundingMode::RoundDown;
+        let r = Decimal::MAX.checked_mul_add_rounded_with_mode(
+            Decimal::ONE,
+            Dec!(0.5),
+            0,
+            mode,
+        );
+        assert_eq!(r.unwrap(), Decimal::MAX);
+    }
+
+    #[test]
+    fn test_mul_div_rounded() {
+        let x = Dec!(17.89);
+        let y = Dec!(7);
+        let z = Dec!(1.0825);
+        let mode = RoundingMode::RoundHalfEven;
+        // exact: 115.685912240184757505773672055427251732101616628...
+        let r = x.mul_div_rounded_with_mode(y, z, 2, mode);
+        assert_eq!(r, Dec!(115.69));
+        let r = x.mul_div_rounded_with_mode(y, z, 18, mode);
+        assert_eq!(r, Dec!(115.685912240184757506));
+        let r =
+            (-x).mul_div_rounded_with_mode(y, z, 0, RoundingMode::RoundUp);
+        assert_eq!(r, Dec!(-116));
+        let r =
+            x.mul_div_rounded_with_mode(y, -z, 0, RoundingMode::RoundFloor);
+        assert_eq!(r, Dec!(-116));
+        let r =
+            (-x).mul_div_rounded_with_mode(-y, z, 0, RoundingMode::RoundDown);
+        assert_eq!(r, Dec!(115));
+        let x = Dec!(0.125);
+        let y = Dec!(0.2);
+        let z = Dec!(0.01);
+        let r = x.mul_div_rounded_with_mode(y, z, 0, mode);
+        assert_eq!(r, Dec!(2));
+        let r = x.mul_div_rounded_with_mode(y, z, 1, mode);
+        assert_eq!(r, Dec!(2.5));
+        assert_eq!(r.n_frac_digits(), 1);
+    }
+
+    #[test]
+    fn test_mul_div_rounded_large_divisor() {
+        // the divisor is scaled to z.coeff * 10^36
+        let x = Decimal::new_raw(i128::MAX, 18);
+        let y = Decimal::new_raw(i128::MAX, 18);
+        let z = Decimal::new_raw(i128::MAX - 1, 0);
+        let r = x.mul_div_rounded(y, z, 0);
+        // exact: 170.141183460469231731687303715884105728...
+        assert_eq!(r, Dec!(170));
+        let r = x.mul_div_rounded(y, z, 18);
+        assert_eq!(r, Dec!(170.141183460469231732));
+        let x = Decimal::new_raw(25, 18);
+        let y = Decimal::new_raw(1, 18);
+        let z = Decimal::new_raw(i128::MAX >> 3, 0);
+        let r = x.mul_div_rounded(y, z, 0);
+        assert_eq!(r, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_mul_div_rounded_errors() {
+        let x = Dec!(1.5);
+        let r = x.checked_mul_div_rounded(x, x, 19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+        let r = x.checked_mul_div_rounded(x, Decimal::ZERO, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+        let r = Decimal::MAX.checked_mul_div_rounded(x, Dec!(1.5), 1);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let r = Decimal::MAX.checked_mul_div_rounded_with_mode(
+            Decimal::MAX,
+            Decimal::MAX,
+            0,
+            RoundingMode::RoundCeiling,
+        );
+        assert_eq!(r.unwrap(), Decimal::MAX);
+        let r = Decimal::MAX.checked_mul_div_rounded(Dec!(3), Dec!(2), 0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_div_rounded_zero_div() {
+        let _ = Dec!(1).mul_div_rounded(Dec!(1), Decimal::ZERO, 2);
+    }
+}
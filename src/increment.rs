@@ -0,0 +1,293 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::cmp::Ordering;
+
+use fpdec_core::{ten_pow, u128_mul_u128, u256_div_rem_u128, RoundingMode};
+
+use crate::{
+    decimal256::{u128_shifted_div_rem_code, u256_round_mag_with_mode},
+    fused_ops::round_mag,
+    Decimal, DecimalError,
+};
+
+impl Decimal {
+    /// Returns the integer multiple of `tick` nearest to `self` according
+    /// to `mode`, with exactly as many fractional digits as `tick`.
+    ///
+    /// Only the magnitude of `tick` is taken into account, so directional
+    /// modes like [RoundingMode::RoundFloor] always refer to the value of
+    /// `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, RoundingMode};
+    /// let d = Dec!(17.37);
+    /// let r = d.round_to_increment(Dec!(0.25), RoundingMode::RoundHalfUp);
+    /// assert_eq!(r.to_string(), "17.25");
+    /// let r = d.round_to_increment(Dec!(0.005), RoundingMode::RoundUp);
+    /// assert_eq!(r.to_string(), "17.370");
+    /// let r = d.round_to_increment(Dec!(5), RoundingMode::RoundHalfEven);
+    /// assert_eq!(r.to_string(), "15");
+    /// ```
+    pub fn round_to_increment(self, tick: Self, mode: RoundingMode) -> Self {
+        match self.checked_round_to_increment(tick, mode) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the integer multiple of `tick` nearest to `self` according
+    /// to `mode`, with exactly as many fractional digits as `tick`, wrapped
+    /// in `Result::Ok`.
+    ///
+    /// Only the magnitude of `tick` is taken into account, so directional
+    /// modes like [RoundingMode::RoundFloor] always refer to the value of
+    /// `self`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::DivisionByZero` if `tick` equals zero,
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError, RoundingMode};
+    /// # fn main() -> Result<(), DecimalError> {
+    /// let d = Dec!(-17.37);
+    /// let mode = RoundingMode::RoundFloor;
+    /// let r = d.checked_round_to_increment(Dec!(0.25), mode)?;
+    /// assert_eq!(r.to_string(), "-17.50");
+    /// let r = d.checked_round_to_increment(Decimal::ZERO, mode);
+    /// assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+    /// # Ok(()) }
+    /// ```
+    pub fn checked_round_to_increment(
+        self,
+        tick: Self,
+        mode: RoundingMode,
+    ) -> Result<Self, DecimalError> {
+        if tick.eq_zero() {
+            return Err(DecimalError::DivisionByZero);
+        }
+        let tick_mag = tick.coeff.unsigned_abs();
+        let mag = self.coeff.unsigned_abs();
+        let neg = self.coeff < 0;
+        // self / |tick| = x / y
+        let n_ticks = match self.n_frac_digits.cmp(&tick.n_frac_digits) {
+            Ordering::Less => {
+                // shift <= 18, so ten_pow is safe.
+                let shift = tick.n_frac_digits - self.n_frac_digits;
+                let (h, l) = u128_mul_u128(mag, ten_pow(shift) as u128);
+                let ((qh, ql), rem) = u256_div_rem_u128(h, l, tick_mag);
+                if qh != 0 {
+                    return Err(DecimalError::InternalOverflow);
+                }
+                round_mag(neg, ql, rem, tick_mag, Some(mode))
+            }
+            _ => {
+                // shift <= 18, so ten_pow is safe.
+                let shift = self.n_frac_digits - tick.n_frac_digits;
+                match tick_mag.checked_mul(ten_pow(shift) as u128) {
+                    Some(y) if y <= i128::MAX as u128 => {
+                        let ((_, quot), rem) = u256_div_rem_u128(0, mag, y);
+                        round_mag(neg, quot, rem, y, Some(mode))
+                    }
+                    _ => {
+                        // The divisor exceeds 127 bits, so the quotient is
+                        // derived from mag / |tick| instead.
+                        let (quot, code) = u128_shifted_div_rem_code(
+                            mag,
+                            tick_mag,
+                            -i32::from(shift),
+                        );
+                        match u256_round_mag_with_mode(
+                            neg,
+                            quot,
+                            code,
+                            Some(mode),
+                        ) {
+                            Some((0, n_ticks)) => Some(n_ticks),
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        };
+        match n_ticks
+            .and_then(|n_ticks| n_ticks.checked_mul(tick_mag))
+            .and_then(|mag| i128::try_from(mag).ok())
+        {
+            Some(mag) => Ok(Self {
+                coeff: if neg { -mag } else { mag },
+                n_frac_digits: tick.n_frac_digits,
+            }),
+            None => Err(DecimalError::InternalOverflow),
+        }
+    }
+
+    /// Returns the largest integer multiple of `tick` less than or equal to
+    /// `self`, with exactly as many fractional digits as `tick`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let bid = Dec!(101.3789);
+    /// assert_eq!(bid.floor_to_increment(Dec!(0.25)).to_string(), "101.25");
+    /// let r = (-bid).floor_to_increment(Dec!(0.005));
+    /// assert_eq!(r.to_string(), "-101.380");
+    /// ```
+    pub fn floor_to_increment(self, tick: Self) -> Self {
+        self.round_to_increment(tick, RoundingMode::RoundFloor)
+    }
+
+    /// Returns the smallest integer multiple of `tick` greater than or equal
+    /// to `self`, with exactly as many fractional digits as `tick`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick` equals zero or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let ask = Dec!(101.3789);
+    /// assert_eq!(ask.ceil_to_increment(Dec!(0.25)).to_string(), "101.50");
+    /// let r = (-ask).ceil_to_increment(Dec!(0.005));
+    /// assert_eq!(r.to_string(), "-101.375");
+    /// ```
+    pub fn ceil_to_increment(self, tick: Self) -> Self {
+        self.round_to_increment(tick, RoundingMode::RoundCeiling)
+    }
+}
+
+#[cfg(test)]
+mod round_to_increment_tests {
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_round_to_increment() {
+        let tick = Dec!(0.25);
+        let d = Dec!(17.125);
+        let r = d.round_to_increment(tick, RoundingMode::RoundHalfEven);
+        assert_eq!(r, Dec!(17.00));
+        assert_eq!(r.n_frac_digits(), 2);
+        let r = d.round_to_increment(tick, RoundingMode::RoundHalfUp);
+        assert_eq!(r, Dec!(17.25));
+        let r = (-d).round_to_increment(tick, RoundingMode::RoundHalfDown);
+        assert_eq!(r, Dec!(-17.00));
+        let r = d.round_to_increment(-tick, RoundingMode::RoundHalfUp);
+        assert_eq!(r, Dec!(17.25));
+        let tick = Dec!(0.005);
+        let d = Dec!(3);
+        let r = d.round_to_increment(tick, RoundingMode::RoundUp);
+        assert_eq!(r.coefficient(), 3000);
+        assert_eq!(r.n_frac_digits(), 3);
+        let tick = Dec!(50);
+        let d = Dec!(-1234.5678);
+        let r = d.round_to_increment(tick, RoundingMode::RoundDown);
+        assert_eq!(r.coefficient(), -1200);
+        assert_eq!(r.n_frac_digits(), 0);
+        let r = Decimal::ZERO.round_to_increment(tick, RoundingMode::RoundUp);
+        assert_eq!(r, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_floor_ceil_to_increment() {
+        let tick = Dec!(0.25);
+        for (d, floor, ceil) in [
+            (Dec!(1.3789), Dec!(1.25), Dec!(1.5)),
+            (Dec!(-1.3789), Dec!(-1.5), Dec!(-1.25)),
+            (Dec!(1.5), Dec!(1.5), Dec!(1.5)),
+            (Dec!(-0.1), Dec!(-0.25), Dec!(0)),
+        ] {
+            assert_eq!(d.floor_to_increment(tick), floor);
+            assert_eq!(d.ceil_to_increment(tick), ceil);
+            assert_eq!(d.floor_to_increment(tick).n_frac_digits(), 2);
+        }
+    }
+
+    #[test]
+    fn test_large_tick() {
+        let tick = Decimal::MAX;
+        let d = Dec!(1);
+        let r = d.round_to_increment(tick, RoundingMode::RoundDown);
+        assert_eq!(r, Decimal::ZERO);
+        let r = d.round_to_increment(tick, RoundingMode::RoundUp);
+        assert_eq!(r, Decimal::MAX);
+        let r = (-d).floor_to_increment(tick);
+        assert_eq!(r, Decimal::MIN);
+        let tick = Decimal::new_raw(1, 18);
+        let r = Decimal::MAX
+            .checked_round_to_increment(tick, RoundingMode::RoundHalfEven);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    fn test_large_scaled_tick() {
+        // |tick| * 10^18 exceeds i128::MAX
+        let tick = Decimal::new_raw(300_000_000_000_000_000_000, 0);
+        let half = 150_000_000_000_000_000_000_000_000_000_000_000_000;
+        let d = Decimal::new_raw(half, 18);
+        let r = d.round_to_increment(tick, RoundingMode::RoundHalfEven);
+        assert_eq!(r, Decimal::ZERO);
+        let r = d.round_to_increment(tick, RoundingMode::RoundHalfUp);
+        assert_eq!(r, tick);
+        let d = Decimal::new_raw(half + 1, 18);
+        let r = d.round_to_increment(tick, RoundingMode::RoundHalfEven);
+        assert_eq!(r, tick);
+        let d = Decimal::new_raw(-half + 1, 18);
+        let r = d.round_to_increment(tick, RoundingMode::RoundHalfDown);
+        assert_eq!(r, Decimal::ZERO);
+        let d = Decimal::new_raw(1, 18);
+        let r = d.round_to_increment(tick, RoundingMode::RoundUp);
+        assert_eq!(r, tick);
+        let r = (-d).round_to_increment(tick, RoundingMode::RoundCeiling);
+        assert_eq!(r, Decimal::ZERO);
+        let r = (-d).floor_to_increment(tick);
+        assert_eq!(r, -tick);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let tick = Dec!(7);
+        let d = Decimal::MAX;
+        let r = d.checked_round_to_increment(tick, RoundingMode::RoundUp);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let r = d.checked_round_to_increment(tick, RoundingMode::RoundDown);
+        assert_eq!(r.unwrap().coefficient(), i128::MAX - 1);
+        // the result would need more fractional digits than available
+        let r =
+            d.checked_round_to_increment(Dec!(0.5), RoundingMode::RoundDown);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_tick() {
+        let _ = Dec!(1).ceil_to_increment(Decimal::ZERO);
+    }
+}
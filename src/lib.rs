@@ -66,33 +66,75 @@ pub use binops::{
     checked_add_sub::CheckedAdd, checked_add_sub::CheckedSub,
     checked_div::CheckedDiv, checked_mul::CheckedMul,
     checked_rem::CheckedRem, div_rounded::DivRounded,
-    mul_rounded::MulRounded,
+    mul_rounded::MulRounded, overflowing::OverflowingAdd,
+    overflowing::OverflowingMul, overflowing::OverflowingSub,
+    saturating::SaturatingAdd, saturating::SaturatingMul,
+    saturating::SaturatingSub, sum_product::CheckedProduct,
+    sum_product::CheckedSum,
 };
 #[doc(inline)]
+pub use cash_rounding::{CashRounded, CashRounding};
+#[doc(inline)]
+pub use context::{Context, Signal, Signals};
+#[doc(inline)]
+pub use decimal256::Decimal256;
+#[doc(inline)]
+pub use decimal_format::{DecimalFormat, Grouping, SignStyle};
+#[doc(inline)]
+pub use decimal_parser::DecimalParser;
+#[doc(inline)]
 pub use errors::*;
+#[cfg(feature = "rkyv")]
+#[doc(inline)]
+pub use fixed_decimal::ArchivedFixedDecimal;
+#[doc(inline)]
+pub use fixed_decimal::FixedDecimal;
 use fpdec_core::i128_magnitude;
 #[doc(inline)]
 pub use fpdec_core::{
-    ParseDecimalError, Round, RoundingMode, MAX_N_FRAC_DIGITS,
+    ParseDecimalError, RandomSource, Round, RoundingMode, MAX_N_FRAC_DIGITS,
 };
 #[doc(inline)]
+pub use fpdec_core::{with_rounding_mode, RoundingModeGuard};
+#[doc(inline)]
 pub use fpdec_macros::Dec;
 #[doc(inline)]
 pub use quantize::Quantize;
+#[doc(inline)]
+pub use small_decimal::{Decimal32, Decimal64};
+#[doc(inline)]
+pub use udecimal::UDecimal;
 
+mod allocate;
 mod as_integer_ratio;
+mod bigint;
 mod binops;
+mod cash_rounding;
+mod context;
+mod decimal256;
+mod decimal_format;
+mod decimal_parser;
+mod div_mod;
 mod errors;
+mod exp_log;
+mod fixed_decimal;
 mod format;
 mod from_float;
 mod from_int;
 mod from_str;
+mod fused_ops;
+mod increment;
 mod into_float;
 mod into_int;
 #[cfg(feature = "num-traits")]
 mod num_traits;
+mod pow;
 mod quantize;
+mod roots;
 mod round;
+mod scale;
+mod small_decimal;
+mod udecimal;
 mod unops;
 
 /// Represents a decimal number as a coefficient (`i128`) combined with a
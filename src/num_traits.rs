@@ -69,7 +69,10 @@ mod one_tests {
 impl Num for Decimal {
     type FromStrRadixErr = <Self as FromStr>::Err;
 
-    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+    fn from_str_radix(
+        str: &str,
+        radix: u32,
+    ) -> Result<Self, Self::FromStrRadixErr> {
         if radix != 10 {
             return Err(ParseDecimalError::Invalid);
         }
@@ -0,0 +1,534 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use fpdec_core::{ten_pow, MAX_N_FRAC_DIGITS};
+
+use crate::{
+    bigint::UBig,
+    exp_log::{ln, round_exp, round_parts},
+    Decimal, DecimalError,
+};
+
+// Limit (in bits) for the size of the exact intermediate results of
+// `powi`; beyond it the power is calculated as e^(exp * ln(|self|)).
+const MAX_EXACT_BITS: u32 = 4096;
+
+// Return the coefficient of num / den, rounded to an int according to the
+// current RoundingMode and negated if `neg` is true.
+fn round_ratio(neg: bool, num: &UBig, den: &UBig) -> Option<i128> {
+    let (quot, rem) = num.div_rem(den);
+    let code = if rem.is_zero() {
+        0
+    } else {
+        match rem.shl(1).cmp(den) {
+            core::cmp::Ordering::Less => 1,
+            core::cmp::Ordering::Equal => 2,
+            core::cmp::Ordering::Greater => 3,
+        }
+    };
+    round_parts(neg, &quot, code)
+}
+
+// Return the coefficient of (-1)^neg * e^(y * ln(x)), where
+// x = x_coeff / 10^x_n_frac_digits and y = y_coeff / 10^y_n_frac_digits,
+// rounded to `n_frac_digits` fractional digits according to the current
+// RoundingMode.
+#[allow(clippy::cast_possible_truncation)]
+fn round_pow(
+    x_coeff: u128,
+    x_n_frac_digits: u8,
+    y_coeff: i128,
+    y_n_frac_digits: u8,
+    neg: bool,
+    n_frac_digits: u8,
+) -> Result<i128, DecimalError> {
+    // The additional bits compensate the magnification of the error of
+    // ln(x) by y_coeff.
+    let extra = 136 - y_coeff.unsigned_abs().leading_zeros();
+    round_exp(neg, n_frac_digits, |prec| {
+        let y_ln_x = ln(x_coeff, x_n_frac_digits, prec + extra)
+            .mul_int(y_coeff)
+            .shr(extra)
+            .div_u64(ten_pow(y_n_frac_digits) as u64);
+        (y_ln_x, 3)
+    })
+}
+
+impl Decimal {
+    /// Returns `self` raised to the power of `exp`, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// The power is calculated by exponentiation by squaring with a wide
+    /// intermediate result, so that it is rounded only once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` equals zero and `exp` is negative, `n_frac_digits`
+    /// exceeds [MAX_N_FRAC_DIGITS] or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(1.5);
+    /// let r = d.powi(3, 2);
+    /// assert_eq!(r.to_string(), "3.38");
+    /// let d = Dec!(-2);
+    /// let r = d.powi(-3, 4);
+    /// assert_eq!(r.to_string(), "-0.1250");
+    /// ```
+    pub fn powi(self, exp: i32, n_frac_digits: u8) -> Self {
+        match self.checked_powi(exp, n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `self` raised to the power of `exp`, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::DivisionByZero` if `self` equals zero and `exp` is
+    ///   negative,
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(1.01);
+    /// let r = d.checked_powi(100, 6)?;
+    /// assert_eq!(r.to_string(), "2.704814");
+    /// let r = Decimal::ZERO.checked_powi(-1, 2);
+    /// assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+    /// let r = Dec!(10).checked_powi(40, 0);
+    /// assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_powi(
+        self,
+        exp: i32,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(DecimalError::MaxNFracDigitsExceeded);
+        }
+        if self.eq_zero() {
+            return match exp {
+                0 => Ok(Self {
+                    coeff: ten_pow(n_frac_digits),
+                    n_frac_digits,
+                }),
+                e if e < 0 => Err(DecimalError::DivisionByZero),
+                _ => Ok(Self {
+                    coeff: 0,
+                    n_frac_digits,
+                }),
+            };
+        }
+        let neg = self.is_negative() && exp & 1 != 0;
+        let x = self.coeff.unsigned_abs();
+        let p = u32::from(self.n_frac_digits);
+        let e = exp.unsigned_abs();
+        let n_bits = 128 - x.leading_zeros() + 4 * p;
+        let coeff = if u64::from(n_bits) * u64::from(e)
+            <= u64::from(MAX_EXACT_BITS)
+        {
+            // x^e / 10^(p * e) * 10^n
+            // resp. 10^(p * e) / x^e * 10^n
+            let pow = UBig::from_u128(x).pow(e);
+            let scale = UBig::ten_pow(p * e);
+            let shift = UBig::ten_pow(u32::from(n_frac_digits));
+            let res = if exp >= 0 {
+                round_ratio(neg, &pow.mul(&shift), &scale)
+            } else {
+                round_ratio(neg, &scale.mul(&shift), &pow)
+            };
+            res.ok_or(DecimalError::InternalOverflow)?
+        } else if self.coeff.unsigned_abs()
+            == ten_pow(self.n_frac_digits) as u128
+        {
+            // |self| = 1
+            let one = ten_pow(n_frac_digits);
+            if neg {
+                -one
+            } else {
+                one
+            }
+        } else {
+            round_pow(
+                x,
+                self.n_frac_digits,
+                i128::from(exp),
+                0,
+                neg,
+                n_frac_digits,
+            )?
+        };
+        Ok(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+
+    /// Returns `self` raised to the power of `exp`, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// If `exp` is an int fitting into an `i32`, the result is the same as
+    /// that of [powi](Decimal::powi). Otherwise it is calculated as
+    /// e^(exp * ln(self)) and correctly rounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is negative and `exp` is not an int, `self` equals
+    /// zero and `exp` is negative, `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS] or the result can not be represented by
+    /// `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(2);
+    /// let r = d.pow_rounded(Dec!(0.5), 9);
+    /// assert_eq!(r.to_string(), "1.414213562");
+    /// let d = Dec!(1.5);
+    /// let r = d.pow_rounded(Dec!(-2.5), 6);
+    /// assert_eq!(r.to_string(), "0.362887");
+    /// ```
+    pub fn pow_rounded(self, exp: Self, n_frac_digits: u8) -> Self {
+        match self.checked_pow_rounded(exp, n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns `self` raised to the power of `exp`, rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::NotANumber` if `self` is negative and `exp` is not
+    ///   an int,
+    /// * `DecimalError::DivisionByZero` if `self` equals zero and `exp` is
+    ///   negative,
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(0.25);
+    /// let r = d.checked_pow_rounded(Dec!(1.5), 4)?;
+    /// assert_eq!(r.to_string(), "0.1250");
+    /// let r = Dec!(-8).checked_pow_rounded(Dec!(0.5), 4);
+    /// assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    #[allow(clippy::integer_division)]
+    pub fn checked_pow_rounded(
+        self,
+        exp: Self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(DecimalError::MaxNFracDigitsExceeded);
+        }
+        let is_int = exp.fract().eq_zero();
+        if is_int {
+            if let Ok(exp) = i32::try_from(exp.trunc().coeff) {
+                return self.checked_powi(exp, n_frac_digits);
+            }
+        } else if self.is_negative() {
+            return Err(DecimalError::NotANumber);
+        }
+        if self.eq_zero() {
+            return if exp.is_negative() {
+                Err(DecimalError::DivisionByZero)
+            } else {
+                Ok(Self {
+                    coeff: 0,
+                    n_frac_digits,
+                })
+            };
+        }
+        // here: exp is either not an int or beyond the range of i32
+        let neg = self.is_negative() && exp.trunc().coeff & 1 != 0;
+        let coeff = if self.coeff.unsigned_abs()
+            == ten_pow(self.n_frac_digits) as u128
+        {
+            // |self| = 1
+            let one = ten_pow(n_frac_digits);
+            if neg {
+                -one
+            } else {
+                one
+            }
+        } else {
+            round_pow(
+                self.coeff.unsigned_abs(),
+                self.n_frac_digits,
+                exp.coeff,
+                exp.n_frac_digits,
+                neg,
+                n_frac_digits,
+            )?
+        };
+        Ok(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod powi_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::RoundingMode;
+
+    #[test]
+    fn test_powi_exact() {
+        let d = Decimal::new_raw(15, 1);
+        let r = d.powi(3, 3);
+        assert_eq!(r.coefficient(), 3375);
+        assert_eq!(r.n_frac_digits(), 3);
+        let d = Decimal::new_raw(-2, 0);
+        let r = d.powi(125, 0);
+        assert_eq!(r.coefficient(), -(1_i128 << 125));
+        let d = Decimal::new_raw(7, 0);
+        let r = d.powi(0, 2);
+        assert_eq!(r.coefficient(), 100);
+        let d = Decimal::new_raw(-5, 1);
+        let r = d.powi(-3, 1);
+        assert_eq!(r.coefficient(), -80);
+    }
+
+    #[test]
+    fn test_powi_inexact() {
+        let d = Decimal::new_raw(101, 2);
+        let r = d.powi(100, 18);
+        assert_eq!(r.coefficient(), 2704813829421526093);
+        let d = Decimal::new_raw(3, 0);
+        let r = d.powi(-1, 18);
+        assert_eq!(r.coefficient(), 333333333333333333);
+        let d = Decimal::new_raw(-7, 1);
+        let r = d.powi(-5, 10);
+        assert_eq!(r.coefficient(), -59499018266);
+        let d = Decimal::new_raw(123456789, 8);
+        let r = d.powi(-17, 18);
+        assert_eq!(r.coefficient(), 27812843247015888);
+    }
+
+    #[test]
+    fn test_powi_tie() {
+        // 0.5^3 = 0.125
+        let d = Decimal::new_raw(5, 1);
+        let r = d.powi(3, 2);
+        assert_eq!(r.coefficient(), 12);
+        let d = Decimal::new_raw(-15, 1);
+        let r = d.powi(3, 2);
+        assert_eq!(r.coefficient(), -338);
+    }
+
+    #[test]
+    fn test_powi_large_exp() {
+        let d = Decimal::new_raw(1000001, 6);
+        let r = d.powi(1000000, 18);
+        assert_eq!(r.coefficient(), 2718280469319376884);
+        let r = d.powi(-1000000, 18);
+        assert_eq!(r.coefficient(), 367879625111086266);
+        let d = Decimal::new_raw(-1, 0);
+        let r = d.powi(i32::MAX, 3);
+        assert_eq!(r.coefficient(), -1000);
+        let d = Decimal::new_raw(5, 1);
+        let r = d.powi(i32::MAX, 18);
+        assert_eq!(r.coefficient(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_powi_rounding_mode() {
+        let d = Decimal::new_raw(5, 1);
+        RoundingMode::set_default(RoundingMode::RoundUp);
+        let r = d.powi(i32::MAX, 18);
+        let s = d.powi(3, 2);
+        RoundingMode::set_default(RoundingMode::RoundHalfEven);
+        assert_eq!(r.coefficient(), 1);
+        assert_eq!(s.coefficient(), 13);
+    }
+
+    #[test]
+    fn test_powi_zero() {
+        let d = Decimal::ZERO;
+        assert_eq!(d.powi(0, 2), Decimal::ONE);
+        assert_eq!(d.powi(5, 2), Decimal::ZERO);
+        let r = Decimal::new_raw(50, 2).powi(0, 4);
+        assert_eq!(r.coefficient(), 10000);
+        assert_eq!(r.n_frac_digits(), 4);
+        let d = Decimal::new_raw(0, 2);
+        let r = d.powi(0, 4);
+        assert_eq!(r.coefficient(), 10000);
+        assert_eq!(r.n_frac_digits(), 4);
+        let r = d.powi(3, 4);
+        assert_eq!(r.coefficient(), 0);
+        assert_eq!(r.n_frac_digits(), 4);
+        let r = d.checked_powi(-2, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+    }
+
+    #[test]
+    fn test_checked_powi_err() {
+        let d = Decimal::new_raw(2, 0);
+        let r = d.checked_powi(127, 0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let r = d.checked_powi(100, 18);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let d = Decimal::new_raw(1000001, 6);
+        let r = d.checked_powi(100000000, 0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+        let r = d.checked_powi(2, 19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+    }
+
+    #[test]
+    fn test_checked_powi_limits() {
+        assert_eq!(Decimal::MAX.checked_powi(1, 0), Ok(Decimal::MAX));
+        assert_eq!(Decimal::MIN.checked_powi(1, 0), Ok(Decimal::MIN));
+        assert_eq!(
+            Decimal::MIN.checked_powi(1, 0).unwrap().coefficient(),
+            -i128::MAX
+        );
+        let d = Decimal::new_raw(i128::MAX, 1);
+        assert_eq!(d.checked_powi(1, 1), Ok(d));
+        assert_eq!(
+            d.checked_powi(1, 2).unwrap_err(),
+            DecimalError::InternalOverflow
+        );
+        assert_eq!(Decimal::MAX.checked_powi(-1, 0).unwrap(), Decimal::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_powi_div_by_zero() {
+        let _ = Decimal::ZERO.powi(-1, 2);
+    }
+}
+
+#[cfg(test)]
+mod pow_rounded_tests {
+    use super::*;
+
+    #[test]
+    fn test_pow_rounded_int_exp() {
+        let d = Decimal::new_raw(15, 1);
+        let e = Decimal::new_raw(300, 2);
+        assert_eq!(d.pow_rounded(e, 3), d.powi(3, 3));
+        let e = Decimal::new_raw(-4, 0);
+        assert_eq!(d.pow_rounded(e, 18), d.powi(-4, 18));
+    }
+
+    #[test]
+    fn test_pow_rounded_inexact() {
+        let d = Decimal::TWO;
+        let e = Decimal::new_raw(5, 1);
+        let r = d.pow_rounded(e, 18);
+        assert_eq!(r.coefficient(), 1414213562373095049);
+        let d = Decimal::new_raw(15, 1);
+        let e = Decimal::new_raw(-25, 1);
+        let r = d.pow_rounded(e, 18);
+        assert_eq!(r.coefficient(), 362887369301211570);
+        let d = Decimal::new_raw(123456789, 3);
+        let e = Decimal::new_raw(314159, 5);
+        let r = d.pow_rounded(e, 3);
+        assert_eq!(r.coefficient(), 9895837090196686844);
+        let d = Decimal::new_raw(7, 18);
+        let e = Decimal::new_raw(1, 2);
+        let r = d.pow_rounded(e, 18);
+        assert_eq!(r.coefficient(), 673675852173110223);
+    }
+
+    #[test]
+    fn test_pow_rounded_exact() {
+        let d = Decimal::new_raw(25, 2);
+        let e = Decimal::new_raw(15, 1);
+        let r = d.pow_rounded(e, 4);
+        assert_eq!(r.coefficient(), 1250);
+        // 0.25^1.5 = 0.125 => tie when rounded to 2 digits
+        let r = d.pow_rounded(e, 2);
+        assert_eq!(r.coefficient(), 12);
+        let d = Decimal::new_raw(4, 0);
+        let e = Decimal::new_raw(-5, 1);
+        let r = d.pow_rounded(e, 0);
+        assert_eq!(r.coefficient(), 0);
+    }
+
+    #[test]
+    fn test_pow_rounded_large_exp() {
+        let d = Decimal::new_raw(-1, 0);
+        let e = Decimal::new_raw(1_i128 << 100, 0);
+        let r = d.pow_rounded(e, 2);
+        assert_eq!(r.coefficient(), 100);
+        let d = Decimal::new_raw(1, 18);
+        let e = Decimal::new_raw(-1_i128 << 100, 15);
+        let r = d.checked_pow_rounded(e, 0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    fn test_checked_pow_rounded_err() {
+        let d = Decimal::new_raw(-8, 0);
+        let e = Decimal::new_raw(1, 1);
+        let r = d.checked_pow_rounded(e, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let d = Decimal::ZERO;
+        let e = Decimal::new_raw(-1, 1);
+        let r = d.checked_pow_rounded(e, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::DivisionByZero);
+        let e = Decimal::new_raw(1, 1);
+        let r = d.pow_rounded(e, 2);
+        assert_eq!(r, Decimal::ZERO);
+        assert_eq!(r.n_frac_digits(), 2);
+        let d = Decimal::new_raw(10, 0);
+        let e = Decimal::new_raw(385, 1);
+        let r = d.checked_pow_rounded(e, 0);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    fn test_checked_pow_rounded_limits() {
+        assert_eq!(
+            Decimal::MAX.checked_pow_rounded(Decimal::ONE, 0),
+            Ok(Decimal::MAX)
+        );
+        assert_eq!(
+            Decimal::MIN.checked_pow_rounded(Decimal::ONE, 0),
+            Ok(Decimal::MIN)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pow_rounded_nan() {
+        let d = Decimal::new_raw(-2, 0);
+        let _ = d.pow_rounded(Decimal::new_raw(5, 1), 2);
+    }
+}
@@ -9,7 +9,7 @@
 
 use core::ops::Mul;
 
-use crate::DivRounded;
+use crate::{with_rounding_mode, DivRounded, RoundingMode};
 
 /// Rounding a number to the nearest integer multiple of a given quantum.
 pub trait Quantize<Rhs = Self> {
@@ -42,6 +42,44 @@ pub trait Quantize<Rhs = Self> {
     /// assert_eq!(r.to_string(), "28.25");
     /// ```
     fn quantize(self, quant: Rhs) -> Self::Output;
+
+    /// Returns an instance of `Self::Output` with its value set to the
+    /// integer multiple of `quant` nearest to `self`, according to `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quant` equals zero or the resulting value can not be
+    /// represented by `Self::Output`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Quantize, RoundingMode};
+    /// let d = Dec!(28.27093);
+    /// let q = Dec!(0.05);
+    /// let r = d.quantize_with_mode(q, RoundingMode::RoundUp);
+    /// assert_eq!(r.to_string(), "28.30");
+    /// let r = d.quantize_with_mode(q, RoundingMode::RoundDown);
+    /// assert_eq!(r.to_string(), "28.25");
+    /// ```
+    ///
+    /// The default implementation calls [Quantize::quantize] with the
+    /// default RoundingMode temporarily set to `mode` (see
+    /// [with_rounding_mode]). Without feature `std` that mode is set
+    /// globally for the duration of the call, so it then also applies to
+    /// other threads and interrupt handlers; implementors used in such a
+    /// context should override this method.
+    #[inline]
+    fn quantize_with_mode(
+        self,
+        quant: Rhs,
+        mode: RoundingMode,
+    ) -> Self::Output
+    where
+        Self: Sized,
+    {
+        with_rounding_mode(mode, || self.quantize(quant))
+    }
 }
 
 impl<T, Q> Quantize<Q> for T
@@ -56,6 +94,15 @@ where
     fn quantize(self, quant: Q) -> Self::Output {
         self.div_rounded(quant, 0) * quant
     }
+
+    #[inline(always)]
+    fn quantize_with_mode(
+        self,
+        quant: Q,
+        mode: RoundingMode,
+    ) -> Self::Output {
+        self.div_rounded_with_mode(quant, 0, mode) * quant
+    }
 }
 
 #[cfg(test)]
@@ -115,4 +162,21 @@ mod div_rounded_int_by_int_tests {
         assert_eq!(q.coefficient(), (&x).quantize(y).coefficient());
         assert_eq!(q.coefficient(), (&x).quantize(&y).coefficient());
     }
+
+    #[test]
+    fn test_quantize_with_mode() {
+        let x = Decimal::new_raw(4375, 2);
+        let y = Decimal::new_raw(125, 1);
+        let q = x.quantize_with_mode(y, RoundingMode::RoundHalfDown);
+        assert_eq!(q.coefficient(), 375);
+        let q = x.quantize_with_mode(y, RoundingMode::RoundHalfUp);
+        assert_eq!(q.coefficient(), 500);
+        let i = -322_i32;
+        let q = i.quantize_with_mode(3_i32, RoundingMode::RoundFloor);
+        assert_eq!(q.coefficient(), -324);
+        assert_eq!(
+            q,
+            (&i).quantize_with_mode(&3_i32, RoundingMode::RoundFloor)
+        );
+    }
 }
@@ -0,0 +1,518 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::cmp::{max, Ordering};
+
+use fpdec_core::{
+    checked_mul_pow_ten, i128_div_rounded, ten_pow, u128_mul_u128,
+    u256_isqrt, MAX_N_FRAC_DIGITS,
+};
+
+use crate::{Decimal, DecimalError};
+
+// x * 10^(n * (MAX_N_FRAC_DIGITS + 1)) < 2^127 * 2^16095 < 2^(127 * 128) for
+// any x < 2^127 and n <= 255, so that 128 limbs are enough to hold the
+// radicand as well as any power compared to it.
+const N_LIMBS: usize = 128;
+
+// Unsigned int composed of up to N_LIMBS u128 limbs (little endian), used to
+// compare r^n with a scaled radicand.
+struct Limbs {
+    buf: [u128; N_LIMBS],
+    len: usize,
+}
+
+impl Limbs {
+    const fn new(x: u128) -> Self {
+        let mut buf = [0_u128; N_LIMBS];
+        buf[0] = x;
+        Self { buf, len: 1 }
+    }
+
+    // Return x * 10^shift.
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_scaled(x: u128, mut shift: u32) -> Self {
+        let mut res = Self::new(x);
+        while shift > 38 {
+            res.mul_u128(ten_pow(38) as u128);
+            shift -= 38;
+        }
+        res.mul_u128(ten_pow(shift as u8) as u128);
+        res
+    }
+
+    // self <- self * y
+    fn mul_u128(&mut self, y: u128) {
+        let mut carry = 0_u128;
+        for limb in &mut self.buf[..self.len] {
+            let (hi, lo) = u128_mul_u128(*limb, y);
+            let (lo, ovl) = lo.overflowing_add(carry);
+            *limb = lo;
+            // hi <= 2^128 - 2, so this can't overflow
+            carry = hi + u128::from(ovl);
+        }
+        if carry != 0 {
+            self.buf[self.len] = carry;
+            self.len += 1;
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn n_bits(&self) -> u32 {
+        let top = self.buf[self.len - 1];
+        (self.len as u32) * 128 - top.leading_zeros()
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.len.cmp(&other.len) {
+            Ordering::Equal => self.buf[..self.len]
+                .iter()
+                .rev()
+                .cmp(other.buf[..other.len].iter().rev()),
+            ord => ord,
+        }
+    }
+
+    // Compare r^n with self.
+    fn cmp_pow(&self, r: u128, n: u8) -> Ordering {
+        let mut p = Self::new(r);
+        for _ in 1..n {
+            p.mul_u128(r);
+            if p.len > self.len {
+                return Ordering::Greater;
+            }
+        }
+        p.cmp(self)
+    }
+
+    // Return Some((r, e)) with r = floor(self^(1/n)) and e = true if r^n =
+    // self, otherwise e = false, or None if r >= 2^127.
+    // Pre-condition: self != 0 and n > 0
+    fn iroot(&self, n: u8) -> Option<(u128, bool)> {
+        // 2^(n_bits - 1) <= self < 2^n_bits
+        // => 2^e <= r < 2^(e + 1) with e = floor((n_bits - 1) / n)
+        #[allow(clippy::integer_division)]
+        let e = (self.n_bits() - 1) / n as u32;
+        if e >= 127 {
+            return None;
+        }
+        // Binary search, maintaining lo^n <= self < (hi + 1)^n
+        let mut lo = 1_u128 << e;
+        let mut hi = (1_u128 << (e + 1)) - 1;
+        while lo < hi {
+            let mid = lo + ((hi - lo + 1) >> 1);
+            if self.cmp_pow(mid, n) == Ordering::Greater {
+                hi = mid - 1;
+            } else {
+                lo = mid;
+            }
+        }
+        Some((lo, self.cmp_pow(lo, n) == Ordering::Equal))
+    }
+}
+
+// Round the (absolute) value of a root, having `shift` > 0 more fractional
+// digits than requested, according to the current RoundingMode.
+// If the root has been truncated (i.e. is not exact), a last digit 0 or 5 is
+// incremented, so that the truncated digits can neither be mistaken for an
+// exact zero nor for an exact tie.
+fn round_root(
+    root: u128,
+    is_exact: bool,
+    is_negative: bool,
+    shift: u8,
+) -> i128 {
+    // root < 2^127, so root as i128 is safe
+    let mut coeff = root as i128;
+    if !is_exact && coeff % 5 == 0 {
+        coeff += 1;
+    }
+    if is_negative {
+        coeff = -coeff;
+    }
+    i128_div_rounded(coeff, ten_pow(shift), None)
+}
+
+impl Decimal {
+    /// Returns the square root of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is negative or `n_frac_digits` exceeds
+    /// [MAX_N_FRAC_DIGITS]!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(2);
+    /// let r = d.sqrt_rounded(9);
+    /// assert_eq!(r.to_string(), "1.414213562");
+    /// let d = Dec!(0.0625);
+    /// let r = d.sqrt_rounded(4);
+    /// assert_eq!(r.to_string(), "0.2500");
+    /// ```
+    pub fn sqrt_rounded(self, n_frac_digits: u8) -> Self {
+        match self.checked_sqrt_rounded(n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the square root of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::NotANumber` if `self` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(1522.756);
+    /// let r = d.checked_sqrt_rounded(3)?;
+    /// assert_eq!(r.to_string(), "39.023");
+    /// let d = Dec!(-4);
+    /// let r = d.checked_sqrt_rounded(3);
+    /// assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_sqrt_rounded(
+        self,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(DecimalError::MaxNFracDigitsExceeded);
+        }
+        if self.is_negative() {
+            return Err(DecimalError::NotANumber);
+        }
+        if self.eq_zero() {
+            return Ok(Self {
+                coeff: 0,
+                n_frac_digits,
+            });
+        }
+        // The root is calculated with m > n_frac_digits fractional digits,
+        // so that the scaled radicand is an integer:
+        // sqrt(coeff / 10^p) * 10^m = sqrt(coeff * 10^(2m - p))
+        let m = max(n_frac_digits + 1, (self.n_frac_digits + 1) >> 1);
+        // 2m - p <= 38, so ten_pow is safe.
+        let (xh, xl) = u128_mul_u128(
+            self.coeff as u128,
+            ten_pow(2 * m - self.n_frac_digits) as u128,
+        );
+        // coeff < 2^127 => root < 2^63.5 * 10^19 < 2^127
+        let (root, is_exact) = u256_isqrt(xh, xl);
+        Ok(Self {
+            coeff: round_root(root, is_exact, false, m - n_frac_digits),
+            n_frac_digits,
+        })
+    }
+
+    /// Returns the `n`-th root of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` equals zero, `n` is even and `self` is negative,
+    /// `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS] or the result can not be
+    /// represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(2);
+    /// let r = d.nth_root_rounded(3, 10);
+    /// assert_eq!(r.to_string(), "1.2599210499");
+    /// let d = Dec!(-0.008);
+    /// let r = d.nth_root_rounded(3, 2);
+    /// assert_eq!(r.to_string(), "-0.20");
+    /// ```
+    pub fn nth_root_rounded(self, n: u8, n_frac_digits: u8) -> Self {
+        match self.checked_nth_root_rounded(n, n_frac_digits) {
+            Ok(res) => res,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns the `n`-th root of `self`, rounded to `n_frac_digits`
+    /// fractional digits according to the current
+    /// [RoundingMode](crate::RoundingMode), wrapped in `Result::Ok`.
+    ///
+    /// # Errors
+    ///
+    /// * `DecimalError::MaxNFracDigitsExceeded` if `n_frac_digits` exceeds
+    ///   [MAX_N_FRAC_DIGITS],
+    /// * `DecimalError::NotANumber` if `n` equals zero or if `n` is even and
+    ///   `self` is negative,
+    /// * `DecimalError::InternalOverflow` if the result can not be
+    ///   represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, DecimalError};
+    /// let d = Dec!(1.21550625);
+    /// let r = d.checked_nth_root_rounded(4, 4)?;
+    /// assert_eq!(r.to_string(), "1.0500");
+    /// let r = d.checked_nth_root_rounded(0, 4);
+    /// assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+    /// # Ok::<(), DecimalError>(())
+    /// ```
+    pub fn checked_nth_root_rounded(
+        self,
+        n: u8,
+        n_frac_digits: u8,
+    ) -> Result<Self, DecimalError> {
+        if n == 2 {
+            return self.checked_sqrt_rounded(n_frac_digits);
+        }
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return Err(DecimalError::MaxNFracDigitsExceeded);
+        }
+        if n == 0 || self.is_negative() && n & 1 == 0 {
+            return Err(DecimalError::NotANumber);
+        }
+        if self.eq_zero() {
+            return Ok(Self {
+                coeff: 0,
+                n_frac_digits,
+            });
+        }
+        if n == 1 {
+            return if n_frac_digits >= self.n_frac_digits {
+                match checked_mul_pow_ten(
+                    self.coeff,
+                    n_frac_digits - self.n_frac_digits,
+                ) {
+                    Some(coeff) => Ok(Self {
+                        coeff,
+                        n_frac_digits,
+                    }),
+                    None => Err(DecimalError::InternalOverflow),
+                }
+            } else {
+                Ok(Self {
+                    coeff: i128_div_rounded(
+                        self.coeff,
+                        ten_pow(self.n_frac_digits - n_frac_digits),
+                        None,
+                    ),
+                    n_frac_digits,
+                })
+            };
+        }
+        // The root is calculated with m > n_frac_digits fractional digits,
+        // so that the scaled radicand is an integer:
+        // (coeff / 10^p)^(1/n) * 10^m = (coeff * 10^(n * m - p))^(1/n)
+        let m = max(n_frac_digits + 1, self.n_frac_digits.div_ceil(n));
+        let radicand = Limbs::from_scaled(
+            self.coeff.unsigned_abs(),
+            n as u32 * m as u32 - self.n_frac_digits as u32,
+        );
+        // n >= 3 => root < 2^42.4 * 10^19 < 2^127, so iroot can't fail.
+        match radicand.iroot(n) {
+            Some((root, is_exact)) => Ok(Self {
+                coeff: round_root(
+                    root,
+                    is_exact,
+                    self.is_negative(),
+                    m - n_frac_digits,
+                ),
+                n_frac_digits,
+            }),
+            None => Err(DecimalError::InternalOverflow),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sqrt_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::RoundingMode;
+
+    #[test]
+    fn test_sqrt_exact() {
+        let d = Decimal::new_raw(15241383936, 4);
+        let r = d.sqrt_rounded(2);
+        assert_eq!(r.coefficient(), 123456);
+        assert_eq!(r.n_frac_digits(), 2);
+        let r = d.sqrt_rounded(5);
+        assert_eq!(r.coefficient(), 123456000);
+        assert_eq!(r.n_frac_digits(), 5);
+        let d = Decimal::new_raw(1, 18);
+        let r = d.sqrt_rounded(9);
+        assert_eq!(r.coefficient(), 1);
+        assert_eq!(r.n_frac_digits(), 9);
+    }
+
+    #[test]
+    fn test_sqrt_inexact() {
+        let d = Decimal::TWO;
+        let r = d.sqrt_rounded(18);
+        assert_eq!(r.coefficient(), 1414213562373095049);
+        assert_eq!(r.n_frac_digits(), 18);
+        let d = Decimal::new_raw(3, 1);
+        let r = d.sqrt_rounded(0);
+        assert_eq!(r.coefficient(), 1);
+        let d = Decimal::MAX;
+        let r = d.sqrt_rounded(18);
+        assert_eq!(r.coefficient(), 13043817825332782212349571806252508369);
+    }
+
+    #[test]
+    fn test_sqrt_tie() {
+        // sqrt(0.25) = 0.5 => tie when rounded to an int
+        let d = Decimal::new_raw(25, 2);
+        let r = d.sqrt_rounded(0);
+        assert_eq!(r.coefficient(), 0);
+        let d = Decimal::new_raw(225, 2);
+        let r = d.sqrt_rounded(0);
+        assert_eq!(r.coefficient(), 2);
+        let d = Decimal::new_raw(2250001, 6);
+        let r = d.sqrt_rounded(0);
+        assert_eq!(r.coefficient(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sqrt_rounding_mode() {
+        // sqrt(1.5) = 1.22474487139...
+        let d = Decimal::new_raw(15, 1);
+        RoundingMode::set_default(RoundingMode::RoundUp);
+        let r = d.sqrt_rounded(2);
+        RoundingMode::set_default(RoundingMode::RoundHalfEven);
+        assert_eq!(r.coefficient(), 123);
+        // just above the tie
+        let d = Decimal::new_raw(2250001, 6);
+        RoundingMode::set_default(RoundingMode::RoundHalfDown);
+        let r = d.sqrt_rounded(0);
+        RoundingMode::set_default(RoundingMode::RoundHalfEven);
+        assert_eq!(r.coefficient(), 2);
+        let d = Decimal::new_raw(225, 2);
+        RoundingMode::set_default(RoundingMode::RoundHalfDown);
+        let r = d.sqrt_rounded(0);
+        RoundingMode::set_default(RoundingMode::RoundHalfEven);
+        assert_eq!(r.coefficient(), 1);
+    }
+
+    #[test]
+    fn test_sqrt_zero() {
+        let d = Decimal::new_raw(0, 7);
+        let r = d.sqrt_rounded(3);
+        assert_eq!(r.coefficient(), 0);
+        assert_eq!(r.n_frac_digits(), 3);
+        let r = d.nth_root_rounded(3, 5);
+        assert_eq!(r.coefficient(), 0);
+        assert_eq!(r.n_frac_digits(), 5);
+    }
+
+    #[test]
+    fn test_checked_sqrt_err() {
+        let d = Decimal::NEG_ONE;
+        let r = d.checked_sqrt_rounded(2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let r = Decimal::TWO.checked_sqrt_rounded(19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sqrt_neg() {
+        let d = Decimal::new_raw(-1, 5);
+        let _ = d.sqrt_rounded(2);
+    }
+}
+
+#[cfg(test)]
+mod nth_root_tests {
+    use super::*;
+
+    #[test]
+    fn test_nth_root_exact() {
+        let d = Decimal::new_raw(27, 0);
+        let r = d.nth_root_rounded(3, 0);
+        assert_eq!(r.coefficient(), 3);
+        let d = Decimal::new_raw(-8, 0);
+        let r = d.nth_root_rounded(3, 2);
+        assert_eq!(r.coefficient(), -200);
+        assert_eq!(r.n_frac_digits(), 2);
+        let d = Decimal::new_raw(1024, 0);
+        let r = d.nth_root_rounded(10, 1);
+        assert_eq!(r.coefficient(), 20);
+        let d = Decimal::new_raw(1, 18);
+        let r = d.nth_root_rounded(6, 3);
+        assert_eq!(r.coefficient(), 1);
+    }
+
+    #[test]
+    fn test_nth_root_inexact() {
+        let d = Decimal::TWO;
+        let r = d.nth_root_rounded(3, 18);
+        assert_eq!(r.coefficient(), 1259921049894873165);
+        let d = Decimal::new_raw(-2, 0);
+        let r = d.nth_root_rounded(5, 12);
+        assert_eq!(r.coefficient(), -1148698354997);
+        let d = Decimal::MAX;
+        let r = d.nth_root_rounded(255, 18);
+        assert_eq!(r.coefficient(), 1412292793148013930);
+        let d = Decimal::new_raw(5, 0);
+        let r = d.nth_root_rounded(255, 18);
+        assert_eq!(r.coefficient(), 1006331480845079485);
+    }
+
+    #[test]
+    fn test_nth_root_tie() {
+        // 0.125^(1/3) = 0.5 => tie when rounded to an int
+        let d = Decimal::new_raw(125, 3);
+        let r = d.nth_root_rounded(3, 0);
+        assert_eq!(r.coefficient(), 0);
+        let d = Decimal::new_raw(-3375, 3);
+        let r = d.nth_root_rounded(3, 0);
+        assert_eq!(r.coefficient(), -2);
+    }
+
+    #[test]
+    fn test_first_root() {
+        let d = Decimal::new_raw(125, 2);
+        let r = d.nth_root_rounded(1, 1);
+        assert_eq!(r.coefficient(), 12);
+        let r = d.nth_root_rounded(1, 4);
+        assert_eq!(r.coefficient(), 12500);
+        let r = Decimal::MAX.checked_nth_root_rounded(1, 1);
+        assert_eq!(r.unwrap_err(), DecimalError::InternalOverflow);
+    }
+
+    #[test]
+    fn test_nth_root_sqrt() {
+        let d = Decimal::new_raw(17, 1);
+        assert_eq!(d.nth_root_rounded(2, 9), d.sqrt_rounded(9));
+    }
+
+    #[test]
+    fn test_checked_nth_root_err() {
+        let d = Decimal::new_raw(-16, 0);
+        let r = d.checked_nth_root_rounded(4, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let r = d.checked_nth_root_rounded(0, 2);
+        assert_eq!(r.unwrap_err(), DecimalError::NotANumber);
+        let r = d.checked_nth_root_rounded(3, 19);
+        assert_eq!(r.unwrap_err(), DecimalError::MaxNFracDigitsExceeded);
+    }
+}
@@ -7,11 +7,9 @@
 // $Source$
 // $Revision$
 
-use fpdec_core::{i128_div_rounded, ten_pow, Round};
+use fpdec_core::{i128_div_rounded, ten_pow, Round, RoundingMode};
 
-use crate::Decimal;
-#[cfg(doc)]
-use crate::RoundingMode;
+use crate::{Decimal, DecimalError};
 
 impl Round for Decimal {
     /// Returns a new `Decimal` with its value rounded to `n_frac_digits`
@@ -35,7 +33,56 @@ impl Round for Decimal {
     /// let r = d.round(-1);
     /// assert_eq!(r.to_string(), "30");
     /// ```
+    #[inline]
     fn round(self, n_frac_digits: i8) -> Self {
+        self.round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `Decimal` instance with its value rounded to
+    /// `n_frac_digits` fractional digits according to the current
+    /// [RoundingMode], wrapped in `Option::Some`, or `Option::None` if the
+    /// result can not be represented by `Decimal`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Round};
+    /// # fn main() {
+    /// # fn f() -> Option<Decimal> {
+    /// let d = Dec!(28.27093);
+    /// let r = d.checked_round(4)?;
+    /// assert_eq!(r.to_string(), "28.2709");
+    /// let r = d.checked_round(0)?;
+    /// assert_eq!(r.to_string(), "28");
+    /// let d = Dec!(170141183460469231731687303715884105727);
+    /// let r = d.checked_round(-3);
+    /// assert!(r.is_none());
+    /// # Option::None
+    /// # } f();}
+    /// ```
+    #[inline]
+    fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+        self.checked_round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `Decimal` with its value rounded to `n_frac_digits`
+    /// fractional digits according to the given [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Round, RoundingMode};
+    /// let d = Dec!(-28.25);
+    /// let r = d.round_with_mode(1, RoundingMode::RoundHalfUp);
+    /// assert_eq!(r.to_string(), "-28.3");
+    /// let r = d.round_with_mode(1, RoundingMode::RoundCeiling);
+    /// assert_eq!(r.to_string(), "-28.2");
+    /// ```
+    fn round_with_mode(self, n_frac_digits: i8, mode: RoundingMode) -> Self {
         if n_frac_digits >= self.n_frac_digits as i8 {
             self
         } else if n_frac_digits < self.n_frac_digits as i8 - 38 {
@@ -44,7 +91,7 @@ impl Round for Decimal {
             // n_frac_digits < self.n_frac_digits
             let shift: u8 = (self.n_frac_digits as i8 - n_frac_digits) as u8;
             let divisor = ten_pow(shift);
-            let coeff = i128_div_rounded(self.coeff, divisor, None);
+            let coeff = i128_div_rounded(self.coeff, divisor, Some(mode));
             if n_frac_digits >= 0 {
                 Self {
                     coeff,
@@ -61,28 +108,14 @@ impl Round for Decimal {
     }
 
     /// Returns a new `Decimal` instance with its value rounded to
-    /// `n_frac_digits` fractional digits according to the current
+    /// `n_frac_digits` fractional digits according to the given
     /// [RoundingMode], wrapped in `Option::Some`, or `Option::None` if the
     /// result can not be represented by `Decimal`.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// # use fpdec::{Dec, Decimal, Round};
-    /// # fn main() {
-    /// # fn f() -> Option<Decimal> {
-    /// let d = Dec!(28.27093);
-    /// let r = d.checked_round(4)?;
-    /// assert_eq!(r.to_string(), "28.2709");
-    /// let r = d.checked_round(0)?;
-    /// assert_eq!(r.to_string(), "28");
-    /// let d = Dec!(170141183460469231731687303715884105727);
-    /// let r = d.checked_round(-3);
-    /// assert!(r.is_none());
-    /// # Option::None
-    /// # } f();}
-    /// ```
-    fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+    fn checked_round_with_mode(
+        self,
+        n_frac_digits: i8,
+        mode: RoundingMode,
+    ) -> Option<Self> {
         if n_frac_digits >= self.n_frac_digits as i8 {
             Some(self)
         } else if n_frac_digits < self.n_frac_digits as i8 - 38 {
@@ -91,7 +124,7 @@ impl Round for Decimal {
             // n_frac_digits < self.n_frac_digits
             let shift: u8 = (self.n_frac_digits as i8 - n_frac_digits) as u8;
             let divisor = ten_pow(shift);
-            let coeff = i128_div_rounded(self.coeff, divisor, None);
+            let coeff = i128_div_rounded(self.coeff, divisor, Some(mode));
             if n_frac_digits >= 0 {
                 Some(Self {
                     coeff,
@@ -110,8 +143,86 @@ impl Round for Decimal {
     }
 }
 
+impl Decimal {
+    /// Returns a new `Decimal` with its value rounded to `n_sig_digits`
+    /// significant digits according to the current [RoundingMode].
+    ///
+    /// A value of 0 for `n_sig_digits` is treated as 1. If `self` has no
+    /// more than `n_sig_digits` significant digits, it is returned
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(28.27093);
+    /// assert_eq!(d.round_sig(3).to_string(), "28.3");
+    /// let d = Dec!(-0.0012345);
+    /// assert_eq!(d.round_sig(2).to_string(), "-0.0012");
+    /// let d = Dec!(98765);
+    /// assert_eq!(d.round_sig(2).to_string(), "99000");
+    /// let d = Dec!(9.996);
+    /// assert_eq!(d.round_sig(3).to_string(), "10.0");
+    /// ```
+    pub fn round_sig(self, n_sig_digits: u8) -> Self {
+        match self.checked_round_sig(n_sig_digits) {
+            Some(d) => d,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+
+    /// Returns a new `Decimal` with its value rounded to `n_sig_digits`
+    /// significant digits according to the current [RoundingMode], wrapped
+    /// in `Option::Some`, or `Option::None` if the result can not be
+    /// represented by `Decimal`.
+    ///
+    /// A value of 0 for `n_sig_digits` is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// # fn main() {
+    /// # fn f() -> Option<Decimal> {
+    /// let d = Dec!(28.27093);
+    /// let r = d.checked_round_sig(4)?;
+    /// assert_eq!(r.to_string(), "28.27");
+    /// let d = Dec!(170141183460469231731687303715884105727);
+    /// let r = d.checked_round_sig(1);
+    /// assert!(r.is_none());
+    /// # Option::None
+    /// # } f();}
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn checked_round_sig(self, n_sig_digits: u8) -> Option<Self> {
+        let magnitude = self.magnitude();
+        let n_frac_digits =
+            i32::from(n_sig_digits.max(1)) - 1 - i32::from(magnitude);
+        if n_frac_digits >= i32::from(self.n_frac_digits) {
+            return Some(self);
+        }
+        // here: -38 <= n_frac_digits < self.n_frac_digits
+        let n_frac_digits = n_frac_digits as i8;
+        let res = self.checked_round(n_frac_digits)?;
+        if res.magnitude() > magnitude && n_frac_digits > 0 {
+            // Rounding has added a digit in front, so the last (zero) digit
+            // is superfluous.
+            res.checked_round(n_frac_digits - 1)
+        } else {
+            Some(res)
+        }
+    }
+}
+
 #[cfg(test)]
 mod round_decimal_tests {
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]
@@ -186,4 +297,90 @@ mod round_decimal_tests {
         let res = d.checked_round(-1);
         assert!(res.is_none());
     }
+
+    #[test]
+    fn test_decimal_round_with_mode() {
+        let d = Decimal::new_raw(-1285, 2);
+        let r = d.round_with_mode(1, RoundingMode::RoundHalfUp);
+        assert_eq!(r.coefficient(), -129);
+        let r = d.round_with_mode(1, RoundingMode::RoundHalfDown);
+        assert_eq!(r.coefficient(), -128);
+        let r = d.round_with_mode(0, RoundingMode::RoundFloor);
+        assert_eq!(r.coefficient(), -13);
+        let r = d.round_with_mode(0, RoundingMode::RoundCeiling);
+        assert_eq!(r.coefficient(), -12);
+        let d = Decimal::MAX;
+        assert!(d
+            .checked_round_with_mode(-1, RoundingMode::RoundDown)
+            .is_some());
+        assert!(d
+            .checked_round_with_mode(-1, RoundingMode::RoundUp)
+            .is_none());
+    }
+
+    #[test]
+    fn test_decimal_round_sig() {
+        let d = Decimal::new_raw(-1285, 2);
+        assert_eq!(d.round_sig(3).coefficient(), -128);
+        assert_eq!(d.round_sig(3).n_frac_digits(), 1);
+        assert_eq!(d.round_sig(1).coefficient(), -10);
+        assert_eq!(d.round_sig(1).n_frac_digits(), 0);
+        assert_eq!(d.round_sig(0), d.round_sig(1));
+        assert_eq!(d.round_sig(4), d);
+        assert_eq!(d.round_sig(20), d);
+        let d = Decimal::new_raw(99999, 18);
+        let r = d.round_sig(2);
+        assert_eq!(r.coefficient(), 10);
+        assert_eq!(r.n_frac_digits(), 14);
+        let d = Decimal::new_raw(999_999, 0);
+        let r = d.round_sig(2);
+        assert_eq!(r.coefficient(), 1_000_000);
+        assert_eq!(r.n_frac_digits(), 0);
+        assert_eq!(Decimal::ZERO.round_sig(3), Decimal::ZERO);
+        let d = Decimal::MAX;
+        assert_eq!(
+            d.checked_round_sig(2).unwrap().to_string(),
+            "170000000000000000000000000000000000000"
+        );
+        assert!(d.checked_round_sig(1).is_none());
+    }
+
+    #[test]
+    fn test_decimal_round_half_odd_to_odd() {
+        let d = Decimal::new_raw(-1285, 2);
+        let r = d.round_with_mode(1, RoundingMode::RoundHalfOdd);
+        assert_eq!(r.coefficient(), -129);
+        let r = d.round_with_mode(0, RoundingMode::RoundHalfOdd);
+        assert_eq!(r.coefficient(), -13);
+        let r = d.round_with_mode(0, RoundingMode::RoundToOdd);
+        assert_eq!(r.coefficient(), -13);
+        let r = d.round_with_mode(1, RoundingMode::RoundToOdd);
+        assert_eq!(r.coefficient(), -129);
+        let d = Decimal::new_raw(1275, 2);
+        let r = d.round_with_mode(1, RoundingMode::RoundToOdd);
+        assert_eq!(r.coefficient(), 127);
+        // double rounding
+        let d = Decimal::new_raw(2500001, 7);
+        let r = d
+            .round_with_mode(3, RoundingMode::RoundToOdd)
+            .round_with_mode(1, RoundingMode::RoundHalfEven);
+        assert_eq!(r, d.round_with_mode(1, RoundingMode::RoundHalfEven));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_decimal_round_stochastic() {
+        use crate::{DivRounded, MulRounded, Quantize};
+        let mode = RoundingMode::RoundStochastic;
+        let d = Decimal::new_raw(-1281, 2);
+        RoundingMode::set_random_source(|| 0);
+        assert_eq!(d.round_with_mode(1, mode).coefficient(), -128);
+        let q = Decimal::new_raw(5, 1);
+        assert_eq!(d.quantize_with_mode(q, mode).coefficient(), -125);
+        RoundingMode::set_random_source(|| u64::MAX);
+        assert_eq!(d.round_with_mode(1, mode).coefficient(), -129);
+        assert_eq!(d.div_rounded_with_mode(3_i32, 0, mode).coefficient(), -5);
+        assert_eq!(d.mul_rounded_with_mode(q, 1, mode).coefficient(), -65);
+        RoundingMode::reset_random_source();
+    }
 }
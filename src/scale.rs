@@ -0,0 +1,257 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use fpdec_core::{
+    checked_mul_pow_ten, ten_pow, Round, RoundingMode, MAX_N_FRAC_DIGITS,
+};
+
+use crate::{normalize, Decimal, DecimalError};
+
+impl Decimal {
+    /// Returns `self` with all trailing zeros of its fractional part
+    /// removed, i.e. the equivalent value with the least number of
+    /// fractional digits.
+    ///
+    /// Equal values give identical results, so the result can be used as a
+    /// canonical representation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(17.5000);
+    /// assert_eq!(d.normalized().to_string(), "17.5");
+    /// let d = Dec!(-1200.00);
+    /// assert_eq!(d.normalized().to_string(), "-1200");
+    /// ```
+    pub fn normalized(self) -> Self {
+        let mut coeff = self.coeff;
+        let mut n_frac_digits = self.n_frac_digits;
+        normalize(&mut coeff, &mut n_frac_digits);
+        Self {
+            coeff,
+            n_frac_digits,
+        }
+    }
+
+    /// Returns the number of trailing zeros of the fractional part of
+    /// `self`, i.e. the number of fractional digits removed by
+    /// [Decimal::normalized].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// assert_eq!(Dec!(17.5000).trailing_zeros(), 3);
+    /// assert_eq!(Dec!(1200).trailing_zeros(), 0);
+    /// assert_eq!(Dec!(0.00).trailing_zeros(), 2);
+    /// ```
+    #[must_use]
+    pub fn trailing_zeros(self) -> u8 {
+        self.n_frac_digits - self.normalized().n_frac_digits
+    }
+
+    /// Returns `true` if `self` is an integral value, otherwise `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// assert!(Dec!(-17.000).is_integer());
+    /// assert!(!Dec!(17.001).is_integer());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn is_integer(self) -> bool {
+        self.n_frac_digits == 0
+            || self.coeff % ten_pow(self.n_frac_digits) == 0
+    }
+
+    /// Returns the value of `self` with exactly `n_frac_digits` fractional
+    /// digits, rounded according to the current [RoundingMode], if
+    /// `n_frac_digits` is less than the number of fractional digits of
+    /// `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS] or the result
+    /// can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(28.27093);
+    /// assert_eq!(d.rescale(2).to_string(), "28.27");
+    /// assert_eq!(d.rescale(7).to_string(), "28.2709300");
+    /// ```
+    pub fn rescale(self, n_frac_digits: u8) -> Self {
+        self.rescale_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns the value of `self` with exactly `n_frac_digits` fractional
+    /// digits, rounded according to the given [RoundingMode], if
+    /// `n_frac_digits` is less than the number of fractional digits of
+    /// `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [MAX_N_FRAC_DIGITS] or the result
+    /// can not be represented by `Decimal`!
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, RoundingMode};
+    /// let d = Dec!(-28.275);
+    /// let r = d.rescale_with_mode(2, RoundingMode::RoundHalfUp);
+    /// assert_eq!(r.to_string(), "-28.28");
+    /// let r = d.rescale_with_mode(4, RoundingMode::RoundHalfUp);
+    /// assert_eq!(r.to_string(), "-28.2750");
+    /// ```
+    pub fn rescale_with_mode(
+        self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self {
+        #[allow(clippy::manual_assert)]
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+        }
+        if n_frac_digits < self.n_frac_digits {
+            // n_frac_digits < MAX_N_FRAC_DIGITS <= i8::MAX
+            #[allow(clippy::cast_possible_wrap)]
+            return self.round_with_mode(n_frac_digits as i8, mode);
+        }
+        match self.checked_with_scale(n_frac_digits) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+
+    /// Returns `Some(d)` with `d` being equal to `self` and having exactly
+    /// `n_frac_digits` fractional digits, or `None` if `n_frac_digits`
+    /// exceeds [MAX_N_FRAC_DIGITS] or such a value can not be represented
+    /// by `Decimal`.
+    ///
+    /// In contrast to [Decimal::rescale] this never rounds, i.e. reducing
+    /// the number of fractional digits only succeeds if the digits to be
+    /// removed are all zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal};
+    /// let d = Dec!(28.2700);
+    /// assert_eq!(d.checked_with_scale(6).unwrap().to_string(), "28.270000");
+    /// assert_eq!(d.checked_with_scale(2).unwrap().to_string(), "28.27");
+    /// assert_eq!(d.checked_with_scale(1), None);
+    /// ```
+    #[must_use]
+    pub fn checked_with_scale(self, n_frac_digits: u8) -> Option<Self> {
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            return None;
+        }
+        if n_frac_digits >= self.n_frac_digits {
+            return Some(Self {
+                coeff: checked_mul_pow_ten(
+                    self.coeff,
+                    n_frac_digits - self.n_frac_digits,
+                )?,
+                n_frac_digits,
+            });
+        }
+        let divisor = ten_pow(self.n_frac_digits - n_frac_digits);
+        #[allow(clippy::integer_division)]
+        (self.coeff % divisor == 0).then(|| Self {
+            coeff: self.coeff / divisor,
+            n_frac_digits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod scale_tests {
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_normalized() {
+        for (d, coeff, n_frac_digits) in [
+            (Dec!(17.5000), 175, 1),
+            (Dec!(-1200.00), -1200, 0),
+            (Dec!(0.000), 0, 0),
+            (Dec!(0.000000000000000001), 1, 18),
+            (Decimal::MIN, i128::MIN + 1, 0),
+        ] {
+            let n = d.normalized();
+            assert_eq!(n, d);
+            assert_eq!(n.coefficient(), coeff);
+            assert_eq!(n.n_frac_digits(), n_frac_digits);
+            assert_eq!(d.trailing_zeros(), d.n_frac_digits() - n_frac_digits);
+        }
+        let (a, b) = (Dec!(1.10).normalized(), Dec!(1.1000).normalized());
+        assert_eq!(a.coefficient(), b.coefficient());
+        assert_eq!(a.n_frac_digits(), b.n_frac_digits());
+    }
+
+    #[test]
+    fn test_is_integer() {
+        assert!(Decimal::ZERO.is_integer());
+        assert!(Dec!(0.000).is_integer());
+        assert!(Dec!(-3.00).is_integer());
+        assert!(Decimal::MAX.is_integer());
+        assert!(!Dec!(-3.01).is_integer());
+        assert!(!Decimal::DELTA.is_integer());
+    }
+
+    #[test]
+    fn test_rescale() {
+        let d = Dec!(-28.275);
+        assert_eq!(d.rescale(2), Dec!(-28.28));
+        assert_eq!(d.rescale(2).n_frac_digits(), 2);
+        assert_eq!(d.rescale(0), Dec!(-28));
+        assert_eq!(d.rescale(3).coefficient(), -28275);
+        assert_eq!(d.rescale(18).coefficient(), -28275 * ten_pow(15));
+        let r = d.rescale_with_mode(2, RoundingMode::RoundDown);
+        assert_eq!(r, Dec!(-28.27));
+        let r = d.rescale_with_mode(1, RoundingMode::RoundCeiling);
+        assert_eq!(r, Dec!(-28.2));
+        assert_eq!(r.n_frac_digits(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rescale_overflow() {
+        let _ = Decimal::MAX.rescale(1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rescale_max_n_frac_digits_exceeded() {
+        let _ = Decimal::ONE.rescale(19);
+    }
+
+    #[test]
+    fn test_checked_with_scale() {
+        let d = Dec!(-28.2700);
+        let r = d.checked_with_scale(18).unwrap();
+        assert_eq!(r, d);
+        assert_eq!(r.n_frac_digits(), 18);
+        let r = d.checked_with_scale(2).unwrap();
+        assert_eq!(r.coefficient(), -2827);
+        assert_eq!(r.n_frac_digits(), 2);
+        assert_eq!(d.checked_with_scale(1), None);
+        assert_eq!(d.checked_with_scale(19), None);
+        assert_eq!(Decimal::MAX.checked_with_scale(1), None);
+        assert_eq!(Decimal::MAX.checked_with_scale(0), Some(Decimal::MAX));
+        let r = Dec!(0.00).checked_with_scale(0).unwrap();
+        assert_eq!(r.n_frac_digits(), 0);
+    }
+}
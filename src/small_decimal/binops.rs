@@ -0,0 +1,1203 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+    SubAssign,
+};
+
+use fpdec_core::{
+    checked_mul_pow_ten, i128_div_rounded, mul_pow_ten, ten_pow,
+    MAX_N_FRAC_DIGITS,
+};
+
+use super::{Decimal32, Decimal64};
+use crate::{
+    normalize, CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub,
+    Decimal, DecimalError, DivRounded, MulRounded, RoundingMode,
+};
+
+// Implements binary operators "&T op U", "T op &U", "&T op &U"
+// based on "T op U"
+macro_rules! forward_ref_binop_small {
+    (impl $imp:ident, $method:ident, $t:ty) => {
+        forward_ref_binop_small!(impl $imp, $method, $t, $t);
+    };
+    (impl $imp:ident, $method:ident, $t:ty, $u:ty) => {
+        impl<'a> $imp<$u> for &'a $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: $u) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+        impl $imp<&$u> for $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$u) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+        impl $imp<&$u> for &$t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$u) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+// Same for ops giving rounded result.
+macro_rules! forward_ref_binop_rounded_small {
+    (impl $imp:ident, $method:ident, $method_with_mode:ident, $t:ty) => {
+        forward_ref_binop_rounded_small!(
+            impl $imp,
+            $method,
+            $method_with_mode,
+            $t,
+            $t
+        );
+    };
+    (impl $imp:ident, $method:ident, $method_with_mode:ident, $t:ty,
+     $u:ty) => {
+        impl<'a> $imp<$u> for &'a $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: $u, n_frac_digits: u8) -> Self::Output {
+                $imp::$method(*self, rhs, n_frac_digits)
+            }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: $u,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                $imp::$method_with_mode(*self, rhs, n_frac_digits, mode)
+            }
+        }
+        impl $imp<&$u> for $t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$u, n_frac_digits: u8) -> Self::Output {
+                $imp::$method(self, *rhs, n_frac_digits)
+            }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: &$u,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                $imp::$method_with_mode(self, *rhs, n_frac_digits, mode)
+            }
+        }
+        impl $imp<&$u> for &$t {
+            type Output = <$t as $imp<$u>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &$u, n_frac_digits: u8) -> Self::Output {
+                $imp::$method(*self, *rhs, n_frac_digits)
+            }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: &$u,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                $imp::$method_with_mode(*self, *rhs, n_frac_digits, mode)
+            }
+        }
+    };
+}
+
+// Implements "T op= U" based on "T op U"
+macro_rules! forward_op_assign_small {
+    (impl $imp:ident, $method:ident, $base_imp:ident, $base_method:ident,
+     $t:ty) => {
+        impl<U> $imp<U> for $t
+        where
+            $t: $base_imp<U, Output = $t>,
+        {
+            #[inline(always)]
+            fn $method(&mut self, rhs: U) {
+                *self = $base_imp::$base_method(*self, rhs);
+            }
+        }
+    };
+}
+
+// The ops are done on the coefficients of the operands widened to i128. An
+// operand is either a small decimal, having a coefficient of at most 63
+// bits, or a native int of at most 64 bits. Therefore, adjusting the
+// coefficients to a common number of fractional digits, adding or
+// multiplying them can not overflow.
+
+// Returns `x` and `y`, having `p` resp. `q` fractional digits, adjusted to
+// the greater number of fractional digits, together with that number.
+#[inline]
+const fn align(x: i128, p: u8, y: i128, q: u8) -> (i128, i128, u8) {
+    if p >= q {
+        (x, mul_pow_ten(y, p - q), p)
+    } else {
+        (mul_pow_ten(x, q - p), y, q)
+    }
+}
+
+// Returns the coefficient of `x` * 10^-`p` * `y` * 10^-`q`, rounded to
+// `n_frac_digits` according to `mode`, together with its number of
+// fractional digits. If the exact product has less than `n_frac_digits`
+// fractional digits, it is returned unchanged.
+fn mul_coeffs(
+    x: i128,
+    p: u8,
+    y: i128,
+    q: u8,
+    n_frac_digits: u8,
+    mode: Option<RoundingMode>,
+) -> (i128, u8) {
+    let prod = x * y;
+    let n_prod_frac_digits = p + q;
+    if n_frac_digits >= n_prod_frac_digits {
+        (prod, n_prod_frac_digits)
+    } else {
+        // shift <= 2 * MAX_N_FRAC_DIGITS
+        let shift = n_prod_frac_digits - n_frac_digits;
+        (i128_div_rounded(prod, ten_pow(shift), mode), n_frac_digits)
+    }
+}
+
+// Returns the coefficient of (`x` * 10^-`p`) / (`y` * 10^-`q`), rounded to
+// `n_frac_digits` according to `mode`, or `None` if it can not be
+// calculated in i128.
+fn div_coeffs(
+    x: i128,
+    p: u8,
+    y: i128,
+    q: u8,
+    n_frac_digits: u8,
+    mode: Option<RoundingMode>,
+) -> Option<i128> {
+    let shift = n_frac_digits + q;
+    if shift >= p {
+        // If the shifted divident exceeds i128, the quotient exceeds 63
+        // bits and can not be represented by a small decimal anyway.
+        let divident = checked_mul_pow_ten(x, shift - p)?;
+        Some(i128_div_rounded(divident, y, mode))
+    } else {
+        Some(i128_div_rounded(x, mul_pow_ten(y, p - shift), mode))
+    }
+}
+
+// Results of multiplications and divisions which need more fractional digits
+// than the coefficient can hold are rounded to fit.
+macro_rules! impl_small_decimal_ops {
+    ($t:ident) => {
+        impl $t {
+            // Returns `x` * 10^-`p` + `y` * 10^-`q` as `Self`, if it can be
+            // represented by `Self`.
+            fn checked_add_coeffs(
+                x: i128,
+                p: u8,
+                y: i128,
+                q: u8,
+            ) -> Option<Self> {
+                let (x, y, n_frac_digits) = align(x, p, y, q);
+                Self::checked_from_coeff(x + y, n_frac_digits)
+            }
+
+            // Returns `x` * 10^-`p` * `y` * 10^-`q` as `Self`, rounded to
+            // fit, if its integral part can be represented by `Self`.
+            fn checked_mul_coeffs(
+                x: i128,
+                p: u8,
+                y: i128,
+                q: u8,
+            ) -> Option<Self> {
+                Self::checked_fit((p + q).min(MAX_N_FRAC_DIGITS), |n| {
+                    Some(mul_coeffs(x, p, y, q, n, None))
+                })
+            }
+
+            // Returns (`x` * 10^-`p`) / (`y` * 10^-`q`) as `Self`, rounded
+            // to fit, if `y` is not zero and the integral part of the
+            // quotient can be represented by `Self`.
+            fn checked_div_coeffs(
+                x: i128,
+                p: u8,
+                y: i128,
+                q: u8,
+            ) -> Option<Self> {
+                if y == 0 {
+                    return None;
+                }
+                Self::checked_fit(MAX_N_FRAC_DIGITS, |n| {
+                    let mut coeff = div_coeffs(x, p, y, q, n, None)?;
+                    let mut n_frac_digits = n;
+                    normalize(&mut coeff, &mut n_frac_digits);
+                    Some((coeff, n_frac_digits))
+                })
+            }
+
+            // Returns (`x` * 10^-`p`) % (`y` * 10^-`q`) as `Self`, if `y` is
+            // not zero.
+            fn checked_rem_coeffs(
+                x: i128,
+                p: u8,
+                y: i128,
+                q: u8,
+            ) -> Option<Self> {
+                if y == 0 {
+                    return None;
+                }
+                let (x, y, n_frac_digits) = align(x, p, y, q);
+                Self::checked_from_coeff(x % y, n_frac_digits)
+            }
+
+            // Returns `x` * 10^-`p` * `y` * 10^-`q` as `Self`, rounded to
+            // `n_frac_digits` according to `mode`.
+            fn mul_rounded_coeffs(
+                x: i128,
+                p: u8,
+                y: i128,
+                q: u8,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self {
+                #[allow(clippy::manual_assert)]
+                if n_frac_digits > MAX_N_FRAC_DIGITS {
+                    panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+                }
+                let (coeff, n_frac_digits) =
+                    mul_coeffs(x, p, y, q, n_frac_digits, Some(mode));
+                match Self::checked_from_coeff(coeff, n_frac_digits) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+
+            // Returns (`x` * 10^-`p`) / (`y` * 10^-`q`) as `Self`, rounded
+            // to `n_frac_digits` according to `mode`.
+            fn div_rounded_coeffs(
+                x: i128,
+                p: u8,
+                y: i128,
+                q: u8,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self {
+                #[allow(clippy::manual_assert)]
+                if n_frac_digits > MAX_N_FRAC_DIGITS {
+                    panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+                }
+                #[allow(clippy::manual_assert)]
+                if y == 0 {
+                    panic!("{}", DecimalError::DivisionByZero);
+                }
+                match div_coeffs(x, p, y, q, n_frac_digits, Some(mode))
+                    .and_then(|c| Self::checked_from_coeff(c, n_frac_digits))
+                {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+
+            #[inline(always)]
+            fn neg(self) -> Self::Output {
+                Self {
+                    coeff: -self.coeff,
+                    n_frac_digits: self.n_frac_digits,
+                }
+            }
+        }
+
+        impl Neg for &$t {
+            type Output = $t;
+
+            #[inline(always)]
+            fn neg(self) -> Self::Output {
+                -*self
+            }
+        }
+
+        impl CheckedAdd<Self> for $t {
+            type Output = Option<Self>;
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Self::Output {
+                if self.n_frac_digits == rhs.n_frac_digits {
+                    let coeff = self.coeff.checked_add(rhs.coeff)?;
+                    return (coeff != <$t>::MIN.coeff - 1).then_some(Self {
+                        coeff,
+                        n_frac_digits: self.n_frac_digits,
+                    });
+                }
+                Self::checked_add_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedAdd, checked_add, $t);
+
+        impl CheckedSub<Self> for $t {
+            type Output = Option<Self>;
+
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Self::Output {
+                self.checked_add(-rhs)
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedSub, checked_sub, $t);
+
+        impl CheckedMul<Self> for $t {
+            type Output = Option<Self>;
+
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Self::Output {
+                Self::checked_mul_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedMul, checked_mul, $t);
+
+        impl CheckedDiv<Self> for $t {
+            type Output = Option<Self>;
+
+            #[inline]
+            fn checked_div(self, rhs: Self) -> Self::Output {
+                Self::checked_div_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedDiv, checked_div, $t);
+
+        impl CheckedRem<Self> for $t {
+            type Output = Option<Self>;
+
+            #[inline]
+            fn checked_rem(self, rhs: Self) -> Self::Output {
+                Self::checked_rem_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedRem, checked_rem, $t);
+
+        impl_small_decimal_panicking_ops!($t, $t, $t);
+
+        forward_op_assign_small!(impl AddAssign, add_assign, Add, add, $t);
+        forward_op_assign_small!(impl SubAssign, sub_assign, Sub, sub, $t);
+        forward_op_assign_small!(impl MulAssign, mul_assign, Mul, mul, $t);
+        forward_op_assign_small!(impl DivAssign, div_assign, Div, div, $t);
+        forward_op_assign_small!(impl RemAssign, rem_assign, Rem, rem, $t);
+
+        impl MulRounded<Self> for $t {
+            type Output = Self;
+
+            /// Returns `self` * `rhs`, rounded to `n_frac_digits`, according
+            /// to the current [RoundingMode].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n_frac_digits` exceeds
+            /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result
+            /// can not be represented by `Self`!
+            #[inline]
+            fn mul_rounded(self, rhs: Self, n_frac_digits: u8) -> Self {
+                self.mul_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            /// Returns `self` * `rhs`, rounded to `n_frac_digits`, according
+            /// to `mode`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n_frac_digits` exceeds
+            /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result
+            /// can not be represented by `Self`!
+            fn mul_rounded_with_mode(
+                self,
+                rhs: Self,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self {
+                Self::mul_rounded_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                    n_frac_digits,
+                    mode,
+                )
+            }
+        }
+
+        forward_ref_binop_rounded_small!(
+            impl MulRounded,
+            mul_rounded,
+            mul_rounded_with_mode,
+            $t
+        );
+
+        impl DivRounded<Self> for $t {
+            type Output = Self;
+
+            /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according
+            /// to the current [RoundingMode].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs` equals zero, `n_frac_digits` exceeds
+            /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result
+            /// can not be represented by `Self`!
+            #[inline]
+            fn div_rounded(self, rhs: Self, n_frac_digits: u8) -> Self {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according
+            /// to `mode`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs` equals zero, `n_frac_digits` exceeds
+            /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result
+            /// can not be represented by `Self`!
+            fn div_rounded_with_mode(
+                self,
+                rhs: Self,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self {
+                Self::div_rounded_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                    n_frac_digits,
+                    mode,
+                )
+            }
+        }
+
+        forward_ref_binop_rounded_small!(
+            impl DivRounded,
+            div_rounded,
+            div_rounded_with_mode,
+            $t
+        );
+
+        impl_small_decimal_and_int_ops!(
+            $t, u8, i8, u16, i16, u32, i32, u64, i64
+        );
+
+        impl_small_decimal_and_decimal_ops!($t);
+    };
+}
+
+// Implements the operators "T op U" based on the corresponding checked ops,
+// with `S` being the resulting small decimal type.
+macro_rules! impl_small_decimal_panicking_ops {
+    ($s:ty, $t:ty, $u:ty) => {
+        impl Add<$u> for $t {
+            type Output = $s;
+
+            /// # Panics
+            ///
+            /// Panics if the result can not be represented by
+            /// `Self::Output`!
+            #[inline]
+            fn add(self, rhs: $u) -> Self::Output {
+                match CheckedAdd::checked_add(self, rhs) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        forward_ref_binop_small!(impl Add, add, $t, $u);
+
+        impl Sub<$u> for $t {
+            type Output = $s;
+
+            /// # Panics
+            ///
+            /// Panics if the result can not be represented by
+            /// `Self::Output`!
+            #[inline]
+            fn sub(self, rhs: $u) -> Self::Output {
+                match CheckedSub::checked_sub(self, rhs) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        forward_ref_binop_small!(impl Sub, sub, $t, $u);
+
+        impl Mul<$u> for $t {
+            type Output = $s;
+
+            /// Returns `self` * `rhs`, rounded to the maximum number of
+            /// fractional digits which can be represented by
+            /// `Self::Output`, if necessary.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the integral part of the result can not be
+            /// represented by `Self::Output`!
+            fn mul(self, rhs: $u) -> Self::Output {
+                match CheckedMul::checked_mul(self, rhs) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        forward_ref_binop_small!(impl Mul, mul, $t, $u);
+
+        impl Div<$u> for $t {
+            type Output = $s;
+
+            /// Returns `self` / `rhs`, rounded to the maximum number of
+            /// fractional digits which can be represented by
+            /// `Self::Output`, if necessary.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs` equals zero or the integral part of the
+            /// result can not be represented by `Self::Output`!
+            fn div(self, rhs: $u) -> Self::Output {
+                #[allow(clippy::manual_assert)]
+                if rhs == <$u>::default() {
+                    panic!("{}", DecimalError::DivisionByZero);
+                }
+                match CheckedDiv::checked_div(self, rhs) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        forward_ref_binop_small!(impl Div, div, $t, $u);
+
+        impl Rem<$u> for $t {
+            type Output = $s;
+
+            /// # Panics
+            ///
+            /// Panics if `rhs` equals zero or the result can not be
+            /// represented by `Self::Output`!
+            fn rem(self, rhs: $u) -> Self::Output {
+                #[allow(clippy::manual_assert)]
+                if rhs == <$u>::default() {
+                    panic!("{}", DecimalError::DivisionByZero);
+                }
+                match CheckedRem::checked_rem(self, rhs) {
+                    Some(res) => res,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+        }
+
+        forward_ref_binop_small!(impl Rem, rem, $t, $u);
+    };
+}
+
+// Implements the arithmetic ops of small decimal type `T` and the native
+// ints, giving results of type `T`.
+macro_rules! impl_small_decimal_and_int_ops {
+    ($t:ident, $($i:ty),*) => {
+        $(
+        impl CheckedAdd<$i> for $t {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_add(self, rhs: $i) -> Self::Output {
+                <$t>::checked_add_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs),
+                    0,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedAdd, checked_add, $t, $i);
+
+        impl CheckedAdd<$t> for $i {
+            type Output = Option<$t>;
+
+            #[inline(always)]
+            fn checked_add(self, rhs: $t) -> Self::Output {
+                rhs.checked_add(self)
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedAdd, checked_add, $i, $t);
+
+        impl CheckedSub<$i> for $t {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_sub(self, rhs: $i) -> Self::Output {
+                <$t>::checked_add_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    -i128::from(rhs),
+                    0,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedSub, checked_sub, $t, $i);
+
+        impl CheckedSub<$t> for $i {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_sub(self, rhs: $t) -> Self::Output {
+                <$t>::checked_add_coeffs(
+                    i128::from(self),
+                    0,
+                    -i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedSub, checked_sub, $i, $t);
+
+        impl CheckedMul<$i> for $t {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_mul(self, rhs: $i) -> Self::Output {
+                <$t>::checked_mul_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs),
+                    0,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedMul, checked_mul, $t, $i);
+
+        impl CheckedMul<$t> for $i {
+            type Output = Option<$t>;
+
+            #[inline(always)]
+            fn checked_mul(self, rhs: $t) -> Self::Output {
+                rhs.checked_mul(self)
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedMul, checked_mul, $i, $t);
+
+        impl CheckedDiv<$i> for $t {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_div(self, rhs: $i) -> Self::Output {
+                <$t>::checked_div_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs),
+                    0,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedDiv, checked_div, $t, $i);
+
+        impl CheckedDiv<$t> for $i {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_div(self, rhs: $t) -> Self::Output {
+                <$t>::checked_div_coeffs(
+                    i128::from(self),
+                    0,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedDiv, checked_div, $i, $t);
+
+        impl CheckedRem<$i> for $t {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_rem(self, rhs: $i) -> Self::Output {
+                <$t>::checked_rem_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs),
+                    0,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedRem, checked_rem, $t, $i);
+
+        impl CheckedRem<$t> for $i {
+            type Output = Option<$t>;
+
+            #[inline]
+            fn checked_rem(self, rhs: $t) -> Self::Output {
+                <$t>::checked_rem_coeffs(
+                    i128::from(self),
+                    0,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                )
+            }
+        }
+
+        forward_ref_binop_small!(impl CheckedRem, checked_rem, $i, $t);
+
+        impl_small_decimal_panicking_ops!($t, $t, $i);
+        impl_small_decimal_panicking_ops!($t, $i, $t);
+
+        impl DivRounded<$i> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn div_rounded(
+                self,
+                rhs: $i,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            fn div_rounded_with_mode(
+                self,
+                rhs: $i,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                <$t>::div_rounded_coeffs(
+                    i128::from(self.coeff),
+                    self.n_frac_digits,
+                    i128::from(rhs),
+                    0,
+                    n_frac_digits,
+                    mode,
+                )
+            }
+        }
+
+        forward_ref_binop_rounded_small!(
+            impl DivRounded,
+            div_rounded,
+            div_rounded_with_mode,
+            $t,
+            $i
+        );
+
+        impl DivRounded<$t> for $i {
+            type Output = $t;
+
+            #[inline]
+            fn div_rounded(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                self.div_rounded_with_mode(
+                    rhs,
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            fn div_rounded_with_mode(
+                self,
+                rhs: $t,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                <$t>::div_rounded_coeffs(
+                    i128::from(self),
+                    0,
+                    i128::from(rhs.coeff),
+                    rhs.n_frac_digits,
+                    n_frac_digits,
+                    mode,
+                )
+            }
+        }
+
+        forward_ref_binop_rounded_small!(
+            impl DivRounded,
+            div_rounded,
+            div_rounded_with_mode,
+            $i,
+            $t
+        );
+        )*
+    };
+}
+
+// Implements the arithmetic ops of small decimal type `T` and `Decimal`,
+// based on the ops of `Decimal`, giving results of type `Decimal`.
+macro_rules! impl_small_decimal_and_decimal_ops {
+    ($t:ident) => {
+        impl_small_decimal_and_decimal_ops!(
+            $t,
+            [Add, add],
+            [Sub, sub],
+            [Mul, mul],
+            [Div, div],
+            [Rem, rem],
+            [CheckedAdd, checked_add],
+            [CheckedSub, checked_sub],
+            [CheckedMul, checked_mul],
+            [CheckedDiv, checked_div],
+            [CheckedRem, checked_rem]
+        );
+    };
+    ($t:ident, $([$imp:ident, $method:ident]),*) => {
+        $(
+        impl $imp<Decimal> for $t {
+            type Output = <Decimal as $imp<Decimal>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                $imp::$method(Decimal::from(self), rhs)
+            }
+        }
+
+        forward_ref_binop_small!(impl $imp, $method, $t, Decimal);
+
+        impl $imp<$t> for Decimal {
+            type Output = <Self as $imp<Self>>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: $t) -> Self::Output {
+                $imp::$method(self, Self::from(rhs))
+            }
+        }
+
+        forward_ref_binop_small!(impl $imp, $method, Decimal, $t);
+        )*
+    };
+}
+
+impl_small_decimal_ops!(Decimal64);
+impl_small_decimal_ops!(Decimal32);
+
+#[cfg(test)]
+mod small_decimal_binops_tests {
+    use super::*;
+    use crate::{Dec, Quantize, Round};
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_add_sub() {
+        let x = Decimal64::new(175, 1);
+        let y = Decimal64::new(-1, 3);
+        let z = x + y;
+        assert_eq!(z.coefficient(), 17499);
+        assert_eq!(z.n_frac_digits(), 3);
+        assert_eq!((x - y).coefficient(), 17501);
+        assert_eq!(&x + y, z);
+        assert_eq!(x - &y, x - y);
+        let mut a = x;
+        a += y;
+        a -= &x;
+        assert_eq!(a, y);
+        assert_eq!(-y, Decimal64::new(1, 3));
+        assert_eq!(
+            Decimal32::new(1, 2) + Decimal32::new(2, 2),
+            Decimal32::new(3, 2)
+        );
+    }
+
+    #[test]
+    fn test_checked_add_sub() {
+        assert!(Decimal64::MAX.checked_add(Decimal64::ONE).is_none());
+        assert!(Decimal64::MIN.checked_sub(Decimal64::ONE).is_none());
+        assert!(Decimal32::MAX.checked_add(Decimal32::DELTA).is_none());
+        assert_eq!(
+            Decimal32::MAX.checked_sub(Decimal32::ONE),
+            Some(Decimal32::new(i32::MAX - 1, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_overflow() {
+        let _ = Decimal32::MAX + Decimal32::ONE;
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_mul() {
+        let x = Decimal64::new(15, 1);
+        let y = Decimal64::new(-250000001, 8);
+        let z = x * y;
+        assert_eq!(z.coefficient(), -3750000015);
+        assert_eq!(z.n_frac_digits(), 9);
+        assert_eq!(&x * &y, z);
+        let mut a = y;
+        a *= x;
+        assert_eq!(a, z);
+        // product of coefficients exceeds i64, but normalized result fits
+        let x = Decimal64::new(4000000000000, 6);
+        let z = x * x;
+        assert_eq!(z.coefficient(), 16000000000000);
+        assert_eq!(z.n_frac_digits(), 0);
+        assert!(Decimal32::MAX.checked_mul(Decimal32::TWO).is_none());
+    }
+
+    #[test]
+    fn test_div() {
+        let x = Decimal64::new(17, 0);
+        let y = Decimal64::new(-200, 2);
+        let z = x / y;
+        assert_eq!(z.coefficient(), -85);
+        assert_eq!(z.n_frac_digits(), 1);
+        assert!(Decimal32::ONE.checked_div(Decimal32::ZERO).is_none());
+        assert!(Decimal32::MAX.checked_div(Decimal32::new(1, 1)).is_none());
+        let mut a = x;
+        a /= y;
+        assert_eq!(a, Decimal64::new(-85, 1));
+    }
+
+    #[test]
+    fn test_div_rounded_to_fit() {
+        let z = Decimal64::ONE / Decimal64::new(3, 0);
+        assert_eq!(z.coefficient(), 333333333333333333);
+        assert_eq!(z.n_frac_digits(), 18);
+        let z = Decimal64::new(200, 0) / Decimal64::new(3, 0);
+        assert_eq!(z.coefficient(), 6666666666666666667);
+        assert_eq!(z.n_frac_digits(), 17);
+        let z = Decimal32::ONE / Decimal32::new(3, 0);
+        assert_eq!(z.coefficient(), 333333333);
+        assert_eq!(z.n_frac_digits(), 9);
+        let z = Decimal32::new(-7, 0) / Decimal32::new(3, 0);
+        assert_eq!(z.coefficient(), -233333333);
+        assert_eq!(z.n_frac_digits(), 8);
+        let z = Decimal32::MAX / Decimal32::new(-3, 0);
+        assert_eq!(z.coefficient(), -715827882);
+        assert_eq!(z.n_frac_digits(), 0);
+        assert!(Decimal32::MAX.checked_div(Decimal32::new(3, 1)).is_none());
+    }
+
+    #[test]
+    fn test_mul_rounded_to_fit() {
+        let x = Decimal32::new(123456, 5);
+        let z = x * x;
+        assert_eq!(z.coefficient(), 1524138394);
+        assert_eq!(z.n_frac_digits(), 9);
+        let x = Decimal64::new(1234567890123, 12);
+        let z = x * x;
+        assert_eq!(z.coefficient(), 1524157875322755801);
+        assert_eq!(z.n_frac_digits(), 18);
+        let x = Decimal64::new(12345678901234, 12);
+        let z = x * x;
+        assert_eq!(z.coefficient(), 1524157875323743455);
+        assert_eq!(z.n_frac_digits(), 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_by_zero() {
+        let _ = Decimal64::ONE / Decimal64::ZERO;
+    }
+
+    #[test]
+    fn test_rem() {
+        let x = Decimal64::new(175, 1);
+        let y = Decimal64::new(4, 0);
+        assert_eq!(x % y, Decimal64::new(15, 1));
+        assert!(x.checked_rem(Decimal64::ZERO).is_none());
+        let mut a = x;
+        a %= y;
+        assert_eq!(a, Decimal64::new(15, 1));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_mul_div_rounded() {
+        let x = Decimal64::new(12345, 2);
+        let z = x.mul_rounded(x, 2);
+        assert_eq!(z.coefficient(), 1523990);
+        assert_eq!(z.n_frac_digits(), 2);
+        assert_eq!(MulRounded::mul_rounded(&x, &x, 2), z);
+        let d = Decimal32::new(2827093, 5);
+        let q = Decimal32::new(3, 2);
+        let r = d.div_rounded(q, 3);
+        assert_eq!(r.coefficient(), 942364);
+        assert_eq!(r.n_frac_digits(), 3);
+        assert_eq!(DivRounded::div_rounded(d, &q, 3), r);
+    }
+
+    #[test]
+    fn test_mul_div_rounded_with_mode() {
+        let x = Decimal64::new(12345, 2);
+        let z = x.mul_rounded_with_mode(x, 2, RoundingMode::RoundUp);
+        assert_eq!(z.coefficient(), 1523991);
+        let d = Decimal32::new(2827093, 5);
+        let q = Decimal32::new(3, 2);
+        let r = (&d).div_rounded_with_mode(&q, 3, RoundingMode::RoundUp);
+        assert_eq!(r.coefficient(), 942365);
+        let r = d.round_with_mode(0, RoundingMode::RoundCeiling);
+        assert_eq!(r.coefficient(), 29);
+    }
+
+    #[test]
+    fn test_div_shifted_divident_exceeding_i128() {
+        let z = Decimal64::ONE / Decimal64::new(3, 18);
+        assert_eq!(z.coefficient(), 3333333333333333333);
+        assert_eq!(z.n_frac_digits(), 1);
+        assert!(Decimal64::MAX.checked_div(Decimal64::DELTA).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_int_ops() {
+        let x = Decimal64::new(175, 1);
+        assert_eq!(x + 3_i32, Decimal64::new(205, 1));
+        assert_eq!(3_u8 - x, Decimal64::new(-145, 1));
+        assert_eq!(x * 2_u64, Decimal64::new(35, 0));
+        assert_eq!(7_i64 / Decimal64::new(2, 0), Decimal64::new(35, 1));
+        assert_eq!(x % 4_i16, Decimal64::new(15, 1));
+        assert_eq!(&x + &1_i8, x + 1_i8);
+        assert_eq!(&2_u16 * x, x * 2_u16);
+        let mut a = x;
+        a *= 2_u8;
+        a -= &x;
+        assert_eq!(a, x);
+        let z = Decimal32::ONE / 3_i32;
+        assert_eq!(z.coefficient(), 333333333);
+        assert_eq!(z.n_frac_digits(), 9);
+    }
+
+    #[test]
+    fn test_checked_int_ops() {
+        assert!(Decimal32::MAX.checked_add(1_u8).is_none());
+        assert!(Decimal32::MAX.checked_sub(u64::MAX).is_none());
+        // operand beyond the range of Decimal32, but result fits
+        assert_eq!(
+            Decimal32::NEG_ONE.checked_add(i64::from(i32::MAX) + 1),
+            Some(Decimal32::MAX)
+        );
+        assert!(Decimal64::ONE.checked_div(0_u32).is_none());
+        assert!(CheckedRem::checked_rem(5_i32, Decimal64::ZERO).is_none());
+        assert!(CheckedMul::checked_mul(u64::MAX, Decimal64::TWO).is_none());
+        assert_eq!(
+            CheckedMul::checked_mul(u64::MAX, Decimal64::new(1, 1)),
+            Some(Decimal64::new(1844674407370955162, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_int_by_zero() {
+        let _ = Decimal64::ONE / 0_i32;
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_decimal_ops() {
+        let x = Decimal64::new(175, 1);
+        let d = Dec!(0.25);
+        let r: Decimal = x + d;
+        assert_eq!(r, Dec!(17.75));
+        assert_eq!(d - x, Dec!(-17.25));
+        assert_eq!(&x * d, Dec!(4.375));
+        assert_eq!(Decimal32::ONE / Dec!(8), Dec!(0.125));
+        assert_eq!(Decimal::MAX.checked_add(Decimal32::ONE), None);
+        assert_eq!(x.checked_rem(Dec!(0)), None);
+        let mut a = d;
+        a += x;
+        assert_eq!(a, Dec!(17.75));
+    }
+
+    #[test]
+    fn test_div_rounded_int() {
+        let d = Decimal64::new(2827093, 5);
+        let r = d.div_rounded(5_u32, 4);
+        assert_eq!(r.coefficient(), 56542);
+        assert_eq!(r.n_frac_digits(), 4);
+        let r = 17_i32.div_rounded_with_mode(
+            Decimal32::new(3, 0),
+            2,
+            RoundingMode::RoundUp,
+        );
+        assert_eq!(r.coefficient(), 567);
+        assert_eq!(r.n_frac_digits(), 2);
+    }
+
+    #[test]
+    fn test_quantize() {
+        let d = Decimal64::new(2827093, 5);
+        let r = d.quantize(Decimal64::new(5, 2));
+        assert_eq!(r.coefficient(), 2825);
+        assert_eq!(r.n_frac_digits(), 2);
+        assert_eq!(d.quantize(5_u32), Decimal64::new(30, 0));
+        let r = Decimal32::new(-322, 0)
+            .quantize_with_mode(3_i32, RoundingMode::RoundFloor);
+        assert_eq!(r, Decimal32::new(-324, 0));
+        let r = 43_u8.quantize(Decimal32::new(75, 2));
+        assert_eq!(r.coefficient(), 4275);
+        assert_eq!(r.n_frac_digits(), 2);
+    }
+}
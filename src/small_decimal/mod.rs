@@ -0,0 +1,744 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+// The resolver types generated by deriving `rkyv::Archive` inside of
+// `define_small_decimal!` do not implement `Debug`.
+#![cfg_attr(feature = "rkyv", allow(missing_debug_implementations))]
+
+#[cfg(feature = "serde-as-str")]
+use alloc::string::String;
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use fpdec_core::{
+    i128_magnitude, ParseDecimalError, Round, RoundingMode, MAX_N_FRAC_DIGITS,
+};
+
+use crate::{normalize, Decimal, DecimalError};
+
+mod binops;
+
+macro_rules! define_small_decimal {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $int:ident, $max_doc:literal, $min_doc:literal,
+        [$($from_t:ty),*]
+    ) => {
+        $(#[$attr])*
+        #[must_use]
+        #[derive(Copy, Clone, Default)]
+        #[cfg_attr(
+            feature = "serde-as-str",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(into = "String"),
+            serde(try_from = "String")
+        )]
+        #[cfg_attr(
+            feature = "rkyv",
+            derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+            archive(check_bytes),
+            archive_attr(derive(Copy, Clone, Debug))
+        )]
+        pub struct $name {
+            coeff: $int,
+            n_frac_digits: u8,
+        }
+
+        impl $name {
+            #[doc = concat!(
+                "Creates a new `", stringify!($name), "` with the value ",
+                "`coeff` * 10^-`n_frac_digits`.\n\n",
+                "# Panics\n\n",
+                "Panics if `n_frac_digits` exceeds [`MAX_N_FRAC_DIGITS`]!"
+            )]
+            #[inline]
+            pub const fn new(coeff: $int, n_frac_digits: u8) -> Self {
+                assert!(
+                    n_frac_digits <= MAX_N_FRAC_DIGITS,
+                    "More than MAX_N_FRAC_DIGITS fractional decimal digits \
+                     requested."
+                );
+                Self {
+                    coeff,
+                    n_frac_digits,
+                }
+            }
+
+            /// Coefficient of `self`.
+            #[must_use]
+            #[inline(always)]
+            pub const fn coefficient(self) -> $int {
+                self.coeff
+            }
+
+            /// Number of fractional decimal digits of `self`.
+            #[must_use]
+            #[inline(always)]
+            pub const fn n_frac_digits(self) -> u8 {
+                self.n_frac_digits
+            }
+
+            /// Returns the positional index of the most significant decimal
+            /// digit of `self`.
+            ///
+            /// Special case: for a value equal to 0 `magnitude()` returns 0.
+            #[must_use]
+            #[inline(always)]
+            pub const fn magnitude(self) -> i8 {
+                i128_magnitude(self.coeff as i128) as i8
+                    - self.n_frac_digits as i8
+            }
+
+            /// Returns true if `self` equals zero.
+            #[must_use]
+            #[inline(always)]
+            pub const fn eq_zero(self) -> bool {
+                self.coeff == 0
+            }
+
+            /// Returns true if `self` equals one.
+            #[must_use]
+            #[inline(always)]
+            pub const fn eq_one(self) -> bool {
+                Decimal::new_raw(self.coeff as i128, self.n_frac_digits)
+                    .eq_one()
+            }
+
+            /// Returns true if `self` is less than zero.
+            #[must_use]
+            #[inline(always)]
+            pub const fn is_negative(self) -> bool {
+                self.coeff < 0
+            }
+
+            /// Returns true if `self` is greater than zero.
+            #[must_use]
+            #[inline(always)]
+            pub const fn is_positive(self) -> bool {
+                self.coeff > 0
+            }
+
+            // Converts `d` into `Self`, if its coefficient - after removing
+            // trailing zeros if necessary - fits into the range of `Self`.
+            #[inline]
+            pub(crate) fn checked_from_decimal(d: Decimal) -> Option<Self> {
+                Self::checked_from_coeff(d.coefficient(), d.n_frac_digits())
+            }
+
+            // Converts the value `coeff` * 10^-`n_frac_digits` into `Self`,
+            // if `coeff` - after removing trailing zeros if necessary - fits
+            // into the range of `Self`.
+            #[allow(clippy::cast_possible_truncation)]
+            pub(crate) fn checked_from_coeff(
+                mut coeff: i128,
+                mut n_frac_digits: u8,
+            ) -> Option<Self> {
+                let in_range = |c: i128| {
+                    c >= Self::MIN.coeff as i128
+                        && c <= Self::MAX.coeff as i128
+                };
+                if !in_range(coeff) {
+                    normalize(&mut coeff, &mut n_frac_digits);
+                    if !in_range(coeff) {
+                        return None;
+                    }
+                }
+                Some(Self {
+                    coeff: coeff as $int,
+                    n_frac_digits,
+                })
+            }
+
+            // Returns the result of `op` as `Self`, where `op(n)` must give
+            // the coefficient and the number of fractional digits of the
+            // result rounded to at most `n` fractional digits, or `None` if
+            // that coefficient exceeds the range of `i128`. Starting with
+            // `n_frac_digits`, the number of fractional digits is reduced
+            // until the result fits into `Self`.
+            pub(crate) fn checked_fit<F>(
+                mut n_frac_digits: u8,
+                op: F,
+            ) -> Option<Self>
+            where
+                F: Fn(u8) -> Option<(i128, u8)>,
+            {
+                loop {
+                    let n_excess = match op(n_frac_digits) {
+                        Some((coeff, n)) => {
+                            let res = Self::checked_from_coeff(coeff, n);
+                            if res.is_some() {
+                                return res;
+                            }
+                            // Removing a fractional digit removes at most one
+                            // digit from the coefficient.
+                            n_frac_digits = n;
+                            i128_magnitude(coeff)
+                                .saturating_sub(i128_magnitude(
+                                    Self::MAX.coeff as i128,
+                                ))
+                                .max(1)
+                        }
+                        None => 1,
+                    };
+                    n_frac_digits = n_frac_digits.checked_sub(n_excess)?;
+                }
+            }
+
+            /// Additive identity
+            pub const ZERO: Self = Self {
+                coeff: 0,
+                n_frac_digits: 0,
+            };
+
+            /// Multiplicative identity
+            pub const ONE: Self = Self {
+                coeff: 1,
+                n_frac_digits: 0,
+            };
+
+            /// Multiplicative negator
+            pub const NEG_ONE: Self = Self {
+                coeff: -1,
+                n_frac_digits: 0,
+            };
+
+            /// Equivalent of 2
+            pub const TWO: Self = Self {
+                coeff: 2,
+                n_frac_digits: 0,
+            };
+
+            /// Equivalent of 10
+            pub const TEN: Self = Self {
+                coeff: 10,
+                n_frac_digits: 0,
+            };
+
+            #[doc = concat!(
+                "Maximum value representable by `", stringify!($name),
+                "` = ", $max_doc
+            )]
+            pub const MAX: Self = Self {
+                coeff: $int::MAX,
+                n_frac_digits: 0,
+            };
+
+            #[doc = concat!(
+                "Minimum value representable by `", stringify!($name),
+                "` = ", $min_doc
+            )]
+            pub const MIN: Self = Self {
+                coeff: $int::MIN + 1,
+                n_frac_digits: 0,
+            };
+
+            #[doc = concat!(
+                "Smallest absolute difference between two non-equal values ",
+                "of `", stringify!($name), "`"
+            )]
+            pub const DELTA: Self = Self {
+                coeff: 1,
+                n_frac_digits: MAX_N_FRAC_DIGITS,
+            };
+        }
+
+        impl From<$name> for Decimal {
+            #[inline]
+            fn from(d: $name) -> Self {
+                Self::new_raw(i128::from(d.coeff), d.n_frac_digits)
+            }
+        }
+
+        impl TryFrom<Decimal> for $name {
+            type Error = DecimalError;
+
+            #[doc = concat!(
+                "Converts `d` into a `", stringify!($name), "`, without ",
+                "loss of precision.\n\n",
+                "Returns `DecimalError::InternalOverflow` if `d` is out of ",
+                "the range of `", stringify!($name), "`."
+            )]
+            #[inline]
+            fn try_from(d: Decimal) -> Result<Self, Self::Error> {
+                Self::checked_from_decimal(d)
+                    .ok_or(DecimalError::InternalOverflow)
+            }
+        }
+
+        $(
+        impl From<$from_t> for $name {
+            #[inline]
+            fn from(i: $from_t) -> Self {
+                Self {
+                    coeff: $int::from(i),
+                    n_frac_digits: 0,
+                }
+            }
+        }
+        )*
+
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                if self.n_frac_digits == other.n_frac_digits {
+                    self.coeff == other.coeff
+                } else {
+                    Decimal::from(*self).eq(&Decimal::from(*other))
+                }
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                if self.n_frac_digits == other.n_frac_digits {
+                    self.coeff.cmp(&other.coeff)
+                } else {
+                    Decimal::from(*self).cmp(&Decimal::from(*other))
+                }
+            }
+        }
+
+        impl PartialEq<Decimal> for $name {
+            #[inline]
+            fn eq(&self, other: &Decimal) -> bool {
+                Decimal::from(*self).eq(other)
+            }
+        }
+
+        impl PartialEq<$name> for Decimal {
+            #[inline]
+            fn eq(&self, other: &$name) -> bool {
+                self.eq(&Self::from(*other))
+            }
+        }
+
+        impl PartialOrd<Decimal> for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+                Decimal::from(*self).partial_cmp(other)
+            }
+        }
+
+        impl PartialOrd<$name> for Decimal {
+            #[inline]
+            fn partial_cmp(&self, other: &$name) -> Option<Ordering> {
+                self.partial_cmp(&Self::from(*other))
+            }
+        }
+
+        impl Hash for $name {
+            // Must give the same hash as the equivalent `Decimal`.
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                Decimal::from(*self).hash(state);
+            }
+        }
+
+        impl fmt::Display for $name {
+            /// Formats the value using the given formatter, like the
+            /// equivalent `Decimal`.
+            #[inline]
+            fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&Decimal::from(*self), form)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(form, concat!(stringify!($name), "({})"), self)
+            }
+        }
+
+        #[cfg(feature = "serde-as-str")]
+        impl From<$name> for String {
+            #[inline]
+            fn from(d: $name) -> Self {
+                Self::from(Decimal::from(d))
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseDecimalError;
+
+            #[doc = concat!(
+                "Convert a number literal into a `", stringify!($name),
+                "`.\n\n",
+                "The literal must have the same form as accepted by ",
+                "[`Decimal::from_str`](struct.Decimal.html#method.from_str). ",
+                "Its value must be in the range of `", stringify!($name),
+                "`, otherwise `ParseDecimalError::InternalOverflow` is ",
+                "returned."
+            )]
+            fn from_str(lit: &str) -> Result<Self, Self::Err> {
+                Self::checked_from_decimal(Decimal::from_str(lit)?)
+                    .ok_or(ParseDecimalError::InternalOverflow)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = ParseDecimalError;
+
+            #[inline]
+            fn try_from(lit: &str) -> Result<Self, Self::Error> {
+                Self::from_str(lit)
+            }
+        }
+
+        #[cfg(feature = "serde-as-str")]
+        impl TryFrom<String> for $name {
+            type Error = ParseDecimalError;
+
+            #[inline]
+            fn try_from(lit: String) -> Result<Self, Self::Error> {
+                Self::from_str(lit.as_str())
+            }
+        }
+
+        impl Round for $name {
+            #[doc = concat!(
+                "Returns a new `", stringify!($name), "` with its value ",
+                "rounded to `n_frac_digits` fractional digits according to ",
+                "the current [RoundingMode].\n\n",
+                "# Panics\n\n",
+                "Panics if the resulting value can not be represented by `",
+                stringify!($name), "`!"
+            )]
+            #[inline]
+            fn round(self, n_frac_digits: i8) -> Self {
+                self.round_with_mode(n_frac_digits, RoundingMode::default())
+            }
+
+            #[doc = concat!(
+                "Returns a new `", stringify!($name), "` with its value ",
+                "rounded to `n_frac_digits` fractional digits according to ",
+                "the current [RoundingMode], wrapped in `Option::Some`, or ",
+                "`Option::None` if the result can not be represented by `",
+                stringify!($name), "`."
+            )]
+            #[inline]
+            fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+                self.checked_round_with_mode(
+                    n_frac_digits,
+                    RoundingMode::default(),
+                )
+            }
+
+            #[doc = concat!(
+                "Returns a new `", stringify!($name), "` with its value ",
+                "rounded to `n_frac_digits` fractional digits according to ",
+                "`mode`.\n\n",
+                "# Panics\n\n",
+                "Panics if the resulting value can not be represented by `",
+                stringify!($name), "`!"
+            )]
+            fn round_with_mode(
+                self,
+                n_frac_digits: i8,
+                mode: RoundingMode,
+            ) -> Self {
+                match self.checked_round_with_mode(n_frac_digits, mode) {
+                    Some(d) => d,
+                    None => panic!("{}", DecimalError::InternalOverflow),
+                }
+            }
+
+            #[doc = concat!(
+                "Returns a new `", stringify!($name), "` with its value ",
+                "rounded to `n_frac_digits` fractional digits according to ",
+                "`mode`, wrapped in `Option::Some`, or `Option::None` if ",
+                "the result can not be represented by `", stringify!($name),
+                "`."
+            )]
+            fn checked_round_with_mode(
+                self,
+                n_frac_digits: i8,
+                mode: RoundingMode,
+            ) -> Option<Self> {
+                Decimal::from(self)
+                    .checked_round_with_mode(n_frac_digits, mode)
+                    .and_then(Self::checked_from_decimal)
+            }
+        }
+    };
+}
+
+define_small_decimal!(
+    /// Represents a decimal number as a coefficient (`i64`) combined with a
+    /// value (`u8`) specifying the number of fractional decimal digits.
+    ///
+    /// `Decimal64` offers the same operations as [`Decimal`], but needs only
+    /// half of its memory. It can be widened into a `Decimal` without loss,
+    /// while narrowing a `Decimal` into a `Decimal64` may fail.
+    ///
+    /// The number of fractional digits can be in the range 0 ..
+    /// [`MAX_N_FRAC_DIGITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Decimal64};
+    /// let p = Decimal64::try_from(Dec!(17.5)).unwrap();
+    /// let q: Decimal64 = "0.25".parse().unwrap();
+    /// assert_eq!((p * q).to_string(), "4.375");
+    /// assert_eq!(Decimal::from(p - q), Dec!(17.25));
+    /// ```
+    Decimal64, i64, "2⁶³ - 1", "-2⁶³ + 1",
+    [u8, i8, u16, i16, u32, i32, i64]
+);
+
+define_small_decimal!(
+    /// Represents a decimal number as a coefficient (`i32`) combined with a
+    /// value (`u8`) specifying the number of fractional decimal digits.
+    ///
+    /// `Decimal32` offers the same operations as [`Decimal`], but needs only
+    /// a quarter of its memory. It can be widened into a `Decimal` or a
+    /// [`Decimal64`] without loss, while narrowing may fail.
+    ///
+    /// The number of fractional digits can be in the range 0 ..
+    /// [`MAX_N_FRAC_DIGITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use fpdec::{Dec, Decimal, Decimal32};
+    /// let p = Decimal32::try_from(Dec!(17.5)).unwrap();
+    /// let q: Decimal32 = "0.25".parse().unwrap();
+    /// assert_eq!((p * q).to_string(), "4.375");
+    /// assert_eq!(Decimal::from(p - q), Dec!(17.25));
+    /// ```
+    Decimal32, i32, "2³¹ - 1", "-2³¹ + 1",
+    [u8, i8, u16, i16, i32]
+);
+
+impl From<Decimal32> for Decimal64 {
+    #[inline]
+    fn from(d: Decimal32) -> Self {
+        Self {
+            coeff: i64::from(d.coeff),
+            n_frac_digits: d.n_frac_digits,
+        }
+    }
+}
+
+impl TryFrom<Decimal64> for Decimal32 {
+    type Error = DecimalError;
+
+    /// Converts `d` into a `Decimal32`, without loss of precision.
+    ///
+    /// Returns `DecimalError::InternalOverflow` if `d` is out of the range
+    /// of `Decimal32`.
+    #[inline]
+    fn try_from(d: Decimal64) -> Result<Self, Self::Error> {
+        Self::try_from(Decimal::from(d))
+    }
+}
+
+#[cfg(test)]
+mod small_decimal_tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_consts() {
+        assert_eq!(Decimal64::MAX.coefficient(), i64::MAX);
+        assert_eq!(Decimal64::MIN.coefficient(), i64::MIN + 1);
+        assert_eq!(Decimal32::MAX.coefficient(), i32::MAX);
+        assert_eq!(Decimal32::DELTA.to_string(), "0.000000000000000001");
+        assert_eq!(Decimal64::default(), Decimal64::ZERO);
+        assert!(Decimal32::ONE.eq_one());
+        assert!(Decimal64::new(1000, 3).eq_one());
+        assert!(Decimal64::NEG_ONE.is_negative());
+        assert_eq!(Decimal64::new(-123, 5).magnitude(), -3);
+    }
+
+    #[test]
+    fn test_size() {
+        assert!(
+            core::mem::size_of::<Decimal64>()
+                < core::mem::size_of::<Decimal>()
+        );
+        assert_eq!(core::mem::size_of::<Decimal32>(), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_too_many_frac_digits() {
+        let _ = Decimal64::new(1, 19);
+    }
+
+    #[test]
+    fn test_widen_narrow() {
+        let d = Dec!(-17.5);
+        let s = Decimal64::try_from(d).unwrap();
+        assert_eq!(s.coefficient(), -175);
+        assert_eq!(s.n_frac_digits(), 1);
+        assert_eq!(Decimal::from(s), d);
+        let t = Decimal32::try_from(s).unwrap();
+        assert_eq!(Decimal64::from(t), s);
+        // trailing zeros are removed if necessary
+        let d = Decimal::new_raw(9223372036854775807000, 3);
+        let s = Decimal64::try_from(d).unwrap();
+        assert_eq!(s, Decimal64::MAX);
+        assert_eq!(s.n_frac_digits(), 0);
+        let d = Decimal::from(i64::MIN);
+        assert_eq!(
+            Decimal64::try_from(d).unwrap_err(),
+            DecimalError::InternalOverflow
+        );
+        let s = Decimal64::from(3_000_000_000_u32);
+        assert_eq!(
+            Decimal32::try_from(s).unwrap_err(),
+            DecimalError::InternalOverflow
+        );
+    }
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(Decimal64::from(-7_i64).coefficient(), -7);
+        assert_eq!(
+            Decimal64::from(u32::MAX).coefficient(),
+            i64::from(u32::MAX)
+        );
+        assert_eq!(
+            Decimal32::from(u16::MAX).coefficient(),
+            i32::from(u16::MAX)
+        );
+        assert_eq!(Decimal32::from(-5_i8).n_frac_digits(), 0);
+    }
+
+    #[test]
+    fn test_eq_ord() {
+        let x = Decimal64::new(1500, 3);
+        let y = Decimal64::new(15, 1);
+        assert_eq!(x, y);
+        assert!(Decimal64::new(1501, 3) > y);
+        assert!(Decimal32::new(-1, 0) < Decimal32::new(-99, 2));
+        assert_eq!(x, Dec!(1.5));
+        assert_eq!(Dec!(1.5), x);
+        assert!(Dec!(1.4) < x);
+        assert!(x > Dec!(1.4));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash<T: Hash>(t: &T) -> u64 {
+            let mut h = DefaultHasher::new();
+            t.hash(&mut h);
+            h.finish()
+        }
+
+        let x = Decimal64::new(1500, 3);
+        assert_eq!(hash(&x), hash(&Decimal64::new(15, 1)));
+        assert_eq!(hash(&x), hash(&Dec!(1.5)));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let d = Decimal64::new(-12345, 4);
+        assert_eq!(d.to_string(), "-1.2345");
+        assert_eq!(format!("{:.2}", d), "-1.23");
+        assert_eq!(format!("{:?}", d), "Decimal64(-1.2345)");
+        let d = Decimal32::new(7, 0);
+        assert_eq!(format!("{:?}", d), "Decimal32(7)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let d = Decimal64::from_str("-38.207").unwrap();
+        assert_eq!(d.coefficient(), -38207);
+        assert_eq!(d.n_frac_digits(), 3);
+        assert_eq!(
+            Decimal32::from_str("2147483648").unwrap_err(),
+            ParseDecimalError::InternalOverflow
+        );
+        assert_eq!(
+            Decimal32::from_str("2147483647").unwrap(),
+            Decimal32::MAX
+        );
+        assert_eq!(
+            Decimal64::try_from("x").unwrap_err(),
+            ParseDecimalError::Invalid
+        );
+    }
+
+    #[test]
+    fn test_round() {
+        let d = Decimal64::new(2827093, 5);
+        assert_eq!(d.round(1), Decimal64::new(283, 1));
+        assert_eq!(d.round(-1), Decimal64::new(30, 0));
+        assert_eq!(d.checked_round(7), Some(d));
+        assert!(Decimal32::MAX.checked_round(-1).is_none());
+    }
+}
+
+#[cfg(feature = "serde-as-str")]
+#[cfg(test)]
+mod serde_json_tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let d = Decimal64::new(-170, 1);
+        let s = serde_json::to_value(d).unwrap();
+        assert_eq!(s, serde_json::json!("-17.0"));
+        let t: Decimal64 = serde_json::from_value(s).unwrap();
+        assert_eq!(t.coefficient(), -170);
+        let d = Decimal32::new(5, 2);
+        let s = serde_json::to_string(&d).unwrap();
+        let t: Decimal32 = serde_json::from_str(&s).unwrap();
+        assert_eq!(t.coefficient(), 5);
+        assert_eq!(t.n_frac_digits(), 2);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+#[cfg(test)]
+mod rkyv_tests {
+    use rkyv::{self, Deserialize};
+
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        for d in [
+            Decimal64::MIN,
+            Decimal64::ZERO,
+            Decimal64::DELTA,
+            Decimal64::new(-1234567890123, 7),
+            Decimal64::MAX,
+        ] {
+            let bytes = rkyv::to_bytes::<_, 256>(&d).unwrap();
+            let archived =
+                rkyv::check_archived_root::<Decimal64>(&bytes[..]).unwrap();
+            let t: Decimal64 =
+                archived.deserialize(&mut rkyv::Infallible).unwrap();
+            assert_eq!(t.coefficient(), d.coefficient());
+            assert_eq!(t.n_frac_digits(), d.n_frac_digits());
+        }
+    }
+}
@@ -0,0 +1,680 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub,
+    SubAssign,
+};
+
+use fpdec_core::{
+    u128_mul_u128, u256_checked_add, u256_div_rem_u128, u256_sub,
+    RoundingMode, MAX_N_FRAC_DIGITS,
+};
+
+use super::{div_ten_pow_rounded, UDecimal};
+use crate::{
+    decimal256::{u128_shifted_div_rem_code, u256_round_mag_with_mode},
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub, DecimalError,
+    DivRounded, MulRounded,
+};
+
+// Implements binary operators "&T op T", "T op &T", "&T op &T"
+// based on "T op T"
+macro_rules! forward_ref_binop_udec {
+    (impl $imp:ident, $method:ident) => {
+        impl<'a> $imp<UDecimal> for &'a UDecimal {
+            type Output = <UDecimal as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: UDecimal) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+        impl $imp<&Self> for UDecimal {
+            type Output = <Self as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &Self) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+        impl $imp<&UDecimal> for &UDecimal {
+            type Output = <UDecimal as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &UDecimal) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+// Same for ops giving rounded result.
+macro_rules! forward_ref_binop_rounded_udec {
+    (impl $imp:ident, $method:ident, $method_with_mode:ident) => {
+        impl<'a> $imp<UDecimal> for &'a UDecimal {
+            type Output = <UDecimal as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(
+                self,
+                rhs: UDecimal,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                $imp::$method(*self, rhs, n_frac_digits)
+            }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: UDecimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                $imp::$method_with_mode(*self, rhs, n_frac_digits, mode)
+            }
+        }
+        impl $imp<&Self> for UDecimal {
+            type Output = <Self as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(self, rhs: &Self, n_frac_digits: u8) -> Self::Output {
+                $imp::$method(self, *rhs, n_frac_digits)
+            }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: &Self,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                $imp::$method_with_mode(self, *rhs, n_frac_digits, mode)
+            }
+        }
+        impl $imp<&UDecimal> for &UDecimal {
+            type Output = <UDecimal as $imp>::Output;
+
+            #[inline(always)]
+            fn $method(
+                self,
+                rhs: &UDecimal,
+                n_frac_digits: u8,
+            ) -> Self::Output {
+                $imp::$method(*self, *rhs, n_frac_digits)
+            }
+
+            #[inline(always)]
+            fn $method_with_mode(
+                self,
+                rhs: &UDecimal,
+                n_frac_digits: u8,
+                mode: RoundingMode,
+            ) -> Self::Output {
+                $imp::$method_with_mode(*self, *rhs, n_frac_digits, mode)
+            }
+        }
+    };
+}
+
+// Implements "T op= T" and "T op= &T" based on "T op T"
+macro_rules! forward_op_assign_udec {
+    (impl $imp:ident, $method:ident, $base_imp:ident, $base_method:ident) => {
+        impl $imp<Self> for UDecimal {
+            #[inline(always)]
+            fn $method(&mut self, rhs: Self) {
+                *self = $base_imp::$base_method(*self, rhs);
+            }
+        }
+        impl $imp<&Self> for UDecimal {
+            #[inline(always)]
+            fn $method(&mut self, rhs: &Self) {
+                *self = $base_imp::$base_method(*self, *rhs);
+            }
+        }
+    };
+}
+
+impl UDecimal {
+    // Returns `self` * `rhs`, rounded to `n_frac_digits` according to `mode`
+    // (or the current RoundingMode, if `mode` is `None`), if the exact
+    // product has more fractional digits.
+    fn checked_mul_rounded(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: Option<RoundingMode>,
+    ) -> Option<Self> {
+        let max_n_frac_digits = self.n_frac_digits + rhs.n_frac_digits;
+        let prod = u128_mul_u128(self.coeff, rhs.coeff);
+        if n_frac_digits >= max_n_frac_digits {
+            Self::from_u256(prod.0, prod.1, max_n_frac_digits)
+        } else {
+            let (hi, lo) = div_ten_pow_rounded(
+                prod,
+                u32::from(max_n_frac_digits - n_frac_digits),
+                mode,
+            );
+            Self::from_u256(hi, lo, n_frac_digits)
+        }
+    }
+
+    // Returns `self` / `rhs`, rounded to `n_frac_digits` according to `mode`
+    // (or the current RoundingMode, if `mode` is `None`), or None if `rhs`
+    // equals zero or the result can not be represented by `UDecimal`.
+    fn checked_div_rounded(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: Option<RoundingMode>,
+    ) -> Option<Self> {
+        if rhs.eq_zero() {
+            return None;
+        }
+        // self / rhs = (x / y) * 10^-n_frac_digits
+        // where -18 <= shift <= 36
+        let shift = i32::from(n_frac_digits) + i32::from(rhs.n_frac_digits)
+            - i32::from(self.n_frac_digits);
+        let (quot, code) =
+            u128_shifted_div_rem_code(self.coeff, rhs.coeff, shift);
+        let (hi, lo) = u256_round_mag_with_mode(false, quot, code, mode)?;
+        Self::from_u256(hi, lo, n_frac_digits)
+    }
+}
+
+impl CheckedAdd<Self> for UDecimal {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_add(self, rhs: Self) -> Self::Output {
+        if self.n_frac_digits == rhs.n_frac_digits {
+            return Some(Self {
+                coeff: self.coeff.checked_add(rhs.coeff)?,
+                n_frac_digits: self.n_frac_digits,
+            });
+        }
+        let (x, y, n_frac_digits) = self.aligned_coeffs(rhs);
+        let (hi, lo) = u256_checked_add(x.0, x.1, y.0, y.1)?;
+        Self::from_u256(hi, lo, n_frac_digits)
+    }
+}
+
+forward_ref_binop_udec!(impl CheckedAdd, checked_add);
+
+impl CheckedSub<Self> for UDecimal {
+    type Output = Option<Self>;
+
+    /// Returns `self` - `rhs`, wrapped in `Option::Some`, or `Option::None`
+    /// if the result would be negative.
+    #[inline]
+    fn checked_sub(self, rhs: Self) -> Self::Output {
+        if self.n_frac_digits == rhs.n_frac_digits {
+            return Some(Self {
+                coeff: self.coeff.checked_sub(rhs.coeff)?,
+                n_frac_digits: self.n_frac_digits,
+            });
+        }
+        let (x, y, n_frac_digits) = self.aligned_coeffs(rhs);
+        if x < y {
+            return None;
+        }
+        let (hi, lo) = u256_sub(x.0, x.1, y.0, y.1);
+        Self::from_u256(hi, lo, n_frac_digits)
+    }
+}
+
+forward_ref_binop_udec!(impl CheckedSub, checked_sub);
+
+impl CheckedMul<Self> for UDecimal {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul_rounded(rhs, MAX_N_FRAC_DIGITS, None)
+    }
+}
+
+forward_ref_binop_udec!(impl CheckedMul, checked_mul);
+
+impl CheckedDiv<Self> for UDecimal {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_div(self, rhs: Self) -> Self::Output {
+        self.checked_div_rounded(rhs, MAX_N_FRAC_DIGITS, None)
+            .map(Self::normalized)
+    }
+}
+
+forward_ref_binop_udec!(impl CheckedDiv, checked_div);
+
+impl CheckedRem<Self> for UDecimal {
+    type Output = Option<Self>;
+
+    #[inline]
+    fn checked_rem(self, rhs: Self) -> Self::Output {
+        if rhs.eq_zero() {
+            return None;
+        }
+        let (x, y, n_frac_digits) = self.aligned_coeffs(rhs);
+        // At most one of x and y has been scaled beyond an u128, so for
+        // x >= y y fits into an u128, and for x < y x does.
+        let coeff = if x < y {
+            x.1
+        } else {
+            u256_div_rem_u128(x.0, x.1, y.1).1
+        };
+        Some(Self {
+            coeff,
+            n_frac_digits,
+        })
+    }
+}
+
+forward_ref_binop_udec!(impl CheckedRem, checked_rem);
+
+impl Add<Self> for UDecimal {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the result can not be represented by `UDecimal`!
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        match self.checked_add(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_udec!(impl Add, add);
+
+impl Sub<Self> for UDecimal {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is greater than `self`!
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        match self.checked_sub(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_udec!(impl Sub, sub);
+
+impl Mul<Self> for UDecimal {
+    type Output = Self;
+
+    /// Returns `self` * `rhs`, rounded to
+    /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS), if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result can not be represented by `UDecimal`!
+    fn mul(self, rhs: Self) -> Self::Output {
+        match self.checked_mul(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_udec!(impl Mul, mul);
+
+impl Div<Self> for UDecimal {
+    type Output = Self;
+
+    /// Returns `self` / `rhs`, rounded to
+    /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS), if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero or the result can not be represented by
+    /// `UDecimal`!
+    fn div(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::manual_assert)]
+        if rhs.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        match self.checked_div(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_udec!(impl Div, div);
+
+impl Rem<Self> for UDecimal {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero!
+    fn rem(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::manual_assert)]
+        if rhs.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        match self.checked_rem(rhs) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_udec!(impl Rem, rem);
+
+forward_op_assign_udec!(impl AddAssign, add_assign, Add, add);
+forward_op_assign_udec!(impl SubAssign, sub_assign, Sub, sub);
+forward_op_assign_udec!(impl MulAssign, mul_assign, Mul, mul);
+forward_op_assign_udec!(impl DivAssign, div_assign, Div, div);
+forward_op_assign_udec!(impl RemAssign, rem_assign, Rem, rem);
+
+impl MulRounded<Self> for UDecimal {
+    type Output = Self;
+
+    /// Returns `self` * `rhs`, rounded to `n_frac_digits`, according to
+    /// the current [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds
+    /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result can
+    /// not be represented by `UDecimal`!
+    #[inline]
+    fn mul_rounded(self, rhs: Self, n_frac_digits: u8) -> Self {
+        self.mul_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    /// Returns `self` * `rhs`, rounded to `n_frac_digits`, according to
+    /// `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds
+    /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result can
+    /// not be represented by `UDecimal`!
+    fn mul_rounded_with_mode(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self {
+        #[allow(clippy::manual_assert)]
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+        }
+        match self.checked_mul_rounded(rhs, n_frac_digits, Some(mode)) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_rounded_udec!(
+    impl MulRounded,
+    mul_rounded,
+    mul_rounded_with_mode
+);
+
+impl DivRounded<Self> for UDecimal {
+    type Output = Self;
+
+    /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according to
+    /// the current [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero, `n_frac_digits` exceeds
+    /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result can
+    /// not be represented by `UDecimal`!
+    #[inline]
+    fn div_rounded(self, rhs: Self, n_frac_digits: u8) -> Self {
+        self.div_rounded_with_mode(
+            rhs,
+            n_frac_digits,
+            RoundingMode::default(),
+        )
+    }
+
+    /// Returns `self` / `rhs`, rounded to `n_frac_digits`, according to
+    /// `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` equals zero, `n_frac_digits` exceeds
+    /// [`MAX_N_FRAC_DIGITS`](crate::MAX_N_FRAC_DIGITS) or the result can
+    /// not be represented by `UDecimal`!
+    fn div_rounded_with_mode(
+        self,
+        rhs: Self,
+        n_frac_digits: u8,
+        mode: RoundingMode,
+    ) -> Self {
+        #[allow(clippy::manual_assert)]
+        if rhs.eq_zero() {
+            panic!("{}", DecimalError::DivisionByZero);
+        }
+        #[allow(clippy::manual_assert)]
+        if n_frac_digits > MAX_N_FRAC_DIGITS {
+            panic!("{}", DecimalError::MaxNFracDigitsExceeded);
+        }
+        match self.checked_div_rounded(rhs, n_frac_digits, Some(mode)) {
+            Some(res) => res,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+}
+
+forward_ref_binop_rounded_udec!(
+    impl DivRounded,
+    div_rounded,
+    div_rounded_with_mode
+);
+
+#[cfg(test)]
+mod udecimal_binops_tests {
+    use super::*;
+    use crate::Round;
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    #[allow(clippy::integer_division)]
+    fn test_add_sub() {
+        let x = UDecimal::new(175, 1);
+        let y = UDecimal::new(1, 3);
+        let z = x + y;
+        assert_eq!(z.coefficient(), 17501);
+        assert_eq!(z.n_frac_digits(), 3);
+        assert_eq!((x - y).coefficient(), 17499);
+        assert_eq!(&x + y, z);
+        assert_eq!(x - &y, x - y);
+        let mut a = x;
+        a += y;
+        a -= &x;
+        assert_eq!(a, y);
+        // exceeds the range of Decimal
+        let d = UDecimal::new(u128::MAX / 2, 0);
+        assert_eq!(d + d + UDecimal::ONE, UDecimal::MAX);
+    }
+
+    #[test]
+    fn test_checked_add_sub() {
+        assert!(UDecimal::MAX.checked_add(UDecimal::ONE).is_none());
+        assert!(UDecimal::MAX.checked_add(UDecimal::DELTA).is_none());
+        assert!(UDecimal::ZERO.checked_sub(UDecimal::DELTA).is_none());
+        assert!(UDecimal::new(1, 1)
+            .checked_sub(UDecimal::new(11, 2))
+            .is_none());
+        assert_eq!(
+            UDecimal::new(1, 1).checked_sub(UDecimal::new(1, 2)),
+            Some(UDecimal::new(9, 2))
+        );
+        assert_eq!(
+            UDecimal::MAX.checked_sub(UDecimal::ONE),
+            Some(UDecimal::new(u128::MAX - 1, 0))
+        );
+        // aligned coefficient exceeds u128, but result is normalized
+        let x = UDecimal::new(u128::MAX - 5, 0);
+        assert_eq!(
+            x.checked_add(UDecimal::new(50, 1)),
+            Some(UDecimal::new(u128::MAX, 0))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_overflow() {
+        let _ = UDecimal::MAX + UDecimal::ONE;
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sub_negative() {
+        let _ = UDecimal::ONE - UDecimal::TWO;
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_mul() {
+        let x = UDecimal::new(15, 1);
+        let y = UDecimal::new(250000001, 8);
+        let z = x * y;
+        assert_eq!(z.coefficient(), 3750000015);
+        assert_eq!(z.n_frac_digits(), 9);
+        assert_eq!(&x * &y, z);
+        let mut a = y;
+        a *= x;
+        assert_eq!(a, z);
+        // more than MAX_N_FRAC_DIGITS
+        let x = UDecimal::new(1234567890123456789, 18);
+        let z = x * x;
+        assert_eq!(z.coefficient(), 1524157875323883675);
+        assert_eq!(z.n_frac_digits(), 18);
+        // exceeds the range of Decimal
+        let x = UDecimal::from(u64::MAX);
+        assert_eq!(
+            x * x,
+            UDecimal::new(u128::MAX - 2 * u128::from(u64::MAX), 0)
+        );
+        assert!(UDecimal::MAX.checked_mul(UDecimal::TWO).is_none());
+    }
+
+    #[test]
+    fn test_div() {
+        let x = UDecimal::new(17, 0);
+        let y = UDecimal::new(200, 2);
+        let z = x / y;
+        assert_eq!(z.coefficient(), 85);
+        assert_eq!(z.n_frac_digits(), 1);
+        let z = UDecimal::ONE / UDecimal::new(3, 0);
+        assert_eq!(z.coefficient(), 333333333333333333);
+        assert_eq!(z.n_frac_digits(), 18);
+        let z = UDecimal::TWO / UDecimal::new(3, 0);
+        assert_eq!(z.coefficient(), 666666666666666667);
+        let mut a = UDecimal::MAX;
+        a /= UDecimal::ONE;
+        assert_eq!(a, UDecimal::MAX);
+        assert!(UDecimal::ONE.checked_div(UDecimal::ZERO).is_none());
+        assert!(UDecimal::MAX.checked_div(UDecimal::new(5, 1)).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_by_zero() {
+        let _ = UDecimal::ONE / UDecimal::ZERO;
+    }
+
+    #[test]
+    fn test_rem() {
+        let x = UDecimal::new(175, 1);
+        let y = UDecimal::new(4, 0);
+        assert_eq!(x % y, UDecimal::new(15, 1));
+        assert_eq!(
+            UDecimal::MAX % UDecimal::new(1000, 0),
+            UDecimal::new(455, 0)
+        );
+        assert_eq!(UDecimal::MAX % UDecimal::new(7, 1), UDecimal::new(2, 1));
+        // divisor scaled
+        assert_eq!(
+            UDecimal::new(17, 0) % UDecimal::new(25, 1),
+            UDecimal::new(20, 1)
+        );
+        assert_eq!(
+            UDecimal::new(2, 0) % UDecimal::new(u128::MAX, 18),
+            UDecimal::new(2, 0)
+        );
+        assert!(x.checked_rem(UDecimal::ZERO).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::integer_division)]
+    fn test_mul_div_rounded() {
+        let x = UDecimal::new(12345, 2);
+        assert_eq!(x.mul_rounded(x, 2), UDecimal::new(1523990, 2));
+        assert_eq!(x.mul_rounded(x, 6), UDecimal::new(152399025, 4));
+        let y = UDecimal::new(7, 0);
+        assert_eq!(x.div_rounded(y, 3), UDecimal::new(17636, 3));
+        assert_eq!(
+            UDecimal::MAX.div_rounded(UDecimal::TEN, 0).coefficient(),
+            u128::MAX / 10 + 1
+        );
+        assert_eq!((&x).div_rounded(&y, 0), UDecimal::new(18, 0));
+    }
+
+    #[test]
+    #[allow(clippy::integer_division)]
+    fn test_div_rounded_scaled_divisor() {
+        let y = UDecimal::TWO;
+        // 5.1 / 2 = 2.55
+        let x = UDecimal::new(51, 1);
+        let z = x.div_rounded_with_mode(y, 0, RoundingMode::RoundHalfEven);
+        assert_eq!(z, UDecimal::new(3, 0));
+        let z = x.div_rounded_with_mode(y, 0, RoundingMode::RoundHalfDown);
+        assert_eq!(z, UDecimal::new(3, 0));
+        // 5.0 / 2 = 2.5
+        let x = UDecimal::new(50, 1);
+        let z = x.div_rounded_with_mode(y, 0, RoundingMode::RoundHalfEven);
+        assert_eq!(z, UDecimal::new(2, 0));
+        let z = x.div_rounded_with_mode(y, 0, RoundingMode::RoundHalfUp);
+        assert_eq!(z, UDecimal::new(3, 0));
+        let x = UDecimal::new(u128::MAX, 18);
+        let z = x.div_rounded_with_mode(y, 0, RoundingMode::RoundDown);
+        assert_eq!(z.coefficient(), u128::MAX / 2 / 10_u128.pow(18));
+    }
+
+    #[test]
+    fn test_mul_div_rounded_with_mode() {
+        let x = UDecimal::new(12345, 2);
+        let z = x.mul_rounded_with_mode(x, 2, RoundingMode::RoundUp);
+        assert_eq!(z, UDecimal::new(1523991, 2));
+        let y = UDecimal::new(7, 0);
+        let z = (&x).div_rounded_with_mode(&y, 3, RoundingMode::RoundDown);
+        assert_eq!(z, UDecimal::new(17635, 3));
+        let d = UDecimal::new(125, 1);
+        assert_eq!(
+            d.round_with_mode(0, RoundingMode::RoundHalfUp),
+            UDecimal::new(13, 0)
+        );
+        assert_eq!(
+            d.round_with_mode(0, RoundingMode::RoundHalfEven),
+            UDecimal::new(12, 0)
+        );
+    }
+}
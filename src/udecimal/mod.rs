@@ -0,0 +1,772 @@
+// ---------------------------------------------------------------------------
+// Copyright:   (c) 2021 ff. Michael Amrhein (michael@adrhinum.de)
+// License:     This program is part of a larger application. For license
+//              details please read the file LICENSE.TXT provided together
+//              with the application.
+// ---------------------------------------------------------------------------
+// $Source$
+// $Revision$
+
+#[cfg(feature = "serde-as-str")]
+use alloc::string::String;
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use fpdec_core::{
+    str_to_udec, ten_pow, u128_mul_u128, u256_checked_mul_u128,
+    u256_div_rem_u128, ParseDecimalError, Round, RoundingMode,
+    MAX_N_FRAC_DIGITS,
+};
+
+use crate::{
+    decimal256::{u256_div_rem_code_ten_pow, u256_round_mag_with_mode},
+    format::RevBuf,
+    Decimal, DecimalError, TryFromDecimalError,
+};
+
+mod binops;
+
+/// Represents a non-negative decimal number as a coefficient (`u128`)
+/// combined with a value (`u8`) specifying the number of fractional decimal
+/// digits.
+///
+/// `UDecimal` offers the same operations as [`Decimal`] - except negation -,
+/// but its range of values is twice as large. Subtracting a greater value
+/// from a smaller one is an error, so `checked_sub` should be used where
+/// this can happen.
+///
+/// The number of fractional digits can be in the range 0 ..
+/// [`MAX_N_FRAC_DIGITS`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use fpdec::{CheckedSub, Dec, Decimal, UDecimal};
+/// let p = UDecimal::try_from(Dec!(17.5)).unwrap();
+/// let q: UDecimal = "0.25".parse().unwrap();
+/// assert_eq!((p * q).to_string(), "4.375");
+/// assert_eq!(Decimal::try_from(p - q).unwrap(), Dec!(17.25));
+/// assert!(q.checked_sub(p).is_none());
+/// assert!(UDecimal::try_from(Dec!(-1)).is_err());
+/// ```
+#[must_use]
+#[derive(Copy, Clone, Default)]
+#[cfg_attr(
+    feature = "serde-as-str",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String"),
+    serde(try_from = "String")
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    archive(check_bytes),
+    archive_attr(derive(Copy, Clone))
+)]
+pub struct UDecimal {
+    coeff: u128,
+    n_frac_digits: u8,
+}
+
+impl UDecimal {
+    /// Creates a new `UDecimal` with the value `coeff` *
+    /// 10^-`n_frac_digits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_frac_digits` exceeds [`MAX_N_FRAC_DIGITS`]!
+    #[inline]
+    pub const fn new(coeff: u128, n_frac_digits: u8) -> Self {
+        assert!(
+            n_frac_digits <= MAX_N_FRAC_DIGITS,
+            "More than MAX_N_FRAC_DIGITS fractional decimal digits requested."
+        );
+        Self {
+            coeff,
+            n_frac_digits,
+        }
+    }
+
+    /// Coefficient of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn coefficient(self) -> u128 {
+        self.coeff
+    }
+
+    /// Number of fractional decimal digits of `self`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn n_frac_digits(self) -> u8 {
+        self.n_frac_digits
+    }
+
+    /// Returns the positional index of the most significant decimal digit of
+    /// `self`.
+    ///
+    /// Special case: for a value equal to 0 `magnitude()` returns 0.
+    #[must_use]
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_possible_wrap)]
+    pub const fn magnitude(self) -> i8 {
+        if self.coeff == 0 {
+            return -(self.n_frac_digits as i8);
+        }
+        self.coeff.ilog10() as i8 - self.n_frac_digits as i8
+    }
+
+    /// Returns true if `self` equals zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn eq_zero(self) -> bool {
+        self.coeff == 0
+    }
+
+    /// Returns true if `self` equals one.
+    #[must_use]
+    #[inline(always)]
+    pub const fn eq_one(self) -> bool {
+        self.coeff == fpdec_core::ten_pow(self.n_frac_digits) as u128
+    }
+
+    /// Returns true if `self` is greater than zero.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_positive(self) -> bool {
+        self.coeff > 0
+    }
+
+    // Converts (`hi` * 2¹²⁸ + `lo`) * 10^-`n_frac_digits` into `Self`,
+    // removing trailing zeros if the coefficient does not fit into an u128.
+    fn from_u256(
+        mut hi: u128,
+        mut lo: u128,
+        mut n_frac_digits: u8,
+    ) -> Option<Self> {
+        while hi != 0 {
+            if n_frac_digits == 0 {
+                return None;
+            }
+            let (quot, rem) = u256_div_rem_u128(hi, lo, 10);
+            if rem != 0 {
+                return None;
+            }
+            (hi, lo) = quot;
+            n_frac_digits -= 1;
+        }
+        Some(Self {
+            coeff: lo,
+            n_frac_digits,
+        })
+    }
+
+    // Returns `self` with trailing zeros of its fractional part removed.
+    const fn normalized(self) -> Self {
+        let mut coeff = self.coeff;
+        let mut n_frac_digits = self.n_frac_digits;
+        while n_frac_digits > 0 && coeff.is_multiple_of(10) {
+            coeff /= 10;
+            n_frac_digits -= 1;
+        }
+        Self {
+            coeff,
+            n_frac_digits,
+        }
+    }
+
+    // Returns the coefficient of `self` scaled to `n_frac_digits` >=
+    // `self.n_frac_digits` as (hi, lo) with hi * 2¹²⁸ + lo.
+    #[inline]
+    const fn scaled_coeff(self, n_frac_digits: u8) -> (u128, u128) {
+        if n_frac_digits == self.n_frac_digits {
+            (0, self.coeff)
+        } else {
+            // n_frac_digits - self.n_frac_digits <= 18
+            u128_mul_u128(
+                self.coeff,
+                ten_pow(n_frac_digits - self.n_frac_digits) as u128,
+            )
+        }
+    }
+
+    // Returns the coefficients of `self` and `other`, scaled to their
+    // common number of fractional digits, and that number.
+    #[inline]
+    fn aligned_coeffs(self, other: Self) -> ((u128, u128), (u128, u128), u8) {
+        let n_frac_digits = self.n_frac_digits.max(other.n_frac_digits);
+        (
+            self.scaled_coeff(n_frac_digits),
+            other.scaled_coeff(n_frac_digits),
+            n_frac_digits,
+        )
+    }
+
+    /// Additive identity
+    pub const ZERO: Self = Self {
+        coeff: 0,
+        n_frac_digits: 0,
+    };
+
+    /// Multiplicative identity
+    pub const ONE: Self = Self {
+        coeff: 1,
+        n_frac_digits: 0,
+    };
+
+    /// Equivalent of 2
+    pub const TWO: Self = Self {
+        coeff: 2,
+        n_frac_digits: 0,
+    };
+
+    /// Equivalent of 10
+    pub const TEN: Self = Self {
+        coeff: 10,
+        n_frac_digits: 0,
+    };
+
+    /// Maximum value representable by `UDecimal` = 2¹²⁸ - 1
+    pub const MAX: Self = Self {
+        coeff: u128::MAX,
+        n_frac_digits: 0,
+    };
+
+    /// Minimum value representable by `UDecimal` = 0
+    pub const MIN: Self = Self::ZERO;
+
+    /// Smallest absolute difference between two non-equal values of
+    /// `UDecimal`
+    pub const DELTA: Self = Self {
+        coeff: 1,
+        n_frac_digits: MAX_N_FRAC_DIGITS,
+    };
+}
+
+// Returns (`hi` * 2¹²⁸ + `lo`) / 10^`shift`, rounded according to `mode`
+// or, if `mode` is `None`, according to the current RoundingMode.
+// Pre-condition: shift > 0
+#[inline]
+fn div_ten_pow_rounded(
+    (hi, lo): (u128, u128),
+    shift: u32,
+    mode: Option<RoundingMode>,
+) -> (u128, u128) {
+    let (quot, code) = u256_div_rem_code_ten_pow(hi, lo, shift);
+    // quot < 2²⁵⁶ / 10 => rounding can't overflow
+    u256_round_mag_with_mode(false, quot, code, mode).unwrap_or_default()
+}
+
+impl TryFrom<Decimal> for UDecimal {
+    type Error = TryFromDecimalError;
+
+    /// Converts `d` into an `UDecimal`, without loss of precision.
+    ///
+    /// Returns `TryFromDecimalError::ValueOutOfRange` if `d` is negative.
+    #[inline]
+    #[allow(clippy::cast_sign_loss)]
+    fn try_from(d: Decimal) -> Result<Self, Self::Error> {
+        if d.is_negative() {
+            return Err(TryFromDecimalError::ValueOutOfRange);
+        }
+        Ok(Self {
+            coeff: d.coefficient() as u128,
+            n_frac_digits: d.n_frac_digits(),
+        })
+    }
+}
+
+impl TryFrom<UDecimal> for Decimal {
+    type Error = TryFromDecimalError;
+
+    /// Converts `d` into a `Decimal`, without loss of precision.
+    ///
+    /// Returns `TryFromDecimalError::ValueOutOfRange` if `d` exceeds
+    /// `Decimal::MAX`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn try_from(d: UDecimal) -> Result<Self, Self::Error> {
+        let d = if d.coeff > i128::MAX as u128 {
+            d.normalized()
+        } else {
+            d
+        };
+        if d.coeff > i128::MAX as u128 {
+            return Err(TryFromDecimalError::ValueOutOfRange);
+        }
+        Ok(Self::new_raw(d.coeff as i128, d.n_frac_digits))
+    }
+}
+
+macro_rules! impl_from_uint {
+    () => {
+        impl_from_uint!(u8, u16, u32, u64, u128);
+    };
+    ($($t:ty),*) => {
+        $(
+        impl From<$t> for UDecimal {
+            #[inline]
+            fn from(i: $t) -> Self {
+                Self {
+                    coeff: u128::from(i),
+                    n_frac_digits: 0,
+                }
+            }
+        }
+        )*
+    }
+}
+
+impl_from_uint!();
+
+impl PartialEq for UDecimal {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for UDecimal {}
+
+impl PartialOrd for UDecimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UDecimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.n_frac_digits == other.n_frac_digits {
+            return self.coeff.cmp(&other.coeff);
+        }
+        let (x, y, _) = self.aligned_coeffs(*other);
+        x.cmp(&y)
+    }
+}
+
+impl PartialEq<Decimal> for UDecimal {
+    #[inline]
+    fn eq(&self, other: &Decimal) -> bool {
+        Self::try_from(*other).is_ok_and(|d| self.eq(&d))
+    }
+}
+
+impl PartialEq<UDecimal> for Decimal {
+    #[inline]
+    fn eq(&self, other: &UDecimal) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialOrd<Decimal> for UDecimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(
+            Self::try_from(*other)
+                .map_or(Ordering::Greater, |d| self.cmp(&d)),
+        )
+    }
+}
+
+impl PartialOrd<UDecimal> for Decimal {
+    #[inline]
+    fn partial_cmp(&self, other: &UDecimal) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl Hash for UDecimal {
+    // Gives the same hash as the equivalent `Decimal`, if there is one.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match Decimal::try_from(*self) {
+            Ok(d) => d.hash(state),
+            Err(_) => {
+                let d = self.normalized();
+                (d.coeff, d.n_frac_digits).hash(state);
+            }
+        }
+    }
+}
+
+impl fmt::Display for UDecimal {
+    /// Formats the value using the given formatter.
+    ///
+    /// If the format specifies less fractional digits than
+    /// `self.n_frac_digits()`, the value gets rounded according to the
+    /// default rounding mode.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use fpdec::UDecimal;
+    /// let d = UDecimal::new(123456, 2);
+    /// assert_eq!(format!("{}", d), "1234.56");
+    /// assert_eq!(format!("{:10.1}", d), "    1234.6");
+    /// ```
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[allow(clippy::cast_possible_truncation)]
+        let prec = match form.precision() {
+            Some(prec) => prec.min(MAX_N_FRAC_DIGITS as usize) as u8,
+            None => self.n_frac_digits,
+        };
+        let n_frac_digits = prec.min(self.n_frac_digits);
+        let coeff = if prec < self.n_frac_digits {
+            // The rounded coefficient is less than self.coeff.
+            div_ten_pow_rounded(
+                (0, self.coeff),
+                u32::from(self.n_frac_digits - prec),
+                None,
+            )
+            .1
+        } else {
+            self.coeff
+        };
+        let mut buf: RevBuf = RevBuf::new();
+        if prec > 0 {
+            let divisor = ten_pow(n_frac_digits) as u128;
+            #[allow(clippy::integer_division)]
+            let (int, frac) = (coeff / divisor, coeff % divisor);
+            buf.push_zeros((prec - n_frac_digits) as usize);
+            buf.push_digits(frac, n_frac_digits as usize);
+            buf.push(b'.');
+            buf.push_digits(int, 1);
+        } else {
+            buf.push_digits(coeff, 1);
+        }
+        form.pad_integral(true, "", buf.as_str())
+    }
+}
+
+impl fmt::Debug for UDecimal {
+    fn fmt(&self, form: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(form, "UDecimal({})", self)
+    }
+}
+
+#[cfg(feature = "serde-as-str")]
+impl From<UDecimal> for String {
+    #[inline]
+    fn from(d: UDecimal) -> Self {
+        use alloc::string::ToString;
+        d.to_string()
+    }
+}
+
+impl FromStr for UDecimal {
+    type Err = ParseDecimalError;
+
+    /// Convert a number literal into an `UDecimal`.
+    ///
+    /// The literal must have the same form as accepted by
+    /// [`Decimal::from_str`](struct.Decimal.html#method.from_str), but it
+    /// must not denote a negative value, otherwise
+    /// `ParseDecimalError::Invalid` is returned.
+    ///
+    /// # Examples:
+    ///
+    /// ```rust
+    /// # use fpdec::{ParseDecimalError, UDecimal};
+    /// # use core::str::FromStr;
+    /// # fn main() -> Result<(), ParseDecimalError> {
+    /// let d = UDecimal::from_str("38.207")?;
+    /// assert_eq!(d.to_string(), "38.207");
+    /// let d = UDecimal::from_str("340282366920938463463374607431768211455")?;
+    /// assert_eq!(d, UDecimal::MAX);
+    /// assert!(UDecimal::from_str("-1").is_err());
+    /// # Ok(()) }
+    /// ```
+    fn from_str(lit: &str) -> Result<Self, Self::Err> {
+        let (coeff, exponent) = str_to_udec(lit)?;
+        if -exponent > MAX_N_FRAC_DIGITS as isize {
+            return Err(ParseDecimalError::FracDigitLimitExceeded);
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        if exponent < 0 {
+            Ok(Self {
+                coeff,
+                n_frac_digits: -exponent as u8,
+            })
+        } else {
+            // 10 ^ 39 > u128::MAX
+            u32::try_from(exponent)
+                .ok()
+                .filter(|exp| *exp <= 38)
+                .and_then(|exp| coeff.checked_mul(10_u128.pow(exp)))
+                .map(|coeff| Self {
+                    coeff,
+                    n_frac_digits: 0,
+                })
+                .ok_or(ParseDecimalError::InternalOverflow)
+        }
+    }
+}
+
+impl TryFrom<&str> for UDecimal {
+    type Error = ParseDecimalError;
+
+    #[inline]
+    fn try_from(lit: &str) -> Result<Self, Self::Error> {
+        Self::from_str(lit)
+    }
+}
+
+#[cfg(feature = "serde-as-str")]
+impl TryFrom<String> for UDecimal {
+    type Error = ParseDecimalError;
+
+    #[inline]
+    fn try_from(lit: String) -> Result<Self, Self::Error> {
+        Self::from_str(lit.as_str())
+    }
+}
+
+impl Round for UDecimal {
+    /// Returns a new `UDecimal` with its value rounded to `n_frac_digits`
+    /// fractional digits according to the current [RoundingMode].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by `UDecimal`!
+    #[inline]
+    fn round(self, n_frac_digits: i8) -> Self {
+        self.round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `UDecimal` with its value rounded to `n_frac_digits`
+    /// fractional digits according to the current [RoundingMode], wrapped in
+    /// `Option::Some`, or `Option::None` if the result can not be represented
+    /// by `UDecimal`.
+    #[inline]
+    fn checked_round(self, n_frac_digits: i8) -> Option<Self> {
+        self.checked_round_with_mode(n_frac_digits, RoundingMode::default())
+    }
+
+    /// Returns a new `UDecimal` with its value rounded to `n_frac_digits`
+    /// fractional digits according to `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting value can not be represented by `UDecimal`!
+    fn round_with_mode(self, n_frac_digits: i8, mode: RoundingMode) -> Self {
+        match self.checked_round_with_mode(n_frac_digits, mode) {
+            Some(d) => d,
+            None => panic!("{}", DecimalError::InternalOverflow),
+        }
+    }
+
+    /// Returns a new `UDecimal` with its value rounded to `n_frac_digits`
+    /// fractional digits according to `mode`, wrapped in `Option::Some`,
+    /// or `Option::None` if the result can not be represented by
+    /// `UDecimal`.
+    #[allow(clippy::cast_sign_loss)]
+    fn checked_round_with_mode(
+        self,
+        n_frac_digits: i8,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        if n_frac_digits >= self.n_frac_digits as i8 {
+            return Some(self);
+        }
+        let shift = (i16::from(self.n_frac_digits)
+            - i16::from(n_frac_digits))
+        .unsigned_abs();
+        let (mut hi, mut lo) = div_ten_pow_rounded(
+            (0, self.coeff),
+            u32::from(shift),
+            Some(mode),
+        );
+        // For n_frac_digits < 0 the result has to be shifted back.
+        let mut shift = n_frac_digits.min(0).unsigned_abs();
+        while shift > 0 && (hi != 0 || lo != 0) {
+            let step = shift.min(38);
+            (hi, lo) = u256_checked_mul_u128(hi, lo, ten_pow(step) as u128)?;
+            shift -= step;
+        }
+        Self::from_u256(hi, lo, n_frac_digits.max(0) as u8)
+    }
+}
+
+#[cfg(test)]
+mod udecimal_tests {
+    use alloc::{format, string::ToString};
+
+    use super::*;
+    use crate::Dec;
+
+    #[test]
+    fn test_consts() {
+        assert_eq!(UDecimal::MAX.coefficient(), u128::MAX);
+        assert_eq!(UDecimal::MIN, UDecimal::ZERO);
+        assert_eq!(UDecimal::default(), UDecimal::ZERO);
+        assert_eq!(UDecimal::DELTA.to_string(), "0.000000000000000001");
+        assert!(UDecimal::ONE.eq_one());
+        assert!(UDecimal::new(1000, 3).eq_one());
+        assert!(!UDecimal::TEN.eq_one());
+        assert!(UDecimal::TWO.is_positive());
+        assert!(UDecimal::ZERO.eq_zero());
+        assert_eq!(UDecimal::new(123, 5).magnitude(), -3);
+        assert_eq!(UDecimal::MAX.magnitude(), 38);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_too_many_frac_digits() {
+        let _ = UDecimal::new(1, MAX_N_FRAC_DIGITS + 1);
+    }
+
+    #[test]
+    fn test_from_into_decimal() {
+        let d = UDecimal::try_from(Dec!(17.5)).unwrap();
+        assert_eq!(d, UDecimal::new(175, 1));
+        assert_eq!(Decimal::try_from(d).unwrap(), Dec!(17.5));
+        assert_eq!(
+            UDecimal::try_from(Dec!(-0.001)).unwrap_err(),
+            TryFromDecimalError::ValueOutOfRange
+        );
+        assert_eq!(
+            UDecimal::try_from(Decimal::MAX).unwrap().coefficient(),
+            i128::MAX as u128
+        );
+        assert_eq!(
+            Decimal::try_from(UDecimal::MAX).unwrap_err(),
+            TryFromDecimalError::ValueOutOfRange
+        );
+        // coefficient exceeds i128::MAX, but normalized value fits
+        let d = UDecimal::new(200000000000000000000000000000000000000, 2);
+        assert_eq!(
+            Decimal::try_from(d).unwrap(),
+            Dec!(2000000000000000000000000000000000000)
+        );
+    }
+
+    #[test]
+    fn test_from_uint() {
+        assert_eq!(UDecimal::from(7_u8), UDecimal::new(7, 0));
+        assert_eq!(
+            UDecimal::from(u64::MAX).coefficient(),
+            u128::from(u64::MAX)
+        );
+        assert_eq!(UDecimal::from(u128::MAX), UDecimal::MAX);
+    }
+
+    #[test]
+    fn test_cmp() {
+        let x = UDecimal::new(12500, 4);
+        let y = UDecimal::new(125, 2);
+        assert_eq!(x, y);
+        assert!(UDecimal::MAX > UDecimal::new(u128::MAX, 1));
+        assert!(UDecimal::new(u128::MAX, 18) > UDecimal::from(u64::MAX));
+        assert!(UDecimal::new(3, 1) < UDecimal::new(31, 2));
+        assert_eq!(x, Dec!(1.25));
+        assert_eq!(Dec!(1.25), x);
+        assert_ne!(UDecimal::ZERO, Dec!(-0.1));
+        assert!(UDecimal::ZERO > Dec!(-0.1));
+        assert!(Dec!(-7) < UDecimal::ZERO);
+        assert!(UDecimal::MAX > Decimal::MAX);
+        assert!(Dec!(1.3) > x);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash<T: Hash>(t: &T) -> u64 {
+            let mut h = DefaultHasher::new();
+            t.hash(&mut h);
+            h.finish()
+        }
+
+        assert_eq!(
+            hash(&UDecimal::new(34, 1)),
+            hash(&UDecimal::new(3400, 3))
+        );
+        assert_eq!(hash(&UDecimal::new(34, 1)), hash(&Dec!(3.4)));
+        assert_eq!(hash(&UDecimal::MAX), hash(&UDecimal::new(u128::MAX, 0)));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let d = UDecimal::from_str("17.5").unwrap();
+        assert_eq!(d, UDecimal::new(175, 1));
+        let d = UDecimal::from_str("3.4e38").unwrap();
+        assert_eq!(d.coefficient(), 340000000000000000000000000000000000000);
+        let d = UDecimal::from_str("-0").unwrap();
+        assert_eq!(d, UDecimal::ZERO);
+        assert_eq!(
+            UDecimal::from_str("-0.5").unwrap_err(),
+            ParseDecimalError::Invalid
+        );
+        assert_eq!(
+            UDecimal::from_str("3.5e38").unwrap_err(),
+            ParseDecimalError::InternalOverflow
+        );
+        assert_eq!(
+            UDecimal::from_str("1e39").unwrap_err(),
+            ParseDecimalError::InternalOverflow
+        );
+        assert_eq!(
+            UDecimal::from_str("0.0000000000000000001").unwrap_err(),
+            ParseDecimalError::FracDigitLimitExceeded
+        );
+        assert_eq!(UDecimal::try_from("0.25").unwrap(), UDecimal::new(25, 2));
+    }
+
+    #[test]
+    #[allow(clippy::integer_division)]
+    fn test_round() {
+        let d = UDecimal::new(123456789, 4);
+        assert_eq!(d.round(2), UDecimal::new(1234568, 2));
+        assert_eq!(d.round(-2), UDecimal::new(12300, 0));
+        assert_eq!(d.round(6), d);
+        assert_eq!(UDecimal::new(25, 1).round(0), UDecimal::TWO);
+        assert_eq!(UDecimal::new(35, 1).round(0), UDecimal::new(4, 0));
+        assert!(UDecimal::MAX.checked_round(-1).is_none());
+        assert_eq!(
+            UDecimal::new(u128::MAX, 3).round(0).coefficient(),
+            u128::MAX / 1000
+        );
+        let d = UDecimal::new(5, 0);
+        assert_eq!(d.round(-100), UDecimal::ZERO);
+        assert_eq!(
+            d.checked_round_with_mode(-100, RoundingMode::RoundUp),
+            None
+        );
+        assert_eq!(
+            d.round_with_mode(-38, RoundingMode::RoundUp),
+            UDecimal::new(10_u128.pow(38), 0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_round_overflow() {
+        let _ = UDecimal::MAX.round(-2);
+    }
+
+    #[test]
+    fn test_fmt() {
+        let d = UDecimal::new(123456, 3);
+        assert_eq!(d.to_string(), "123.456");
+        assert_eq!(format!("{:.1}", d), "123.5");
+        assert_eq!(format!("{:.5}", d), "123.45600");
+        assert_eq!(format!("{:>9}", d), "  123.456");
+        assert_eq!(format!("{:+}", d), "+123.456");
+        assert_eq!(format!("{:.2}", UDecimal::new(15, 3)), "0.02");
+        assert_eq!(format!("{:.2}", UDecimal::TEN), "10.00");
+        assert_eq!(
+            UDecimal::MAX.to_string(),
+            "340282366920938463463374607431768211455"
+        );
+        let d = UDecimal::new(u128::MAX, 18);
+        assert_eq!(d.to_string(), "340282366920938463463.374607431768211455");
+        assert_eq!(format!("{:.0}", d), "340282366920938463463");
+        assert_eq!(format!("{:.2}", UDecimal::new(7, 0)), "7.00");
+        assert_eq!(format!("{:?}", UDecimal::new(5, 1)), "UDecimal(0.5)");
+    }
+}